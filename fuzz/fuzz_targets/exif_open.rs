@@ -0,0 +1,32 @@
+/*
+iview/fuzz/fuzz_targets/exif_open.rs
+
+Fuzz target for the EXIF parsers: any APP1 content must give a block or an
+error, never a panic, and its values must format; a block the writer
+produced must read back
+
+    cargo +nightly fuzz run exif_open corpus/exif_open
+
+*/
+
+#![no_main]
+
+use iview::exif_my::ExifBlock;
+use iview::exif_writer::ExifIfds;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // az APP1 tartalma "Exif\0\0" fejléccel, ahogy a JPEG-ből jön
+    if let Ok(exif) = ExifBlock::default().open(data, data.len()) {
+        let _ = exif.thumbnail_bytes();
+        let _ = exif.get_num_field("Orientation");
+        let _ = exif.ifds();
+        let _ = exif.grouped_json("");
+    }
+    // a típusos olvasó és az író: amit kiír, azt vissza is kell tudni olvasni
+    if let Some(ifds) = data.strip_prefix(b"Exif\0\0").and_then(|tiff| ExifIfds::parse(tiff).ok()) {
+        let rebuilt = ifds.to_exif();
+        assert!(ExifIfds::parse(&rebuilt[6..]).is_ok());
+        let _ = ExifBlock::default().open(&rebuilt, rebuilt.len());
+    }
+});
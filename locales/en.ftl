@@ -0,0 +1,681 @@
+# iView English messages
+#
+# key = text, { $name } is replaced by the value given to t!("key", name = ...)
+# continuation lines are indented
+
+## Errors of reading and writing images
+error-exif = EXIF error: { $error }
+warning = Warning: { $text }
+error-open = Open error: { $error }
+error-render = Render error: { $error }
+error-jxl-buffer = { $file }: bad JXL pixel buffer
+error-encode = { $format } encoding error: { $error }
+error-empty-animation = Empty animation
+error-webp-anim-encoder = Cannot create the WebP animation encoder: { $error }
+error-anim-finish = Cannot finish the animation: { $error }
+error-webp-encoder = Cannot create the WebP encoder: { $error }
+error-webp-structure = Cannot process the WebP structure: { $error }
+error-write = { $format } write error: { $error }
+error-file-write = { $format } file write error: { $error }
+error-encoder = { $format } encoder error: { $error }
+
+## LUT files
+lut-expected-values = line { $line }: expected 3 values
+lut-bad-number = line { $line }: bad number '{ $value }'
+lut-bad-keyword = line { $line }: bad { $keyword }
+lut-size-range = line { $line }: LUT_3D_SIZE { $size } out of range
+lut-1d = 1D LUTs are not supported
+lut-data-before-size = line { $line }: data before LUT_3D_SIZE
+lut-missing-size = missing LUT_3D_SIZE
+lut-entries = expected { $expected } entries, found { $found }
+lut-domain = DOMAIN_MAX must be greater than DOMAIN_MIN
+
+## EXIF parser
+exif-no-header = No EXIF header
+exif-corrupt = Corrupt EXIF header: { $reason }
+exif-alignment = invalid byte order marker
+exif-start = invalid EXIF start
+exif-first-ifd = suspicious offset of the first IFD
+exif-nesting = maximum directory nesting exceeded
+exif-dir-size = illegally sized directory
+exif-format = illegal number format { $format } for tag { $tag }
+exif-pointer = illegal value pointer for tag { $tag }
+exif-link = illegal EXIF or interop directory link
+exif-components = bad component count
+exif-truncated = reference past the end of the block
+exif-cycle = cyclic IFD link
+
+## Thumbnails
+thumbnail-cache-error = Thumbnail cache error: { $error }
+
+## Start
+start-wgpu = WGPU supported, shader mode on.
+start-glow = WGPU not available. Switching to GLOW (OpenGL) mode - CPU fallback.
+start-icon-error = Failed to load the icon
+start-virtual-gpu = Virtualized GPU detected ({ $name }), switching to Glow mode to be safe.
+start-gpu-found = GPU found: { $name }, max texture: { $size }
+gpu-no-storage-texture = Error: the GPU does not support storage textures (VirtualBox / old driver).
+gpu-init-start = The GPU state is ready, starting gpu_init ...
+gpu-init-done = GPU interface ready!
+
+## Files
+filter-images = Images
+filter-bmp = Windows bitmap
+title-save-image = iView 🔍 Save image as ...
+title-save-view = iView 🔍 Save view as ...
+saving = Saving { $file }
+error-save = Save error ({ $format }): { $error }
+warning-exif-dropped = The EXIF data could not be updated, saving without EXIF: { $error }
+warning-exif-offset-tag = EXIF tag { $tag } in { $ifd } points to data elsewhere in the file, it is left out
+title-load-lut = Load 3D LUT
+filter-cube = Adobe Cube LUT
+error-lut-load = LUT load error: { $error }
+title-export-lut = Export 3D LUT
+error-lut-export = LUT export error: { $error }
+error-loader-stopped = The loading thread stopped
+load-cancelled = Loading cancelled
+error = Error: { $error }
+error-title = iView ⚠ Error
+error-ok = OK
+
+## Command line converter
+cli-usage =
+    Usage: iview convert [options] <files or wildcards...>
+
+    Options:
+      -f, --format <ext>        output format: png jpg webp tif gif bmp jp2 j2k jxl (required)
+      -o, --out-dir <dir>       output folder (default: next to the input file)
+      -q, --quality <1-100>     JPEG / WebP / JP2 / JXL quality (JP2: 0 = lossless)
+          --lossless            lossless WebP
+      -r, --resize <factor|N%>  scale the image, e.g. 0.5 or 50%
+          --orientation <o>     0 90 180 270 fliph flipv fliph90 flipv90
+      -s, --settings <file>     ColorSettings JSON (or an iview settings.json)
+      -p, --preset <name>       apply a named color preset (from settings.json or --presets)
+          --presets <file>      exported presets JSON to look the preset up in
+          --lut <file.cube>     apply a 3D LUT
+          --lut-intensity <f>   3D LUT strength 0.0 .. 1.0
+          --original            do not apply any correction ("Save image as")
+          --all-frames          save every frame of animated GIF / WebP
+          --no-exif             do not copy EXIF data
+          --overwrite           replace existing output files
+cli-missing-value = missing value after { $option }
+cli-unknown-format = unknown format: { $value }
+cli-bad-value = bad { $option } value: { $value }
+cli-unknown-option = unknown option: { $option }
+cli-missing-format = missing --format
+cli-no-input = no input files
+cli-no-match = no files match { $pattern }
+cli-no-preset = no preset named "{ $name }" in { $file }
+cli-skipped = Skipped { $input }: { $output } already exists
+cli-cannot-open = Error: cannot open { $file }
+cli-summary = Converted: { $converted }, skipped: { $skipped }, failed: { $failed }
+
+## Main menu
+menu-file = File
+menu-options = Options
+menu-anim-stop = ⏸ Stop
+menu-anim-play = ▶ Play
+menu-anim-frame = Frame: { $frame } / { $total }
+
+## File menu
+menu-open = Open ...
+menu-reopen = Reopen
+menu-compare = Compare with ...
+menu-save = Save as ...
+menu-save-view = Save view as ...
+menu-resize = Resize for export ...
+menu-copy = Copy
+menu-copy-view = Copy view
+menu-paste = Paste
+menu-change = Change
+menu-change-view = Change view
+menu-exit = Exit
+menu-compare-clipboard = Compare with clipboard
+menu-blink-compare = Blink compare
+menu-pixel-difference = Pixel difference
+menu-close-compare = Close compare
+menu-recent = Recent Paths ...
+menu-about = About IView ...
+menu-root = Root
+menu-recent-open = Open file
+menu-recent-open-here = Open File Here ...
+menu-recent-save-here = Save Here ...
+menu-recent-save-view-here = Save View Here ...
+
+## Options menu
+menu-sort = Order of images
+menu-orientation = Orientation
+menu-channels = Channels
+menu-zoom = Zoom
+menu-presets = Presets
+menu-window = Window
+menu-background = Background
+menu-color-correction = Color correction
+menu-info = Info
+menu-undo = Undo
+menu-redo = Redo
+menu-history = History
+menu-crop = Crop / straighten
+menu-split = Before / after split
+menu-grid = Thumbnail grid
+menu-filmstrip = Filmstrip
+menu-fullscreen = Fullscreen
+menu-slideshow = Slideshow
+menu-set-pos = Set Window Position
+menu-refit-reopen = Refit at Reopen
+menu-fit-open = Fit at Open
+menu-no-correction = No Correction at Open
+menu-anim-loop = Animation Loop
+menu-use-gpu = Use Gpu
+menu-language = Language
+menu-key-bindings = Key bindings ...
+
+## Submenus
+menu-sort-name = by name
+menu-sort-ext = by extension
+menu-sort-date = by date
+menu-sort-size = by size
+menu-pos-left-up = Left Up
+menu-pos-center = Center
+menu-window-follow = Fit window to image
+menu-window-resizable = Resizable window
+menu-window-maximized = Maximized window
+menu-fit = Fit image to window
+menu-fill = Fill window
+menu-center-1-1 = Center at 1:1
+menu-shuffle = Shuffle
+menu-loop = Loop
+menu-no-transition = No transition
+menu-cross-fade = Cross-fade
+menu-slide = Slide
+menu-slideshow-stop = Stop
+menu-slideshow-start = Start
+menu-interval = s interval
+menu-transition-time = s transition
+menu-preset-save = Save current as ...
+menu-preset-delete-hint = Right click: delete
+menu-import = Import ...
+menu-export = Export ...
+menu-zoom-fit = Fit
+menu-red = Red
+menu-green = Green
+menu-blue = Blue
+menu-invert = Invert
+menu-rotate-180 = Up
+menu-rotate-right = Right
+menu-rotate-left = Left
+menu-rotate-reset = Stand
+menu-flip-h = Horizontal ↔
+menu-flip-v = Vertical ↕
+menu-bg-black = Black
+menu-bg-gray = Gray
+menu-bg-white = White
+menu-bg-green = Green
+menu-bg-dark-bright = 🏁 DarkBright
+menu-bg-green-magenta = 🏁 GreenMagenta
+menu-bg-black-brown = 🏁 BlackBrown
+
+## About
+about-title = iView 🔍 About
+about-tagline = The high-speed Rust image viewer
+about-version = Version: { $version }
+about-developed = Developed by:
+about-ai = AI Contributor:
+about-technologies = Technologies used:
+about-tech-egui = • egui & eframe (0.30) - Graphical interface
+about-tech-image = • image (0.25) - Image decoding and animation
+about-tech-tiff = • tiff (0.9) - Precision metadata management
+about-tech-png = • png (0.17) - Chunk level analysis
+about-tech-exif = • kamadak-exif - EXIF database
+about-tech-rfd = • rfd - Native file dialogs
+about-tech-serde = • serde - Configuration backup
+about-gpu = GPU interface used.
+button-cancel = Cancel
+about-made-in-rust = Made in Rust, for speed.
+
+## Save settings
+save-title = iView ⚙ Save Settings
+save-quality-jpeg = Quality (JPEG)
+save-quality-avif = Quality (1-100) (0: lossless)
+save-lossless = Lossless Compression
+save-quality-webp = Quality (WebP)
+save-quality-lossless = Quality (0 is lossless)
+save-output-size = ⇲ Output size: { $width } x { $height } px ({ $filter })
+save-preset = Preset
+save-current-view = Current view
+save-include-exif = 📝 Include EXIF metadata (+ { $bytes } bytes)
+save-animation = Animation detected:
+save-current-frame = Current Frame Only
+save-full-animation = Full Animation
+save-button = 💾 Save
+save-cancel = ❌ Cancel
+
+## Image info
+info-title = iView 🔍 Image Info
+info-file-name = Name of file:
+info-image-size = Size of image:
+info-pixels = { $width } x { $height } pixel
+info-file-size = Size of file:
+info-bytes = { $size } Byte
+info-file-time = Time of file:
+info-resolution = Resolution:
+info-created = Created:
+info-machine = Machine:
+info-geolocation = GeoLocation:
+info-map = Map:
+info-open-browser = Open in browser 🌍
+error-browser = Cannot open the browser: { $error }
+info-edited = 📝 Edited
+info-no-edit = No saved edit
+sidecar-off = Don't keep edits
+sidecar-next-to-file = Keep next to file
+sidecar-central = Keep in central store
+info-revert = ↺ Revert to original
+info-bake = 💾 Bake to new file ...
+info-exif-detail = Detail EXIF data
+info-exif-error = EXIF error
+
+## Color correction
+color-title = iView 🔍 Color Correction
+color-orientation = Orientation:
+color-rotate-left = Rotate Left (90°)
+color-rotate-right = Rotate Right (90°)
+color-flip-h = Flip Horizontal
+color-flip-v = Flip Vertical
+color-stand-up = Stand Up 🔝
+color-channels = Channels:
+color-gamma = Gamma
+color-contrast = Contrast
+color-algorithm = Color Correction Algorithm:
+color-oklab = Oklab (Natural)
+color-hsv = HSV (Classic)
+color-hue = Hue Shift
+color-saturation = Saturation
+color-brightness = Brightness
+color-sharpen = Sharpen (Amount > 0) & Blur (Amount < 0)
+color-amount = Amount
+color-reset-amount = Reset Amount
+color-radius = Radius
+color-reset-radius = Reset Radius
+color-use-transparency = Use transparency color
+color-tolerance = Tolerance
+color-transparent = Transparent Color
+color-red = Red:
+color-green = Green:
+color-blue = Blue:
+color-lut-none = none
+color-intensity = Intensity
+color-lut-load = Load ...
+color-lut-remove = Remove
+color-lut-export = Export ...
+color-lut-export-hint = Save the current correction as a .cube LUT
+color-histogram = Histogram
+color-rgb-mode = RGB Mode
+color-log-scale = Logarithmic Scale
+color-level = Level: { $level }
+color-gray = Gray
+color-reset-all = Reset All Settings
+color-show-original = Show Original (Shift+Alt)
+
+## Image view
+view-no-image = IView - No image opened
+view-drop-hint = Drag & Drop a file or select it from the File menu!
+view-choose-recent = Choose from the latest ones
+view-loading = Loading { $name } ... { $seconds } s
+view-loading-cancel = Esc: cancel
+view-before = Before
+view-after = After
+
+## History
+history-open = Open
+history-reset = Reset
+history-orientation = Orientation
+history-straighten = Straighten
+history-crop = Crop
+history-channels = Channels
+history-invert = Invert
+history-gamma = Gamma
+history-contrast = Contrast
+history-brightness = Brightness
+history-color-algorithm = Color algorithm
+history-hue = Hue
+history-saturation = Saturation
+history-sharpen = Sharpen
+history-transparency = Transparency
+history-look = Look
+history-edit = Edit
+history-many = { $first }, … ({ $count } changes)
+history-title = iView ⟲ History
+history-undo = ⟲ Undo
+history-redo = ⟳ Redo
+
+## Key bindings
+action-open = Open
+action-reopen = Reopen
+action-compare = Compare with ...
+action-save = Save as
+action-save-view = Save view as
+action-resize = Resize for export
+action-copy = Copy
+action-copy-view = Copy view
+action-paste = Paste
+action-change = Change with clipboard
+action-change-view = Change view with clipboard
+action-next = Next image
+action-previous = Previous image
+action-undo = Undo
+action-redo = Redo
+action-history = History panel
+action-toggle-red = Red channel
+action-toggle-green = Green channel
+action-toggle-blue = Blue channel
+action-invert = Invert
+action-rotate-right = Rotate right
+action-rotate-left = Rotate left
+action-rotate-1-8-0 = Rotate 180°
+action-rotate-reset = Stand to 0°
+action-flip-horizontal = Flip horizontal
+action-flip-vertical = Flip vertical
+action-color-correction = Color correction
+action-crop = Crop / straighten
+action-info = Info
+action-split-view = Before / after split
+action-split-direction = Split direction
+action-thumbnail-grid = Thumbnail grid
+action-filmstrip = Filmstrip
+action-compare-mode = Compare mode
+action-blink = Blink / heat map
+action-slideshow = Slideshow
+action-fullscreen = Fullscreen
+action-background = Background
+action-zoom-in = Zoom in
+action-zoom-out = Zoom out
+action-zoom-fit = Zoom to fit
+action-play-pause = Animation play / stop
+action-prev-frame = Animation previous frame
+action-next-frame = Animation next frame
+action-back = Cancel / close / exit
+action-zoom = Zoom { $percent }%
+action-preset = Preset { $number }
+bindings-title = iView ⌨ Key and mouse bindings
+bindings-conflict = { $binding } is already used by "{ $other }". Move it to "{ $action }"?
+bindings-reassign = Reassign
+bindings-capture = Press a key, turn the wheel or click a side button for "{ $action }"  (Esc: cancel)
+bindings-remove = Click to remove
+bindings-add = Add a binding
+bindings-default = Default
+bindings-reset-all = Reset all to defaults
+bindings-skipped = Skipping saved key binding: { $entry }
+
+## Compare
+compare-title = Compare with
+compare-no-clipboard = No image on the clipboard
+compare-file-size = File size:
+compare-dimensions = Dimensions:
+compare-difference = Difference:
+compare-area = Compared area:
+compare-psnr = PSNR:
+compare-ssim = SSIM:
+compare-error = Mean / max error:
+compare-differing = Differing pixels:
+compare-heat-map = Heat map
+compare-amplify = x amplify
+compare-sizes-differ = { $width } x { $height } (sizes differ)
+compare-pixels-of = { $count } of { $total } ({ $percent } %)
+
+## Crop
+crop-label = Crop:
+crop-free = Free
+crop-original = Original
+crop-custom = Custom
+crop-portrait = Portrait
+crop-guide-none = None
+crop-guide-thirds = Thirds
+crop-guide-golden = Golden
+crop-guide-grid = Grid
+crop-reset = Reset
+crop-done = Done
+crop-straighten = Straighten:
+crop-draw-horizon = Draw horizon
+crop-horizon-hint = Drag along a line that should be level or upright
+
+## Resize
+resize-title = iView ⇲ Resize for export
+resize-width = Width:
+resize-height = Height:
+resize-resolution = Resolution:
+resize-dpi = dpi
+resize-unit-px = px
+resize-unit-percent = %
+resize-unit-cm = cm
+resize-unit-inch = inch
+resize-keep-aspect = 🔗 Keep aspect ratio
+resize-filter = Filter
+resize-summary = { $from_width } x { $from_height } px  →  { $width } x { $height } px,  { $print_width } x { $print_height } cm at { $dpi } dpi
+resize-apply = ✔ Apply
+resize-use-zoom = Use zoom
+resize-use-zoom-hint = Save View follows the screen zoom again
+
+## Presets
+preset-import-title = Import presets
+preset-export-title = Export presets
+filter-presets = iView presets
+error-preset-import = Preset import error: { $error }
+error-preset-export = Preset export error: { $error }
+preset-save-title = iView 🎨 Save preset
+preset-name = Preset name
+preset-replace = ⚠ A preset with this name will be replaced
+
+## Sidecars
+sidecar-error = Sidecar { $path }: { $error }
+sidecar-cannot-read = { $path }: cannot read
+sidecar-remove-error = Sidecar remove error { $path }: { $error }
+sidecar-save-error = Sidecar save error: { $error }
+
+## Browser
+thumbnail-error = Thumbnail error: { $error }
+browser-empty = No images in this folder
+
+## Internal errors
+error-image-buffer = Failed to create image buffer
+gpu-no-render-state = No wgpu render state, GPU processing is not available
+
+## Lossless JPEG
+menu-lossless-save = Lossless JPEG rotate / crop
+menu-lossless-save-as = Lossless JPEG rotate / crop as ...
+menu-auto-rotate-folder = Auto-rotate folder by EXIF (lossless)
+title-lossless-save = iView 🔍 Lossless JPEG save as ...
+error-lossless = Lossless JPEG error: { $error }
+error-lossless-file = { $file }: lossless JPEG error: { $error }
+title-auto-rotate = iView 🔍 Auto-rotate folder
+lossless-rotating = Rotating { $done } / { $total }
+lossless-rotated-count = Auto-rotate done: { $count } of { $total } JPEG files rotated
+error-thumbnail = Cannot regenerate the EXIF thumbnail: { $error }
+jpeg-not-jpeg = not a JPEG file
+jpeg-truncated = truncated or corrupt JPEG
+jpeg-unsupported = unsupported JPEG (lossless, hierarchical or arithmetic coded)
+jpeg-bad-huffman = bad Huffman code in the JPEG data
+jpeg-too-small = the crop is smaller than one MCU block
+
+## EXIF editor
+info-exif-edit = Edit EXIF tags
+exif-edit-byte-order = Byte order:
+exif-edit-thumbnail = Thumbnail: { $size } bytes
+exif-edit-no-thumbnail = No thumbnail
+exif-edit-thumbnail-regenerate = Regenerate
+exif-edit-thumbnail-remove = Remove
+exif-edit-bytes = ({ $size } bytes)
+exif-edit-delete = Delete tag
+exif-edit-tag-hint = Name or 0x…
+exif-edit-value-hint = Value
+exif-edit-add = Add
+exif-edit-apply = Apply
+exif-edit-apply-hint = Use the edited EXIF when the image is saved
+exif-edit-write = Write to file
+exif-edit-write-hint = Replace the EXIF block in the JPEG file without recompressing it
+exif-edit-close = Close
+exif-bad-value = Invalid value: { $value }
+exif-unknown-tag = Unknown tag: { $tag }
+exif-too-large = The EXIF block is too large ({ $size } bytes, at most 65533)
+
+## EXIF values
+exif-search-hint = Search tags or values
+exif-copy-json = Copy as JSON
+exif-copy-json-hint = Copies the listed tags, grouped by IFD, to the clipboard
+exif-group-ifd0 = IFD0 (image)
+exif-group-exif = Exif
+exif-group-gps = GPS
+exif-group-interop = Interop
+exif-group-thumbnail = Thumbnail (IFD1)
+exif-value-bytes = ({ $size } bytes)
+exif-focal-equiv = { $focal } mm ({ $equiv } mm equiv.)
+exif-flash-none = No flash function
+exif-flash-fired = Fired
+exif-flash-not-fired = Did not fire
+exif-flash-compulsory = compulsory
+exif-flash-suppressed = suppressed
+exif-flash-auto = auto
+exif-flash-no-return = return not detected
+exif-flash-return = return detected
+exif-flash-red-eye = red-eye reduction
+exif-orientation-1 = Normal
+exif-orientation-2 = Mirrored horizontally
+exif-orientation-3 = Rotated 180°
+exif-orientation-4 = Mirrored vertically
+exif-orientation-5 = Mirrored horizontally, rotated 270° CW
+exif-orientation-6 = Rotated 90° CW
+exif-orientation-7 = Mirrored horizontally, rotated 90° CW
+exif-orientation-8 = Rotated 270° CW
+exif-unit-1 = None
+exif-unit-2 = inch
+exif-unit-3 = cm
+exif-unit-4 = mm
+exif-unit-5 = µm
+exif-compression-1 = Uncompressed
+exif-compression-6 = JPEG (old-style)
+exif-compression-7 = JPEG
+exif-compression-8 = Deflate
+exif-photometric-0 = White is zero
+exif-photometric-1 = Black is zero
+exif-photometric-2 = RGB
+exif-photometric-6 = YCbCr
+exif-ycbcr-positioning-1 = Centered
+exif-ycbcr-positioning-2 = Co-sited
+exif-exposure-program-0 = Not defined
+exif-exposure-program-1 = Manual
+exif-exposure-program-2 = Program AE
+exif-exposure-program-3 = Aperture priority
+exif-exposure-program-4 = Shutter priority
+exif-exposure-program-5 = Creative (slow speed)
+exif-exposure-program-6 = Action (high speed)
+exif-exposure-program-7 = Portrait
+exif-exposure-program-8 = Landscape
+exif-exposure-program-9 = Bulb
+exif-metering-0 = Unknown
+exif-metering-1 = Average
+exif-metering-2 = Center-weighted average
+exif-metering-3 = Spot
+exif-metering-4 = Multi-spot
+exif-metering-5 = Multi-segment
+exif-metering-6 = Partial
+exif-metering-255 = Other
+exif-light-source-0 = Unknown
+exif-light-source-1 = Daylight
+exif-light-source-2 = Fluorescent
+exif-light-source-3 = Tungsten (incandescent)
+exif-light-source-4 = Flash
+exif-light-source-9 = Fine weather
+exif-light-source-10 = Cloudy
+exif-light-source-11 = Shade
+exif-light-source-12 = Daylight fluorescent
+exif-light-source-13 = Day white fluorescent
+exif-light-source-14 = Cool white fluorescent
+exif-light-source-15 = White fluorescent
+exif-light-source-16 = Warm white fluorescent
+exif-light-source-17 = Standard light A
+exif-light-source-18 = Standard light B
+exif-light-source-19 = Standard light C
+exif-light-source-20 = D55
+exif-light-source-21 = D65
+exif-light-source-22 = D75
+exif-light-source-23 = D50
+exif-light-source-24 = ISO studio tungsten
+exif-light-source-255 = Other
+exif-color-space-1 = sRGB
+exif-color-space-2 = Adobe RGB
+exif-color-space-65535 = Uncalibrated
+exif-sensing-1 = Not defined
+exif-sensing-2 = One-chip color area
+exif-sensing-3 = Two-chip color area
+exif-sensing-4 = Three-chip color area
+exif-sensing-5 = Color sequential area
+exif-sensing-7 = Trilinear
+exif-sensing-8 = Color sequential linear
+exif-file-source-1 = Film scanner
+exif-file-source-2 = Reflection print scanner
+exif-file-source-3 = Digital camera
+exif-scene-type-1 = Directly photographed
+exif-custom-rendered-0 = Normal
+exif-custom-rendered-1 = Custom
+exif-exposure-mode-0 = Auto
+exif-exposure-mode-1 = Manual
+exif-exposure-mode-2 = Auto bracket
+exif-white-balance-0 = Auto
+exif-white-balance-1 = Manual
+exif-scene-capture-0 = Standard
+exif-scene-capture-1 = Landscape
+exif-scene-capture-2 = Portrait
+exif-scene-capture-3 = Night scene
+exif-gain-control-0 = None
+exif-gain-control-1 = Low gain up
+exif-gain-control-2 = High gain up
+exif-gain-control-3 = Low gain down
+exif-gain-control-4 = High gain down
+exif-contrast-0 = Normal
+exif-contrast-1 = Low
+exif-contrast-2 = High
+exif-saturation-0 = Normal
+exif-saturation-1 = Low
+exif-saturation-2 = High
+exif-sharpness-0 = Normal
+exif-sharpness-1 = Soft
+exif-sharpness-2 = Hard
+exif-distance-range-0 = Unknown
+exif-distance-range-1 = Macro
+exif-distance-range-2 = Close
+exif-distance-range-3 = Distant
+exif-sensitivity-type-0 = Unknown
+exif-sensitivity-type-1 = Standard output sensitivity
+exif-sensitivity-type-2 = Recommended exposure index
+exif-sensitivity-type-3 = ISO speed
+exif-sensitivity-type-4 = SOS and REI
+exif-sensitivity-type-5 = SOS and ISO speed
+exif-sensitivity-type-6 = REI and ISO speed
+exif-sensitivity-type-7 = SOS, REI and ISO speed
+exif-composite-0 = Unknown
+exif-composite-1 = Not a composite image
+exif-composite-2 = General composite image
+exif-composite-3 = Composite captured while shooting
+exif-interop-R98 = R98 (sRGB)
+exif-interop-R03 = R03 (Adobe RGB)
+exif-interop-THM = THM (thumbnail)
+exif-gps-lat-ref-N = North
+exif-gps-lat-ref-S = South
+exif-gps-lon-ref-E = East
+exif-gps-lon-ref-W = West
+exif-gps-altitude-ref-0 = Above sea level
+exif-gps-altitude-ref-1 = Below sea level
+exif-gps-status-A = Measurement active
+exif-gps-status-V = Measurement void
+exif-gps-measure-mode-2 = 2D measurement
+exif-gps-measure-mode-3 = 3D measurement
+exif-gps-speed-ref-K = km/h
+exif-gps-speed-ref-M = mph
+exif-gps-speed-ref-N = knots
+exif-gps-direction-ref-T = true north
+exif-gps-direction-ref-M = magnetic north
+exif-gps-distance-ref-K = Kilometers
+exif-gps-distance-ref-M = Miles
+exif-gps-distance-ref-N = Nautical miles
+exif-gps-differential-0 = No correction
+exif-gps-differential-1 = Differential corrected
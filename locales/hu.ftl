@@ -0,0 +1,681 @@
+# iView magyar üzenetek
+#
+# kulcs = szöveg, a { $nev } helyére a t!("kulcs", nev = ...) értéke kerül
+# a folytatósorok behúzva
+
+## Képek olvasási és írási hibái
+error-exif = EXIF hiba: { $error }
+warning = Figyelmeztetés: { $text }
+error-open = Megnyitási hiba: { $error }
+error-render = Kirajzolási hiba: { $error }
+error-jxl-buffer = { $file }: hibás JXL pixelpuffer
+error-encode = { $format } kódolási hiba: { $error }
+error-empty-animation = Üres animáció
+error-webp-anim-encoder = Hiba a WebP animációs enkóder létrehozásakor: { $error }
+error-anim-finish = Hiba az animáció lezárásakor: { $error }
+error-webp-encoder = Hiba a WebP enkóder létrehozásakor: { $error }
+error-webp-structure = Hiba a WebP struktúra feldolgozásakor: { $error }
+error-write = { $format } írási hiba: { $error }
+error-file-write = { $format } fájlírási hiba: { $error }
+error-encoder = { $format } enkóder hiba: { $error }
+
+## LUT fájlok
+lut-expected-values = { $line }. sor: 3 érték kell
+lut-bad-number = { $line }. sor: hibás szám: '{ $value }'
+lut-bad-keyword = { $line }. sor: hibás { $keyword }
+lut-size-range = { $line }. sor: a LUT_3D_SIZE { $size } a tartományon kívül esik
+lut-1d = Az 1D LUT nem támogatott
+lut-data-before-size = { $line }. sor: adat a LUT_3D_SIZE előtt
+lut-missing-size = hiányzik a LUT_3D_SIZE
+lut-entries = { $expected } bejegyzés kellene, { $found } van
+lut-domain = A DOMAIN_MAX nagyobb kell legyen a DOMAIN_MIN-nél
+
+## EXIF értelmező
+exif-no-header = Nincs EXIF fejléc
+exif-corrupt = Sérült EXIF fejléc: { $reason }
+exif-alignment = érvénytelen bájtsorrend jelölő
+exif-start = érvénytelen EXIF kezdet
+exif-first-ifd = gyanús első IFD eltolás
+exif-nesting = túl mély könyvtár egymásba ágyazás
+exif-dir-size = szabálytalan méretű könyvtár
+exif-format = szabálytalan számformátum ({ $format }) a(z) { $tag } mezőnél
+exif-pointer = szabálytalan érték mutató a(z) { $tag } mezőnél
+exif-link = szabálytalan EXIF vagy interop könyvtár hivatkozás
+exif-components = hibás komponens szám
+exif-truncated = a blokk végén túli hivatkozás
+exif-cycle = körkörös IFD hivatkozás
+
+## Bélyegképek
+thumbnail-cache-error = Bélyegkép cache hiba: { $error }
+
+## Indulás
+start-wgpu = WGPU támogatott, Shader mód bekapcsolva.
+start-glow = WGPU nem elérhető. Váltás GLOW (OpenGL) módra - CPU fallback.
+start-icon-error = Nem sikerült az ikont betölteni
+start-virtual-gpu = Virtualizált GPU detektálva ({ $name }), biztonsági okokból Glow módra váltunk.
+start-gpu-found = GPU találva: { $name }, Max Texture: { $size }
+gpu-no-storage-texture = Hiba: A GPU nem támogatja a Storage Texture-öket (VirtualBox/régi driver).
+gpu-init-start = Most már van GPU állapota, indulhat a gpu_init...
+gpu-init-done = GPU INTERFÉSZ KÉSZ!
+
+## Fájlok
+filter-images = Képek
+filter-bmp = Windows bitkép
+title-save-image = iView 🔍 Kép mentése másként ...
+title-save-view = iView 🔍 Nézet mentése másként ...
+saving = Mentés: { $file }
+error-save = Hiba a mentéskor ({ $format }): { $error }
+warning-exif-dropped = Az EXIF adatok nem frissíthetők, mentés EXIF nélkül: { $error }
+warning-exif-offset-tag = A(z) { $ifd } { $tag } EXIF tagja a fájl más részére mutat, kimarad
+title-load-lut = 3D LUT betöltése
+filter-cube = Adobe Cube LUT
+error-lut-load = LUT betöltési hiba: { $error }
+title-export-lut = 3D LUT exportálása
+error-lut-export = LUT exportálási hiba: { $error }
+error-loader-stopped = A betöltő szál leállt
+load-cancelled = A betöltés megszakítva
+error = Hiba: { $error }
+error-title = iView ⚠ Hiba
+error-ok = Rendben
+
+## Parancssori konverter
+cli-usage =
+    Használat: iview convert [kapcsolók] <fájlok vagy minták...>
+
+    Kapcsolók:
+      -f, --format <kiterj>     kimeneti formátum: png jpg webp tif gif bmp jp2 j2k jxl (kötelező)
+      -o, --out-dir <mappa>     kimeneti mappa (alapból a bemeneti fájl mellé)
+      -q, --quality <1-100>     JPEG / WebP / JP2 / JXL minőség (JP2: 0 = veszteségmentes)
+          --lossless            veszteségmentes WebP
+      -r, --resize <szorzó|N%>  átméretezés, pl. 0.5 vagy 50%
+          --orientation <á>     0 90 180 270 fliph flipv fliph90 flipv90
+      -s, --settings <fájl>     ColorSettings JSON (vagy egy iview settings.json)
+      -p, --preset <név>        egy elnevezett színpreset alkalmazása (a settings.json-ból vagy a --presets fájlból)
+          --presets <fájl>      exportált preset JSON, ebben keresi a presetet
+          --lut <fájl.cube>     3D LUT alkalmazása
+          --lut-intensity <f>   a 3D LUT erőssége 0.0 .. 1.0
+          --original            nincs semmilyen korrekció ("Kép mentése másként")
+          --all-frames          az animált GIF / WebP minden kockájának mentése
+          --no-exif             az EXIF adatok nélkül
+          --overwrite           a meglévő kimeneti fájlok felülírása
+cli-missing-value = hiányzik az érték a(z) { $option } után
+cli-unknown-format = ismeretlen formátum: { $value }
+cli-bad-value = hibás { $option } érték: { $value }
+cli-unknown-option = ismeretlen kapcsoló: { $option }
+cli-missing-format = hiányzik a --format
+cli-no-input = nincs bemeneti fájl
+cli-no-match = egy fájl sem illeszkedik erre: { $pattern }
+cli-no-preset = nincs "{ $name }" nevű preset itt: { $file }
+cli-skipped = Kihagyva: { $input }: a(z) { $output } már létezik
+cli-cannot-open = Hiba: nem nyitható meg: { $file }
+cli-summary = Konvertálva: { $converted }, kihagyva: { $skipped }, hibás: { $failed }
+
+## Főmenü
+menu-file = Fájl
+menu-options = Beállítások
+menu-anim-stop = ⏸ Állj
+menu-anim-play = ▶ Lejátszás
+menu-anim-frame = Kocka: { $frame } / { $total }
+
+## Fájl menü
+menu-open = Megnyitás ...
+menu-reopen = Újranyitás
+menu-compare = Összehasonlítás ...
+menu-save = Mentés másként ...
+menu-save-view = Nézet mentése másként ...
+menu-resize = Átméretezés exportra ...
+menu-copy = Másolás
+menu-copy-view = Nézet másolása
+menu-paste = Beillesztés
+menu-change = Csere
+menu-change-view = Nézet cseréje
+menu-exit = Kilépés
+menu-compare-clipboard = Összehasonlítás a vágólappal
+menu-blink-compare = Villogtató összehasonlítás
+menu-pixel-difference = Pixel különbség
+menu-close-compare = Összehasonlítás bezárása
+menu-recent = Legutóbbi helyek ...
+menu-about = Az IView-ról ...
+menu-root = Gyökér
+menu-recent-open = Fájl megnyitása
+menu-recent-open-here = Megnyitás innen ...
+menu-recent-save-here = Mentés ide ...
+menu-recent-save-view-here = Nézet mentése ide ...
+
+## Beállítások menü
+menu-sort = Képek sorrendje
+menu-orientation = Állás
+menu-channels = Csatornák
+menu-zoom = Nagyítás
+menu-presets = Presetek
+menu-window = Ablak
+menu-background = Háttér
+menu-color-correction = Színkorrekció
+menu-info = Információ
+menu-undo = Visszavonás
+menu-redo = Újra
+menu-history = Előzmények
+menu-crop = Kivágás / kiegyenesítés
+menu-split = Előtte / utána osztott nézet
+menu-grid = Bélyegkép rács
+menu-filmstrip = Filmszalag
+menu-fullscreen = Teljes képernyő
+menu-slideshow = Diavetítés
+menu-set-pos = Ablak pozíció beállítása
+menu-refit-reopen = Újraillesztés újranyitáskor
+menu-fit-open = Illesztés megnyitáskor
+menu-no-correction = Nincs korrekció megnyitáskor
+menu-anim-loop = Animáció ismétlése
+menu-use-gpu = GPU használata
+menu-language = Nyelv
+menu-key-bindings = Billentyűk ...
+
+## Almenük
+menu-sort-name = név szerint
+menu-sort-ext = kiterjesztés szerint
+menu-sort-date = dátum szerint
+menu-sort-size = méret szerint
+menu-pos-left-up = Bal felső sarok
+menu-pos-center = Középen
+menu-window-follow = Ablak a képhez igazítva
+menu-window-resizable = Átméretezhető ablak
+menu-window-maximized = Teljes méretű ablak
+menu-fit = Kép az ablakhoz igazítva
+menu-fill = Ablak kitöltése
+menu-center-1-1 = Középen 1:1 méretben
+menu-shuffle = Véletlen sorrend
+menu-loop = Ismétlés
+menu-no-transition = Nincs áttűnés
+menu-cross-fade = Áttűnés
+menu-slide = Becsúszás
+menu-slideshow-stop = Leállítás
+menu-slideshow-start = Indítás
+menu-interval = mp időköz
+menu-transition-time = mp áttűnés
+menu-preset-save = Mentés új presetként ...
+menu-preset-delete-hint = Jobb kattintás: törlés
+menu-import = Importálás ...
+menu-export = Exportálás ...
+menu-zoom-fit = Illesztés
+menu-red = Vörös
+menu-green = Zöld
+menu-blue = Kék
+menu-invert = Invertálás
+menu-rotate-180 = Fejjel lefelé
+menu-rotate-right = Jobbra
+menu-rotate-left = Balra
+menu-rotate-reset = Álló
+menu-flip-h = Vízszintes tükrözés ↔
+menu-flip-v = Függőleges tükrözés ↕
+menu-bg-black = Fekete
+menu-bg-gray = Szürke
+menu-bg-white = Fehér
+menu-bg-green = Zöld
+menu-bg-dark-bright = 🏁 Sötét-világos
+menu-bg-green-magenta = 🏁 Zöld-bíbor
+menu-bg-black-brown = 🏁 Fekete-barna
+
+## Névjegy
+about-title = iView 🔍 Névjegy
+about-tagline = A villámgyors Rust képnézegető
+about-version = Verzió: { $version }
+about-developed = Fejlesztő:
+about-ai = MI közreműködő:
+about-technologies = Felhasznált technológiák:
+about-tech-egui = • egui & eframe (0.30) - Grafikus felület
+about-tech-image = • image (0.25) - Képdekódolás és animáció
+about-tech-tiff = • tiff (0.9) - Pontos metaadat-kezelés
+about-tech-png = • png (0.17) - Chunk szintű elemzés
+about-tech-exif = • kamadak-exif - EXIF adatbázis
+about-tech-rfd = • rfd - Natív fájl dialógusok
+about-tech-serde = • serde - Beállítások mentése
+about-gpu = GPU felület használatban.
+button-cancel = Mégse
+about-made-in-rust = Rustban készült, a sebességért.
+
+## Mentési beállítások
+save-title = iView ⚙ Mentési beállítások
+save-quality-jpeg = Minőség (JPEG)
+save-quality-avif = Minőség (1-100) (0: veszteségmentes)
+save-lossless = Veszteségmentes tömörítés
+save-quality-webp = Minőség (WebP)
+save-quality-lossless = Minőség (0: veszteségmentes)
+save-output-size = ⇲ Kimeneti méret: { $width } x { $height } px ({ $filter })
+save-preset = Preset
+save-current-view = Aktuális nézet
+save-include-exif = 📝 EXIF metaadatok mentése (+ { $bytes } bájt)
+save-animation = Animáció:
+save-current-frame = Csak az aktuális kocka
+save-full-animation = Teljes animáció
+save-button = 💾 Mentés
+save-cancel = ❌ Mégse
+
+## Kép információ
+info-title = iView 🔍 Kép információ
+info-file-name = Fájlnév:
+info-image-size = Képméret:
+info-pixels = { $width } x { $height } pixel
+info-file-size = Fájlméret:
+info-bytes = { $size } bájt
+info-file-time = Fájl ideje:
+info-resolution = Felbontás:
+info-created = Készült:
+info-machine = Gép:
+info-geolocation = Helyszín:
+info-map = Térkép:
+info-open-browser = Megnyitás böngészőben 🌍
+error-browser = Nem sikerült megnyitni a böngészőt: { $error }
+info-edited = 📝 Szerkesztve
+info-no-edit = Nincs mentett szerkesztés
+sidecar-off = Ne őrizze meg a szerkesztést
+sidecar-next-to-file = Megőrzés a fájl mellett
+sidecar-central = Megőrzés a központi tárban
+info-revert = ↺ Vissza az eredetihez
+info-bake = 💾 Mentés új fájlba ...
+info-exif-detail = Részletes EXIF adatok
+info-exif-error = EXIF hiba
+
+## Színkorrekció
+color-title = iView 🔍 Színkorrekció
+color-orientation = Állás:
+color-rotate-left = Forgatás balra (90°)
+color-rotate-right = Forgatás jobbra (90°)
+color-flip-h = Vízszintes tükrözés
+color-flip-v = Függőleges tükrözés
+color-stand-up = Felállítás 🔝
+color-channels = Csatornák:
+color-gamma = Gamma
+color-contrast = Kontraszt
+color-algorithm = Színkorrekciós algoritmus:
+color-oklab = Oklab (természetes)
+color-hsv = HSV (klasszikus)
+color-hue = Színárnyalat eltolás
+color-saturation = Telítettség
+color-brightness = Fényerő
+color-sharpen = Élesítés (mérték > 0) és elmosás (mérték < 0)
+color-amount = Mérték
+color-reset-amount = Mérték visszaállítása
+color-radius = Sugár
+color-reset-radius = Sugár visszaállítása
+color-use-transparency = Átlátszó szín használata
+color-tolerance = Tűrés
+color-transparent = Átlátszó szín
+color-red = Vörös:
+color-green = Zöld:
+color-blue = Kék:
+color-lut-none = nincs
+color-intensity = Erősség
+color-lut-load = Betöltés ...
+color-lut-remove = Eltávolítás
+color-lut-export = Exportálás ...
+color-lut-export-hint = Az aktuális korrekció mentése .cube LUT-ként
+color-histogram = Hisztogram
+color-rgb-mode = RGB mód
+color-log-scale = Logaritmikus skála
+color-level = Szint: { $level }
+color-gray = Szürke
+color-reset-all = Minden beállítás visszaállítása
+color-show-original = Eredeti mutatása (Shift+Alt)
+
+## Képnézet
+view-no-image = IView - Nincs megnyitott kép
+view-drop-hint = Húzz ide egy fájlt, vagy válaszd ki a Fájl menüből!
+view-choose-recent = Válassz a legutóbbiak közül
+view-loading = { $name } betöltése ... { $seconds } mp
+view-loading-cancel = Esc: mégse
+view-before = Előtte
+view-after = Utána
+
+## Előzmények
+history-open = Megnyitás
+history-reset = Visszaállítás
+history-orientation = Állás
+history-straighten = Kiegyenesítés
+history-crop = Kivágás
+history-channels = Csatornák
+history-invert = Invertálás
+history-gamma = Gamma
+history-contrast = Kontraszt
+history-brightness = Fényerő
+history-color-algorithm = Színalgoritmus
+history-hue = Színárnyalat
+history-saturation = Telítettség
+history-sharpen = Élesítés
+history-transparency = Átlátszóság
+history-look = LUT
+history-edit = Szerkesztés
+history-many = { $first }, … ({ $count } változás)
+history-title = iView ⟲ Előzmények
+history-undo = ⟲ Visszavonás
+history-redo = ⟳ Újra
+
+## Billentyűk
+action-open = Megnyitás
+action-reopen = Újranyitás
+action-compare = Összehasonlítás ...
+action-save = Mentés másként
+action-save-view = Nézet mentése másként
+action-resize = Átméretezés exportra
+action-copy = Másolás
+action-copy-view = Nézet másolása
+action-paste = Beillesztés
+action-change = Csere a vágólappal
+action-change-view = Nézet cseréje a vágólappal
+action-next = Következő kép
+action-previous = Előző kép
+action-undo = Visszavonás
+action-redo = Újra
+action-history = Előzmények panel
+action-toggle-red = Vörös csatorna
+action-toggle-green = Zöld csatorna
+action-toggle-blue = Kék csatorna
+action-invert = Invertálás
+action-rotate-right = Forgatás jobbra
+action-rotate-left = Forgatás balra
+action-rotate-1-8-0 = Forgatás 180°-kal
+action-rotate-reset = Felállítás 0°-ra
+action-flip-horizontal = Vízszintes tükrözés
+action-flip-vertical = Függőleges tükrözés
+action-color-correction = Színkorrekció
+action-crop = Kivágás / kiegyenesítés
+action-info = Információ
+action-split-view = Előtte / utána osztott nézet
+action-split-direction = Osztás iránya
+action-thumbnail-grid = Bélyegkép rács
+action-filmstrip = Filmszalag
+action-compare-mode = Összehasonlítási mód
+action-blink = Villogtatás / hőtérkép
+action-slideshow = Diavetítés
+action-fullscreen = Teljes képernyő
+action-background = Háttér
+action-zoom-in = Nagyítás
+action-zoom-out = Kicsinyítés
+action-zoom-fit = Illesztés az ablakhoz
+action-play-pause = Animáció lejátszás / állj
+action-prev-frame = Animáció előző kocka
+action-next-frame = Animáció következő kocka
+action-back = Mégse / bezárás / kilépés
+action-zoom = Nagyítás { $percent }%
+action-preset = { $number }. preset
+bindings-title = iView ⌨ Billentyű- és egérhozzárendelések
+bindings-conflict = A(z) { $binding } már a(z) "{ $other }" művelethez tartozik. Áthelyezed ide: "{ $action }"?
+bindings-reassign = Áthelyezés
+bindings-capture = Nyomj meg egy billentyűt, görgess, vagy kattints egy oldalsó gombbal ehhez: "{ $action }"  (Esc: mégse)
+bindings-remove = Kattintásra törlés
+bindings-add = Új hozzárendelés
+bindings-default = Alapértelmezett
+bindings-reset-all = Minden visszaállítása alapértelmezettre
+bindings-skipped = Hibás mentett billentyűkiosztás kihagyva: { $entry }
+
+## Összehasonlítás
+compare-title = Összehasonlítás ezzel
+compare-no-clipboard = Nincs kép a vágólapon
+compare-file-size = Fájlméret:
+compare-dimensions = Méretek:
+compare-difference = Különbség:
+compare-area = Összehasonlított terület:
+compare-psnr = PSNR:
+compare-ssim = SSIM:
+compare-error = Átlagos / legnagyobb hiba:
+compare-differing = Eltérő pixelek:
+compare-heat-map = Hőtérkép
+compare-amplify = x erősítés
+compare-sizes-differ = { $width } x { $height } (eltérő méretek)
+compare-pixels-of = { $count } / { $total } ({ $percent } %)
+
+## Kivágás
+crop-label = Kivágás:
+crop-free = Szabad
+crop-original = Eredeti
+crop-custom = Egyéni
+crop-portrait = Álló
+crop-guide-none = Nincs
+crop-guide-thirds = Harmadok
+crop-guide-golden = Aranymetszés
+crop-guide-grid = Rács
+crop-reset = Visszaállítás
+crop-done = Kész
+crop-straighten = Kiegyenesítés:
+crop-draw-horizon = Horizont rajzolása
+crop-horizon-hint = Húzz végig egy vonalon, aminek vízszintesnek vagy függőlegesnek kell lennie
+
+## Átméretezés
+resize-title = iView ⇲ Átméretezés exportra
+resize-width = Szélesség:
+resize-height = Magasság:
+resize-resolution = Felbontás:
+resize-dpi = dpi
+resize-unit-px = px
+resize-unit-percent = %
+resize-unit-cm = cm
+resize-unit-inch = hüvelyk
+resize-keep-aspect = 🔗 Méretarány megtartása
+resize-filter = Szűrő
+resize-summary = { $from_width } x { $from_height } px  →  { $width } x { $height } px,  { $print_width } x { $print_height } cm { $dpi } dpi-vel
+resize-apply = ✔ Alkalmaz
+resize-use-zoom = Nagyítás szerint
+resize-use-zoom-hint = A nézet mentése ismét a képernyő nagyítását követi
+
+## Presetek
+preset-import-title = Presetek importálása
+preset-export-title = Presetek exportálása
+filter-presets = iView presetek
+error-preset-import = Preset import hiba: { $error }
+error-preset-export = Preset export hiba: { $error }
+preset-save-title = iView 🎨 Preset mentése
+preset-name = Preset neve
+preset-replace = ⚠ Az ilyen nevű preset felülíródik
+
+## Sidecar fájlok
+sidecar-error = Sidecar { $path }: { $error }
+sidecar-cannot-read = { $path }: nem olvasható
+sidecar-remove-error = Sidecar törlési hiba { $path }: { $error }
+sidecar-save-error = Sidecar mentési hiba: { $error }
+
+## Böngésző
+thumbnail-error = Bélyegkép hiba: { $error }
+browser-empty = Nincs kép ebben a mappában
+
+## Belső hibák
+error-image-buffer = Nem sikerült létrehozni a képpuffert
+gpu-no-render-state = Nincs wgpu render állapot, a GPU feldolgozás nem elérhető
+
+## Veszteségmentes JPEG
+menu-lossless-save = JPEG forgatás / kivágás veszteség nélkül
+menu-lossless-save-as = JPEG forgatás / kivágás veszteség nélkül másként ...
+menu-auto-rotate-folder = Mappa kiegyenesítése EXIF szerint (veszteség nélkül)
+title-lossless-save = iView 🔍 Veszteségmentes JPEG mentés másként ...
+error-lossless = Veszteségmentes JPEG hiba: { $error }
+error-lossless-file = { $file }: veszteségmentes JPEG hiba: { $error }
+title-auto-rotate = iView 🔍 Mappa kiegyenesítése
+lossless-rotating = Kiegyenesítés: { $done } / { $total }
+lossless-rotated-count = Kiegyenesítés kész: { $total } JPEG fájlból { $count } elforgatva
+error-thumbnail = Nem sikerült újra előállítani az EXIF bélyegképet: { $error }
+jpeg-not-jpeg = nem JPEG fájl
+jpeg-truncated = csonka vagy hibás JPEG
+jpeg-unsupported = nem támogatott JPEG (veszteségmentes, hierarchikus vagy aritmetikai kódolású)
+jpeg-bad-huffman = hibás Huffman kód a JPEG adatokban
+jpeg-too-small = a kivágás kisebb egy MCU blokknál
+
+## EXIF szerkesztő
+info-exif-edit = EXIF tagek szerkesztése
+exif-edit-byte-order = Bájtsorrend:
+exif-edit-thumbnail = Bélyegkép: { $size } bájt
+exif-edit-no-thumbnail = Nincs bélyegkép
+exif-edit-thumbnail-regenerate = Újrakészítés
+exif-edit-thumbnail-remove = Törlés
+exif-edit-bytes = ({ $size } bájt)
+exif-edit-delete = Tag törlése
+exif-edit-tag-hint = Név vagy 0x…
+exif-edit-value-hint = Érték
+exif-edit-add = Hozzáadás
+exif-edit-apply = Alkalmaz
+exif-edit-apply-hint = A szerkesztett EXIF kerül a mentett képbe
+exif-edit-write = Írás a fájlba
+exif-edit-write-hint = Az EXIF blokk cseréje a JPEG fájlban, újratömörítés nélkül
+exif-edit-close = Bezárás
+exif-bad-value = Érvénytelen érték: { $value }
+exif-unknown-tag = Ismeretlen tag: { $tag }
+exif-too-large = Az EXIF blokk túl nagy ({ $size } bájt, legfeljebb 65533)
+
+## EXIF értékek
+exif-search-hint = Keresés a tagek és értékek között
+exif-copy-json = Másolás JSON-ként
+exif-copy-json-hint = A listázott tageket IFD-nként csoportosítva a vágólapra másolja
+exif-group-ifd0 = IFD0 (kép)
+exif-group-exif = Exif
+exif-group-gps = GPS
+exif-group-interop = Interop
+exif-group-thumbnail = Bélyegkép (IFD1)
+exif-value-bytes = ({ $size } bájt)
+exif-focal-equiv = { $focal } mm (kisfilmen { $equiv } mm)
+exif-flash-none = Nincs vaku
+exif-flash-fired = Villant
+exif-flash-not-fired = Nem villant
+exif-flash-compulsory = kényszerített
+exif-flash-suppressed = letiltva
+exif-flash-auto = automatikus
+exif-flash-no-return = visszavert fény nem észlelve
+exif-flash-return = visszavert fény észlelve
+exif-flash-red-eye = vörösszem-csökkentés
+exif-orientation-1 = Normál
+exif-orientation-2 = Vízszintesen tükrözve
+exif-orientation-3 = 180°-kal elforgatva
+exif-orientation-4 = Függőlegesen tükrözve
+exif-orientation-5 = Vízszintesen tükrözve, 270°-kal jobbra forgatva
+exif-orientation-6 = 90°-kal jobbra forgatva
+exif-orientation-7 = Vízszintesen tükrözve, 90°-kal jobbra forgatva
+exif-orientation-8 = 270°-kal jobbra forgatva
+exif-unit-1 = Nincs
+exif-unit-2 = hüvelyk
+exif-unit-3 = cm
+exif-unit-4 = mm
+exif-unit-5 = µm
+exif-compression-1 = Tömörítetlen
+exif-compression-6 = JPEG (régi)
+exif-compression-7 = JPEG
+exif-compression-8 = Deflate
+exif-photometric-0 = Fehér a nulla
+exif-photometric-1 = Fekete a nulla
+exif-photometric-2 = RGB
+exif-photometric-6 = YCbCr
+exif-ycbcr-positioning-1 = Középre
+exif-ycbcr-positioning-2 = Együtt
+exif-exposure-program-0 = Nincs megadva
+exif-exposure-program-1 = Kézi
+exif-exposure-program-2 = Program
+exif-exposure-program-3 = Rekesz előválasztás
+exif-exposure-program-4 = Záridő előválasztás
+exif-exposure-program-5 = Kreatív (hosszú záridő)
+exif-exposure-program-6 = Akció (rövid záridő)
+exif-exposure-program-7 = Portré
+exif-exposure-program-8 = Tájkép
+exif-exposure-program-9 = Bulb
+exif-metering-0 = Ismeretlen
+exif-metering-1 = Átlagoló
+exif-metering-2 = Középre súlyozott
+exif-metering-3 = Szpot
+exif-metering-4 = Többpontos
+exif-metering-5 = Mátrix
+exif-metering-6 = Részleges
+exif-metering-255 = Egyéb
+exif-light-source-0 = Ismeretlen
+exif-light-source-1 = Nappali fény
+exif-light-source-2 = Fénycső
+exif-light-source-3 = Izzólámpa
+exif-light-source-4 = Vaku
+exif-light-source-9 = Derült idő
+exif-light-source-10 = Felhős
+exif-light-source-11 = Árnyék
+exif-light-source-12 = Nappali fénycső
+exif-light-source-13 = Napfehér fénycső
+exif-light-source-14 = Hidegfehér fénycső
+exif-light-source-15 = Fehér fénycső
+exif-light-source-16 = Melegfehér fénycső
+exif-light-source-17 = A szabványfény
+exif-light-source-18 = B szabványfény
+exif-light-source-19 = C szabványfény
+exif-light-source-20 = D55
+exif-light-source-21 = D65
+exif-light-source-22 = D75
+exif-light-source-23 = D50
+exif-light-source-24 = ISO stúdió izzó
+exif-light-source-255 = Egyéb
+exif-color-space-1 = sRGB
+exif-color-space-2 = Adobe RGB
+exif-color-space-65535 = Kalibrálatlan
+exif-sensing-1 = Nincs megadva
+exif-sensing-2 = Egychipes színes
+exif-sensing-3 = Kétchipes színes
+exif-sensing-4 = Háromchipes színes
+exif-sensing-5 = Soros színes
+exif-sensing-7 = Trilineáris
+exif-sensing-8 = Soros színes lineáris
+exif-file-source-1 = Filmszkenner
+exif-file-source-2 = Papírkép szkenner
+exif-file-source-3 = Digitális fényképezőgép
+exif-scene-type-1 = Közvetlenül fényképezve
+exif-custom-rendered-0 = Normál
+exif-custom-rendered-1 = Egyéni
+exif-exposure-mode-0 = Automatikus
+exif-exposure-mode-1 = Kézi
+exif-exposure-mode-2 = Automatikus sorozat
+exif-white-balance-0 = Automatikus
+exif-white-balance-1 = Kézi
+exif-scene-capture-0 = Normál
+exif-scene-capture-1 = Tájkép
+exif-scene-capture-2 = Portré
+exif-scene-capture-3 = Éjszakai
+exif-gain-control-0 = Nincs
+exif-gain-control-1 = Kis erősítés
+exif-gain-control-2 = Nagy erősítés
+exif-gain-control-3 = Kis csillapítás
+exif-gain-control-4 = Nagy csillapítás
+exif-contrast-0 = Normál
+exif-contrast-1 = Alacsony
+exif-contrast-2 = Magas
+exif-saturation-0 = Normál
+exif-saturation-1 = Alacsony
+exif-saturation-2 = Magas
+exif-sharpness-0 = Normál
+exif-sharpness-1 = Lágy
+exif-sharpness-2 = Éles
+exif-distance-range-0 = Ismeretlen
+exif-distance-range-1 = Makró
+exif-distance-range-2 = Közeli
+exif-distance-range-3 = Távoli
+exif-sensitivity-type-0 = Ismeretlen
+exif-sensitivity-type-1 = Szabványos kimeneti érzékenység
+exif-sensitivity-type-2 = Ajánlott expozíciós index
+exif-sensitivity-type-3 = ISO érzékenység
+exif-sensitivity-type-4 = SOS és REI
+exif-sensitivity-type-5 = SOS és ISO
+exif-sensitivity-type-6 = REI és ISO
+exif-sensitivity-type-7 = SOS, REI és ISO
+exif-composite-0 = Ismeretlen
+exif-composite-1 = Nem összetett kép
+exif-composite-2 = Összetett kép
+exif-composite-3 = Fényképezéskor összetett kép
+exif-interop-R98 = R98 (sRGB)
+exif-interop-R03 = R03 (Adobe RGB)
+exif-interop-THM = THM (bélyegkép)
+exif-gps-lat-ref-N = Észak
+exif-gps-lat-ref-S = Dél
+exif-gps-lon-ref-E = Kelet
+exif-gps-lon-ref-W = Nyugat
+exif-gps-altitude-ref-0 = Tengerszint felett
+exif-gps-altitude-ref-1 = Tengerszint alatt
+exif-gps-status-A = Mérés folyamatban
+exif-gps-status-V = Érvénytelen mérés
+exif-gps-measure-mode-2 = 2D mérés
+exif-gps-measure-mode-3 = 3D mérés
+exif-gps-speed-ref-K = km/h
+exif-gps-speed-ref-M = mérföld/óra
+exif-gps-speed-ref-N = csomó
+exif-gps-direction-ref-T = földrajzi észak
+exif-gps-direction-ref-M = mágneses észak
+exif-gps-distance-ref-K = Kilométer
+exif-gps-distance-ref-M = Mérföld
+exif-gps-distance-ref-N = Tengeri mérföld
+exif-gps-differential-0 = Korrekció nélkül
+exif-gps-differential-1 = Differenciálisan korrigált
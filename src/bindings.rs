@@ -0,0 +1,685 @@
+/*
+iview/src/bindings.rs
+
+Action registry with user-configurable keyboard and mouse bindings,
+the rebinding window with conflict detection
+
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::presets::PRESET_KEYS;
+use crate::ImageViewer;
+use iview::t;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Action {
+    Open,
+    Reopen,
+    Compare,
+    Save,
+    SaveView,
+    Resize,
+    Copy,
+    CopyView,
+    Paste,
+    Change,
+    ChangeView,
+    Next,
+    Previous,
+    Undo,
+    Redo,
+    History,
+    ToggleRed,
+    ToggleGreen,
+    ToggleBlue,
+    Invert,
+    RotateRight,
+    RotateLeft,
+    Rotate180,
+    RotateReset,
+    FlipHorizontal,
+    FlipVertical,
+    ColorCorrection,
+    Crop,
+    Info,
+    SplitView,
+    SplitDirection,
+    ThumbnailGrid,
+    Filmstrip,
+    CompareMode,
+    Blink,
+    Slideshow,
+    Fullscreen,
+    Background,
+    ZoomIn,
+    ZoomOut,
+    ZoomFit,
+    Zoom(u16), // százalék
+    Preset(u8), // 0 .. 8
+    PlayPause,
+    PrevFrame,
+    NextFrame,
+    Back, // betöltés megszakítása, ablak bezárása, kilépés
+}
+
+/// Minden művelet a beállító ablak sorrendjében
+pub fn all_actions() -> Vec<Action> {
+    use Action::*;
+    let mut actions = vec![
+        Open, Reopen, Compare, Save, SaveView, Resize, Copy, CopyView, Paste, Change, ChangeView,
+        Next, Previous, Undo, Redo, History, ToggleRed, ToggleGreen, ToggleBlue, Invert,
+        RotateRight, RotateLeft, Rotate180, RotateReset, FlipHorizontal, FlipVertical,
+        ColorCorrection, Crop, Info, SplitView, SplitDirection, ThumbnailGrid, Filmstrip,
+        CompareMode, Blink, Slideshow, Fullscreen, Background, ZoomIn, ZoomOut, ZoomFit,
+    ];
+    actions.extend(ZOOM_KEYS.iter().map(|(percent, _, _)| Zoom(*percent)));
+    actions.extend((0..PRESET_KEYS.len() as u8).map(Preset));
+    actions.extend([PlayPause, PrevFrame, NextFrame, Back]);
+    actions
+}
+
+/// Nagyítás százalékban, Ctrl nélkül / Ctrl-lal és a számbillentyű
+const ZOOM_KEYS: [(u16, bool, egui::Key); 20] = [
+    (100, false, egui::Key::Num1), (200, false, egui::Key::Num2), (300, false, egui::Key::Num3),
+    (400, false, egui::Key::Num4), (500, false, egui::Key::Num5), (600, false, egui::Key::Num6),
+    (700, false, egui::Key::Num7), (800, false, egui::Key::Num8), (900, false, egui::Key::Num9),
+    (1000, false, egui::Key::Num0),
+    (80, true, egui::Key::Num1), (75, true, egui::Key::Num2), (50, true, egui::Key::Num3),
+    (45, true, egui::Key::Num4), (40, true, egui::Key::Num5), (35, true, egui::Key::Num6),
+    (30, true, egui::Key::Num7), (25, true, egui::Key::Num8), (20, true, egui::Key::Num9),
+    (10, true, egui::Key::Num0),
+];
+
+impl Action {
+    pub fn label(&self) -> String {
+        match self {
+            Action::Open => t!("action-open"),
+            Action::Reopen => t!("action-reopen"),
+            Action::Compare => t!("action-compare"),
+            Action::Save => t!("action-save"),
+            Action::SaveView => t!("action-save-view"),
+            Action::Resize => t!("action-resize"),
+            Action::Copy => t!("action-copy"),
+            Action::CopyView => t!("action-copy-view"),
+            Action::Paste => t!("action-paste"),
+            Action::Change => t!("action-change"),
+            Action::ChangeView => t!("action-change-view"),
+            Action::Next => t!("action-next"),
+            Action::Previous => t!("action-previous"),
+            Action::Undo => t!("action-undo"),
+            Action::Redo => t!("action-redo"),
+            Action::History => t!("action-history"),
+            Action::ToggleRed => t!("action-toggle-red"),
+            Action::ToggleGreen => t!("action-toggle-green"),
+            Action::ToggleBlue => t!("action-toggle-blue"),
+            Action::Invert => t!("action-invert"),
+            Action::RotateRight => t!("action-rotate-right"),
+            Action::RotateLeft => t!("action-rotate-left"),
+            Action::Rotate180 => t!("action-rotate-1-8-0"),
+            Action::RotateReset => t!("action-rotate-reset"),
+            Action::FlipHorizontal => t!("action-flip-horizontal"),
+            Action::FlipVertical => t!("action-flip-vertical"),
+            Action::ColorCorrection => t!("action-color-correction"),
+            Action::Crop => t!("action-crop"),
+            Action::Info => t!("action-info"),
+            Action::SplitView => t!("action-split-view"),
+            Action::SplitDirection => t!("action-split-direction"),
+            Action::ThumbnailGrid => t!("action-thumbnail-grid"),
+            Action::Filmstrip => t!("action-filmstrip"),
+            Action::CompareMode => t!("action-compare-mode"),
+            Action::Blink => t!("action-blink"),
+            Action::Slideshow => t!("action-slideshow"),
+            Action::Fullscreen => t!("action-fullscreen"),
+            Action::Background => t!("action-background"),
+            Action::ZoomIn => t!("action-zoom-in"),
+            Action::ZoomOut => t!("action-zoom-out"),
+            Action::ZoomFit => t!("action-zoom-fit"),
+            Action::Zoom(percent) => t!("action-zoom", percent = percent),
+            Action::Preset(index) => t!("action-preset", number = index + 1),
+            Action::PlayPause => t!("action-play-pause"),
+            Action::PrevFrame => t!("action-prev-frame"),
+            Action::NextFrame => t!("action-next-frame"),
+            Action::Back => t!("action-back"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Trigger {
+    Key(egui::Key),
+    WheelUp,
+    WheelDown,
+    MiddleClick,
+    MouseBack,    // oldalsó gomb
+    MouseForward, // oldalsó gomb
+}
+
+/// Egy billentyű vagy egérművelet a módosítókkal; a beállításokban szövegként ("Ctrl+Shift+Z")
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(into = "String", try_from = "String")]
+pub struct Binding {
+    pub command: bool, // Ctrl, macOS-en Cmd
+    pub shift: bool,
+    pub alt: bool,
+    pub trigger: Trigger,
+}
+
+impl Binding {
+    fn key(modifiers: egui::Modifiers, key: egui::Key) -> Self {
+        Self::new(modifiers, Trigger::Key(key))
+    }
+
+    fn new(modifiers: egui::Modifiers, trigger: Trigger) -> Self {
+        Self { command: modifiers.command, shift: modifiers.shift, alt: modifiers.alt, trigger }
+    }
+
+    pub fn modifiers(&self) -> egui::Modifiers {
+        let mut modifiers = egui::Modifiers::NONE;
+        if self.command { modifiers |= egui::Modifiers::COMMAND; }
+        if self.shift { modifiers |= egui::Modifiers::SHIFT; }
+        if self.alt { modifiers |= egui::Modifiers::ALT; }
+        modifiers
+    }
+
+    /// A Shift a szimbólum billentyűknél nem számít (kiosztástól függően a + is Shift-es)
+    fn matches(&self, other: &Binding) -> bool {
+        let shift_free = matches!(self.trigger, Trigger::Key(egui::Key::Plus | egui::Key::Minus | egui::Key::Equals));
+        self.trigger == other.trigger && self.command == other.command && self.alt == other.alt
+            && (self.shift == other.shift || (shift_free && !self.shift))
+    }
+
+    /// A Ctrl+C / V / X helyett az egui vágólap eseményt küld, ezeket elengedéskor figyeljük
+    fn on_release(&self) -> bool {
+        self.command && matches!(self.trigger, Trigger::Key(egui::Key::C | egui::Key::V | egui::Key::X))
+    }
+
+    fn trigger_name(&self) -> String {
+        match self.trigger {
+            Trigger::Key(key) => key.name().to_string(),
+            Trigger::WheelUp => "Wheel Up".to_string(),
+            Trigger::WheelDown => "Wheel Down".to_string(),
+            Trigger::MiddleClick => "Middle Click".to_string(),
+            Trigger::MouseBack => "Mouse Back".to_string(),
+            Trigger::MouseForward => "Mouse Forward".to_string(),
+        }
+    }
+
+    /// Menükben és a beállító ablakban; billentyűnél a platform szerinti formában
+    pub fn text(&self, ctx: &egui::Context) -> String {
+        match self.trigger {
+            Trigger::Key(key) => ctx.format_shortcut(&egui::KeyboardShortcut::new(self.modifiers(), key)),
+            _ => String::from(*self),
+        }
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> String {
+        let mut text = String::new();
+        if binding.command { text.push_str("Ctrl+"); }
+        if binding.shift { text.push_str("Shift+"); }
+        if binding.alt { text.push_str("Alt+"); }
+        text.push_str(&binding.trigger_name());
+        text
+    }
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let mut binding = Binding { command: false, shift: false, alt: false, trigger: Trigger::MiddleClick };
+        let mut rest = text.as_str();
+        // a "Ctrl++" végén a + maga a billentyű
+        loop {
+            if let Some(r) = rest.strip_prefix("Ctrl+").or(rest.strip_prefix("Cmd+")) {
+                binding.command = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("Shift+") {
+                binding.shift = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("Alt+") {
+                binding.alt = true;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+        binding.trigger = match rest {
+            "Wheel Up" => Trigger::WheelUp,
+            "Wheel Down" => Trigger::WheelDown,
+            "Middle Click" => Trigger::MiddleClick,
+            "Mouse Back" => Trigger::MouseBack,
+            "Mouse Forward" => Trigger::MouseForward,
+            name => Trigger::Key(egui::Key::from_name(name).ok_or(format!("unknown key: {}", text))?),
+        };
+        Ok(binding)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ActionBinding {
+    pub action: Action,
+    pub bindings: Vec<Binding>,
+}
+
+/// A mentett eltérések elemenként: az ismeretlen művelet vagy billentyűnév kimarad,
+/// nem vész el miatta az egész beállításfájl
+pub fn deserialize_saved<'de, D>(deserializer: D) -> Result<Vec<ActionBinding>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = serde_json::Value::deserialize(deserializer)?;
+    let mut saved = Vec::new();
+    for entry in raw.as_array().into_iter().flatten() {
+        let Some(action) = entry.get("action").and_then(|a| Action::deserialize(a).ok()) else {
+            println!("{}", t!("bindings-skipped", entry = entry.to_string()));
+            continue;
+        };
+        let mut bindings = Vec::new();
+        let mut dropped = false;
+        for text in entry.get("bindings").and_then(|b| b.as_array()).into_iter().flatten() {
+            match text.as_str().map(|s| Binding::try_from(s.to_string())) {
+                Some(Ok(binding)) => bindings.push(binding),
+                _ => {
+                    println!("{}", t!("bindings-skipped", entry = text.to_string()));
+                    dropped = true;
+                }
+            }
+        }
+        // ha semmi sem maradt belőle, az alapértelmezett kiosztás marad
+        if bindings.is_empty() && dropped {
+            continue;
+        }
+        saved.push(ActionBinding { action, bindings });
+    }
+    Ok(saved)
+}
+
+fn default_bindings(action: Action) -> Vec<Binding> {
+    use egui::Key;
+    const NONE: egui::Modifiers = egui::Modifiers::NONE;
+    const SHIFT: egui::Modifiers = egui::Modifiers::SHIFT;
+    const CTRL: egui::Modifiers = egui::Modifiers::COMMAND;
+    let ctrl_shift = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+    let ctrl_alt = egui::Modifiers::COMMAND | egui::Modifiers::ALT;
+    match action {
+        Action::Open => vec![Binding::key(NONE, Key::O)],
+        Action::Reopen => vec![Binding::key(NONE, Key::R)],
+        Action::Compare => vec![Binding::key(SHIFT, Key::O)],
+        Action::Save => vec![Binding::key(NONE, Key::S)],
+        Action::SaveView => vec![Binding::key(SHIFT, Key::S)],
+        Action::Resize => vec![Binding::key(SHIFT, Key::R)],
+        Action::Copy => vec![Binding::key(CTRL, Key::C)],
+        Action::CopyView => vec![Binding::key(ctrl_shift, Key::C)],
+        Action::Paste => vec![Binding::key(CTRL, Key::V)],
+        Action::Change => vec![Binding::key(CTRL, Key::X)],
+        Action::ChangeView => vec![Binding::key(ctrl_shift, Key::X)],
+        Action::Next => vec![Binding::key(NONE, Key::N)],
+        Action::Previous => vec![Binding::key(NONE, Key::B)],
+        Action::Undo => vec![Binding::key(CTRL, Key::Z)],
+        Action::Redo => vec![Binding::key(CTRL, Key::Y), Binding::key(ctrl_shift, Key::Z)],
+        Action::History => vec![Binding::key(NONE, Key::H)],
+        Action::ToggleRed => vec![Binding::key(CTRL, Key::R)],
+        Action::ToggleGreen => vec![Binding::key(CTRL, Key::G)],
+        Action::ToggleBlue => vec![Binding::key(CTRL, Key::B)],
+        Action::Invert => vec![Binding::key(CTRL, Key::I)],
+        Action::RotateRight => vec![Binding::key(CTRL, Key::ArrowRight)],
+        Action::RotateLeft => vec![Binding::key(CTRL, Key::ArrowLeft)],
+        Action::Rotate180 => vec![Binding::key(CTRL, Key::ArrowUp)],
+        Action::RotateReset => vec![Binding::key(CTRL, Key::ArrowDown)],
+        Action::FlipHorizontal => vec![Binding::key(ctrl_alt, Key::ArrowLeft)],
+        Action::FlipVertical => vec![Binding::key(ctrl_alt, Key::ArrowUp)],
+        Action::ColorCorrection => vec![Binding::key(NONE, Key::C)],
+        Action::Crop => vec![Binding::key(SHIFT, Key::C)],
+        Action::Info => vec![Binding::key(NONE, Key::I)],
+        Action::SplitView => vec![Binding::key(NONE, Key::D)],
+        Action::SplitDirection => vec![Binding::key(SHIFT, Key::D)],
+        Action::ThumbnailGrid => vec![Binding::key(NONE, Key::T)],
+        Action::Filmstrip => vec![Binding::key(SHIFT, Key::T)],
+        Action::CompareMode => vec![Binding::key(SHIFT, Key::K)],
+        Action::Blink => vec![Binding::key(NONE, Key::K)],
+        Action::Slideshow => vec![Binding::key(NONE, Key::F5)],
+        Action::Fullscreen => vec![Binding::key(NONE, Key::F11)],
+        Action::Background => vec![Binding::key(NONE, Key::G)],
+        Action::ZoomIn => vec![Binding::key(NONE, Key::Plus), Binding::new(CTRL, Trigger::WheelUp)],
+        Action::ZoomOut => vec![Binding::key(NONE, Key::Minus), Binding::new(CTRL, Trigger::WheelDown)],
+        Action::ZoomFit => vec![Binding::key(NONE, Key::F)],
+        Action::Zoom(percent) => ZOOM_KEYS.iter()
+            .filter(|(p, _, _)| *p == percent)
+            .map(|(_, ctrl, key)| Binding::key(if *ctrl { CTRL } else { NONE }, *key))
+            .collect(),
+        Action::Preset(index) => PRESET_KEYS.get(index as usize)
+            .map(|key| vec![Binding::key(egui::Modifiers::ALT, *key)])
+            .unwrap_or_default(),
+        Action::PlayPause => vec![Binding::key(NONE, Key::Space)],
+        Action::PrevFrame => vec![Binding::key(NONE, Key::ArrowLeft)],
+        Action::NextFrame => vec![Binding::key(NONE, Key::ArrowRight)],
+        Action::Back => vec![Binding::key(NONE, Key::Escape), Binding::key(NONE, Key::Enter)],
+    }
+}
+
+/// Görgetés egységre váltva (egy kattanás egy sor)
+fn wheel_lines(unit: egui::MouseWheelUnit, delta: egui::Vec2) -> f32 {
+    match unit {
+        egui::MouseWheelUnit::Point => delta.y / 50.0,
+        egui::MouseWheelUnit::Line => delta.y,
+        egui::MouseWheelUnit::Page => delta.y * 10.0,
+    }
+}
+
+#[derive(Clone)]
+pub struct KeyBindings {
+    pub table: Vec<ActionBinding>,
+    wheel: f32, // a még el nem használt görgetés (sorban)
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            table: all_actions().into_iter()
+                .map(|action| ActionBinding { action, bindings: default_bindings(action) })
+                .collect(),
+            wheel: 0.0,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// A mentett eltérések az alapértelmezett táblára
+    pub fn from_saved(saved: &[ActionBinding]) -> Self {
+        let mut bindings = Self::default();
+        for entry in saved {
+            if let Some(row) = bindings.table.iter_mut().find(|row| row.action == entry.action) {
+                row.bindings = entry.bindings.clone();
+            }
+        }
+        bindings
+    }
+
+    /// Csak az alapértelmezettől eltérő sorok kerülnek a beállításokba
+    pub fn changed(&self) -> Vec<ActionBinding> {
+        self.table.iter()
+            .filter(|row| row.bindings != default_bindings(row.action))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.table.iter()
+            .find(|row| row.action == action)
+            .map(|row| row.bindings.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn action_for(&self, binding: &Binding) -> Option<Action> {
+        self.table.iter()
+            .find(|row| row.bindings.iter().any(|b| b.matches(binding)))
+            .map(|row| row.action)
+    }
+
+    /// Menü gomb shortcut_text-je: az első hozzárendelés
+    pub fn text(&self, ctx: &egui::Context, action: Action) -> String {
+        self.get(action).first().map(|b| b.text(ctx)).unwrap_or_default()
+    }
+
+    /// Menü címke végére: "  (H)"
+    pub fn hint(&self, ctx: &egui::Context, action: Action) -> String {
+        match self.get(action).first() {
+            Some(binding) => format!("  ({})", binding.text(ctx)),
+            None => String::new(),
+        }
+    }
+
+    /// Más művelethez is rendelt kötés
+    pub fn is_shared(&self, action: Action, binding: &Binding) -> bool {
+        self.table.iter().any(|row| row.action != action && row.bindings.iter().any(|b| b.matches(binding) || binding.matches(b)))
+    }
+
+    pub fn assign(&mut self, action: Action, binding: Binding) {
+        for row in self.table.iter_mut() {
+            if row.action == action {
+                if !row.bindings.contains(&binding) {
+                    row.bindings.push(binding);
+                }
+            } else {
+                row.bindings.retain(|b| !b.matches(&binding) && !binding.matches(b));
+            }
+        }
+    }
+
+    pub fn remove(&mut self, action: Action, index: usize) {
+        if let Some(row) = self.table.iter_mut().find(|row| row.action == action) {
+            if index < row.bindings.len() {
+                row.bindings.remove(index);
+            }
+        }
+    }
+
+    pub fn reset(&mut self, action: Action) {
+        if let Some(row) = self.table.iter_mut().find(|row| row.action == action) {
+            row.bindings = default_bindings(action);
+        }
+    }
+
+    /// A képkocka eseményeiből a kiváltott műveletek; görgetéses nagyításnál a görgetés mértékével.
+    /// A talált billentyű eseményeket elfogyasztja
+    pub fn triggered(&mut self, ctx: &egui::Context) -> Vec<(Action, Option<f32>)> {
+        let mut actions = Vec::new();
+        let table = &self.table;
+        let find = |binding: &Binding| table.iter()
+            .find(|row| row.bindings.iter().any(|b| b.matches(binding)))
+            .map(|row| row.action);
+        let wheel = &mut self.wheel;
+        ctx.input_mut(|i| {
+            i.events.retain(|event| {
+                match event {
+                    egui::Event::Key { key, pressed, modifiers, .. } => {
+                        let binding = Binding::key(*modifiers, *key);
+                        if *pressed == binding.on_release() {
+                            return true;
+                        }
+                        match find(&binding) {
+                            Some(action) => {
+                                actions.push((action, None));
+                                false
+                            }
+                            None => true,
+                        }
+                    }
+                    egui::Event::MouseWheel { unit, delta, modifiers, .. } if delta.y != 0.0 => {
+                        let trigger = if delta.y > 0.0 { Trigger::WheelUp } else { Trigger::WheelDown };
+                        if let Some(action) = find(&Binding::new(*modifiers, trigger)) {
+                            if matches!(action, Action::ZoomIn | Action::ZoomOut) {
+                                // nagyításnál a görgetés mértéke számít
+                                actions.push((action, Some(delta.y.abs())));
+                            } else {
+                                // lépésenként: egy kattanás egy művelet
+                                let lines = wheel_lines(*unit, *delta);
+                                if lines.signum() != wheel.signum() {
+                                    *wheel = 0.0;
+                                }
+                                *wheel += lines;
+                                while wheel.abs() >= 1.0 {
+                                    actions.push((action, None));
+                                    *wheel -= wheel.signum();
+                                }
+                            }
+                        }
+                        true
+                    }
+                    egui::Event::PointerButton { button, pressed: true, modifiers, .. } => {
+                        let trigger = match button {
+                            egui::PointerButton::Middle => Trigger::MiddleClick,
+                            egui::PointerButton::Extra1 => Trigger::MouseBack,
+                            egui::PointerButton::Extra2 => Trigger::MouseForward,
+                            _ => return true,
+                        };
+                        if let Some(action) = find(&Binding::new(*modifiers, trigger)) {
+                            actions.push((action, None));
+                        }
+                        true
+                    }
+                    _ => true,
+                }
+            });
+        });
+        actions
+    }
+}
+
+/// Az új hozzárendelésre váró beállító ablak állapota
+#[derive(Default)]
+pub struct BindingsDialog {
+    capturing: Option<Action>,
+    conflict: Option<(Action, Binding, Action)>, // mihez, mit, kitől venné el
+}
+
+/// Az első lenyomott billentyű, görgetés vagy egérgomb a hozzárendeléshez
+fn capture_binding(ctx: &egui::Context) -> Option<Binding> {
+    ctx.input_mut(|i| {
+        let modifiers = i.modifiers;
+        let mut captured = None;
+        i.events.retain(|event| {
+            if captured.is_some() {
+                return true;
+            }
+            captured = match event {
+                egui::Event::Key { key, pressed: true, modifiers, .. } => Some(Binding::key(*modifiers, *key)),
+                // a vágólap billentyűk helyett ezek jönnek
+                egui::Event::Copy => Some(Binding::key(modifiers, egui::Key::C)),
+                egui::Event::Cut => Some(Binding::key(modifiers, egui::Key::X)),
+                egui::Event::Paste(_) => Some(Binding::key(modifiers, egui::Key::V)),
+                egui::Event::MouseWheel { delta, modifiers, .. } if delta.y != 0.0 =>
+                    Some(Binding::new(*modifiers, if delta.y > 0.0 { Trigger::WheelUp } else { Trigger::WheelDown })),
+                egui::Event::PointerButton { button: egui::PointerButton::Middle, pressed: true, modifiers, .. } =>
+                    Some(Binding::new(*modifiers, Trigger::MiddleClick)),
+                egui::Event::PointerButton { button: egui::PointerButton::Extra1, pressed: true, modifiers, .. } =>
+                    Some(Binding::new(*modifiers, Trigger::MouseBack)),
+                egui::Event::PointerButton { button: egui::PointerButton::Extra2, pressed: true, modifiers, .. } =>
+                    Some(Binding::new(*modifiers, Trigger::MouseForward)),
+                _ => None,
+            };
+            captured.is_none()
+        });
+        captured
+    })
+}
+
+impl ImageViewer {
+
+    /// Hozzárendelés közben minden bemenet ide kerül; igaz, ha a gyorsbillentyűket most ki kell hagyni
+    pub fn capture_binding(&mut self, ctx: &egui::Context) -> bool {
+        let Some(dialog) = &mut self.bindings_dialog else {
+            return false;
+        };
+        let Some(action) = dialog.capturing else {
+            return false;
+        };
+        let Some(binding) = capture_binding(ctx) else {
+            return true;
+        };
+        dialog.capturing = None;
+        if binding.trigger == Trigger::Key(egui::Key::Escape) && !binding.command && !binding.shift && !binding.alt {
+            return true; // mégse
+        }
+        match self.bindings.action_for(&binding).filter(|other| *other != action) {
+            Some(other) => dialog.conflict = Some((action, binding, other)),
+            None => self.bindings.assign(action, binding),
+        }
+        true
+    }
+
+    pub fn bindings_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &mut self.bindings_dialog else {
+            return;
+        };
+        let mut open = true;
+        let mut capture = None;
+        let mut remove = None;
+        let mut reset = None;
+        let mut reset_all = false;
+        let mut reassign = false;
+        let mut cancel = false;
+        egui::Window::new(t!("bindings-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([460.0, 520.0])
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.viewport_rect().center())
+            .show(ctx, |ui| {
+                if let Some((action, binding, other)) = &dialog.conflict {
+                    ui.colored_label(ui.visuals().warn_fg_color, t!("bindings-conflict",
+                        binding = binding.text(ctx), other = other.label(), action = action.label()));
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("bindings-reassign")).clicked() {
+                            reassign = true;
+                        }
+                        if ui.button(t!("button-cancel")).clicked() {
+                            cancel = true;
+                        }
+                    });
+                    ui.separator();
+                } else if let Some(action) = dialog.capturing {
+                    ui.strong(t!("bindings-capture", action = action.label()));
+                    ui.separator();
+                }
+                egui::ScrollArea::vertical().max_height(420.0).show(ui, |ui| {
+                    egui::Grid::new("bindings_grid").num_columns(3).striped(true).show(ui, |ui| {
+                        for row in &self.bindings.table {
+                            ui.label(row.action.label());
+                            ui.horizontal(|ui| {
+                                for (index, binding) in row.bindings.iter().enumerate() {
+                                    let mut text = egui::RichText::new(binding.text(ctx));
+                                    if self.bindings.is_shared(row.action, binding) {
+                                        text = text.color(ui.visuals().error_fg_color);
+                                    }
+                                    if ui.button(text).on_hover_text(t!("bindings-remove")).clicked() {
+                                        remove = Some((row.action, index));
+                                    }
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                if ui.small_button("+").on_hover_text(t!("bindings-add")).clicked() {
+                                    capture = Some(row.action);
+                                }
+                                if ui.small_button("↺").on_hover_text(t!("bindings-default")).clicked() {
+                                    reset = Some(row.action);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                if ui.button(t!("bindings-reset-all")).clicked() {
+                    reset_all = true;
+                }
+            });
+        if let Some(action) = capture {
+            dialog.capturing = Some(action);
+            dialog.conflict = None;
+        }
+        if reassign {
+            if let Some((action, binding, _)) = dialog.conflict.take() {
+                self.bindings.assign(action, binding);
+            }
+        }
+        if cancel {
+            dialog.conflict = None;
+        }
+        if let Some((action, index)) = remove {
+            self.bindings.remove(action, index);
+        }
+        if let Some(action) = reset {
+            self.bindings.reset(action);
+        }
+        if reset_all {
+            self.bindings = KeyBindings::default();
+        }
+        if !open {
+            self.bindings_dialog = None;
+        }
+    }
+}
@@ -0,0 +1,326 @@
+/*
+iview/src/cli.rs
+
+Headless batch converter: iview convert [options] <files...>
+
+*/
+
+use std::path::{Path, PathBuf};
+
+use crate::colors::*;
+use crate::file_handlers::*;
+use crate::presets::*;
+use crate::ImageViewer;
+use iview::t;
+
+struct ConvertOptions {
+    format: Option<SaveFormat>,
+    out_dir: Option<PathBuf>,
+    quality: Option<u8>,
+    lossless: bool,
+    magnify: f32,
+    orientation: Option<Orientation>,
+    settings: Option<PathBuf>,
+    preset: Option<String>,
+    presets: Option<PathBuf>,
+    lut: Option<PathBuf>,
+    lut_intensity: Option<f32>,
+    original: bool,
+    all_frames: bool,
+    no_exif: bool,
+    overwrite: bool,
+    inputs: Vec<PathBuf>,
+}
+
+fn parse_orientation(s: &str) -> Option<Orientation> {
+    match s.to_lowercase().as_str() {
+        "0" => Some(Orientation::Rotate0),
+        "90" => Some(Orientation::Rotate90),
+        "180" => Some(Orientation::Rotate180),
+        "270" => Some(Orientation::Rotate270),
+        "fliph" => Some(Orientation::Rotate0F),
+        "flipv" => Some(Orientation::Rotate180F),
+        "fliph90" => Some(Orientation::Rotate90F),
+        "flipv90" => Some(Orientation::Rotate270F),
+        _ => None,
+    }
+}
+
+fn parse_magnify(s: &str) -> Option<f32> {
+    let value = if let Some(percent) = s.strip_suffix('%') {
+        percent.trim().parse::<f32>().ok()? / 100.0
+    } else {
+        s.parse::<f32>().ok()?
+    };
+    if value > 0.0 && value <= 16.0 { Some(value) } else { None }
+}
+
+fn parse_args(args: &[String]) -> Result<ConvertOptions, String> {
+    let mut opt = ConvertOptions {
+        format: None,
+        out_dir: None,
+        quality: None,
+        lossless: false,
+        magnify: 1.0,
+        orientation: None,
+        settings: None,
+        preset: None,
+        presets: None,
+        lut: None,
+        lut_intensity: None,
+        original: false,
+        all_frames: false,
+        no_exif: false,
+        overwrite: false,
+        inputs: Vec::new(),
+    };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = |name: &String| it.next().cloned().ok_or(t!("cli-missing-value", option = name));
+        match arg.as_str() {
+            "-f" | "--format" => {
+                let v = value(arg)?;
+                opt.format = Some(SaveFormat::from_extension(&v).ok_or(t!("cli-unknown-format", value = v))?);
+            }
+            "-o" | "--out-dir" => opt.out_dir = Some(PathBuf::from(value(arg)?)),
+            "-q" | "--quality" => {
+                let v = value(arg)?;
+                opt.quality = Some(v.parse::<u8>().ok().filter(|q| *q <= 100).ok_or(t!("cli-bad-value", option = "--quality", value = v))?);
+            }
+            "--lossless" => opt.lossless = true,
+            "-r" | "--resize" => {
+                let v = value(arg)?;
+                opt.magnify = parse_magnify(&v).ok_or(t!("cli-bad-value", option = "--resize", value = v))?;
+            }
+            "--orientation" => {
+                let v = value(arg)?;
+                opt.orientation = Some(parse_orientation(&v).ok_or(t!("cli-bad-value", option = "--orientation", value = v))?);
+            }
+            "-s" | "--settings" => opt.settings = Some(PathBuf::from(value(arg)?)),
+            "-p" | "--preset" => opt.preset = Some(value(arg)?),
+            "--presets" => opt.presets = Some(PathBuf::from(value(arg)?)),
+            "--lut" => opt.lut = Some(PathBuf::from(value(arg)?)),
+            "--lut-intensity" => {
+                let v = value(arg)?;
+                opt.lut_intensity = Some(v.parse::<f32>().ok().filter(|i| (0.0..=1.0).contains(i)).ok_or(t!("cli-bad-value", option = "--lut-intensity", value = v))?);
+            }
+            "--original" => opt.original = true,
+            "--all-frames" => opt.all_frames = true,
+            "--no-exif" => opt.no_exif = true,
+            "--overwrite" => opt.overwrite = true,
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') && arg.len() > 1 => return Err(t!("cli-unknown-option", option = arg)),
+            _ => opt.inputs.extend(expand_input(arg)?),
+        }
+    }
+    if opt.format.is_none() {
+        return Err(t!("cli-missing-format"));
+    }
+    if opt.inputs.is_empty() {
+        return Err(t!("cli-no-input"));
+    }
+    Ok(opt)
+}
+
+/// '*' és '?' helyettesítő karakterek a fájlnévben (a Windows shell nem bontja ki őket)
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => wildcard_match(&pattern[1..], name) || (!name.is_empty() && wildcard_match(pattern, &name[1..])),
+        (Some('?'), Some(_)) => wildcard_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) => p.to_lowercase().eq(n.to_lowercase()) && wildcard_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+fn expand_input(arg: &str) -> Result<Vec<PathBuf>, String> {
+    let path = PathBuf::from(arg);
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if !file_name.contains('*') && !file_name.contains('?') {
+        return Ok(vec![path]);
+    }
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let pattern: Vec<char> = file_name.chars().collect();
+    let mut found: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map_err(|e| format!("{}: {}", dir.display(), e))?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .filter(|e| {
+            let name: Vec<char> = e.file_name().to_string_lossy().chars().collect();
+            wildcard_match(&pattern, &name)
+        })
+        .map(|e| e.path())
+        .collect();
+    found.sort();
+    if found.is_empty() {
+        return Err(t!("cli-no-match", pattern = arg));
+    }
+    Ok(found)
+}
+
+/// A kiadott változat grafikus alkalmazás (windows_subsystem), a konzolos kimenethez
+/// a hívó parancssor konzoljához kell csatlakozni
+#[cfg(windows)]
+pub fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // ha nincs szülő konzol (pl. Intézőből indítva), a kimenet elvész, mint eddig
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+fn load_color_settings(path: &Path) -> Result<(ColorSettings, Option<PathBuf>), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    // settings.json esetén a color_settings mezőt vesszük
+    if value.get("color_settings").is_some() {
+        let app: AppSettings = serde_json::from_value(value).map_err(|e| format!("{}: {}", path.display(), e))?;
+        return Ok((app.color_settings, app.look_file));
+    }
+    let colset: ColorSettings = serde_json::from_value(value).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok((colset, None))
+}
+
+/// A `convert` alparancs; a visszatérési érték a folyamat kilépési kódja.
+pub fn run_convert(args: &[String]) -> i32 {
+    let opt = match parse_args(args) {
+        Ok(opt) => opt,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}\n", t!("error", error = e));
+            }
+            eprintln!("{}", t!("cli-usage"));
+            return if e.is_empty() { 0 } else { 2 };
+        }
+    };
+    let saveformat = opt.format.unwrap();
+
+    let mut color_settings = ColorSettings::default();
+    let mut look_file = None;
+    if let Some(path) = &opt.settings {
+        match load_color_settings(path) {
+            Ok((colset, look)) => {
+                color_settings = colset;
+                look_file = look;
+            }
+            Err(e) => {
+                eprintln!("{}", t!("error", error = e));
+                return 2;
+            }
+        }
+    }
+    if let Some(name) = &opt.preset {
+        let presets_file = opt.presets.clone().unwrap_or_else(get_settings_path);
+        let preset = import_presets(&presets_file)
+            .and_then(|presets| find_preset(&presets, name).cloned().ok_or(t!("cli-no-preset", name = name, file = presets_file.display())));
+        match preset {
+            Ok(preset) => color_settings = preset.applied_to(&color_settings),
+            Err(e) => {
+                eprintln!("{}", t!("error", error = e));
+                return 2;
+            }
+        }
+    }
+    if opt.lut.is_some() {
+        look_file = opt.lut.clone();
+        color_settings.use_look = true;
+    }
+    if let Some(intensity) = opt.lut_intensity {
+        color_settings.look_intensity = intensity;
+    }
+    if let Some(orientation) = opt.orientation {
+        color_settings.orientation = orientation;
+    }
+
+    // GPU nélkül a Lut4ColorSettings CPU-n élesít / mos el és tesz átlátszóvá (apply_lut)
+    let mut app = ImageViewer {
+        use_gpu: false,
+        save_original: opt.original,
+        ..Default::default()
+    };
+    if let Some(path) = look_file {
+        match CubeLut::load(&path) {
+            Ok(look) => app.look = Some(look),
+            Err(e) => {
+                eprintln!("{}", t!("error", error = e));
+                return 2;
+            }
+        }
+    }
+    if app.look.is_none() {
+        color_settings.use_look = false;
+    }
+
+    if let Some(dir) = &opt.out_dir {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            eprintln!("{}", t!("error", error = format!("{}: {}", dir.display(), e)));
+            return 2;
+        }
+    }
+
+    let (mut converted, mut skipped, mut failed) = (0, 0, 0);
+    for input in &opt.inputs {
+        let stem = input.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let folder = match &opt.out_dir {
+            Some(dir) => dir.clone(),
+            None => input.parent().map(|p| p.to_path_buf()).unwrap_or_default(),
+        };
+        let output = folder.join(format!("{}.{}", stem, saveformat.extension()));
+        if output == *input || (output.exists() && !opt.overwrite) {
+            eprintln!("{}", t!("cli-skipped", input = input.display(), output = output.display()));
+            skipped += 1;
+            continue;
+        }
+
+        app.image_full_path = Some(input.clone());
+        app.image_format = SaveFormat::from_extension(
+            input.extension().and_then(|s| s.to_str()).unwrap_or("")).unwrap_or(SaveFormat::Bmp);
+        if !app.read_image_file(input) {
+            eprintln!("{}", t!("cli-cannot-open", file = input.display()));
+            failed += 1;
+            continue;
+        }
+
+        app.color_settings = color_settings;
+        app.magnify = opt.magnify;
+        let mut lut = Lut4ColorSettings::default();
+        lut.update_lut(&app.color_settings, app.look.as_ref());
+        app.lut = Some(lut);
+        if let Some(img) = &app.original_image {
+            let (w, h) = (img.width(), img.height());
+            app.image_size = if app.view_settings().orientation.swaps_axes() { (h, w).into() } else { (w, h).into() };
+        }
+
+        let in_exif = app.exif.is_some();
+        let anim = app.anim_data.is_some() && (saveformat == SaveFormat::Gif || saveformat == SaveFormat::Webp);
+        let default_quality = if saveformat == SaveFormat::Jxl { 1 } else { 85 };
+        app.save_dialog = Some(SaveSettings {
+            full_path: output.clone(),
+            saveformat,
+            quality: opt.quality.unwrap_or(default_quality),
+            lossless: opt.lossless,
+            can_include_exif: saveformat.supports_exif() && in_exif,
+            include_exif: in_exif && !opt.no_exif,
+            save_all_frames: anim && opt.all_frames,
+            is_animation: anim,
+            preset: None,
+        });
+        if let Err(e) = app.completing_save() {
+            eprintln!("{}", e);
+            failed += 1;
+            continue;
+        }
+        println!("{:?} -> {:?}", input, output);
+        converted += 1;
+    }
+    println!("{}", t!("cli-summary", converted = converted, skipped = skipped, failed = failed));
+    // a meglévő kimenet kihagyása nem hiba
+    if failed > 0 { 1 } else { 0 }
+}
@@ -0,0 +1,851 @@
+/*
+iview/src/codec.rs
+
+Decoding, encoding and EXIF handling without egui
+
+*/
+
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use image::AnimationDecoder;
+use img_parts::ImageEXIF;
+
+use crate::colors::*;
+use crate::exif_my::*;
+use crate::geometry::rotate_fine;
+use crate::t;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum SaveFormat {
+    Jpeg,
+    Webp,
+    Gif,
+    Png,
+    Bmp,
+    Tif,
+    J2k,
+    Jp2,
+    Jxl,
+}
+
+impl SaveFormat {
+    pub fn from_extension(ext: &str) -> Option<SaveFormat> {
+        match ext.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(SaveFormat::Jpeg),
+            "webp" => Some(SaveFormat::Webp),
+            "png" => Some(SaveFormat::Png),
+            "tif" | "tiff" => Some(SaveFormat::Tif),
+            "gif" => Some(SaveFormat::Gif),
+            "jp2" => Some(SaveFormat::Jp2),
+            "jpc" | "j2k" => Some(SaveFormat::J2k),
+            "jxl" => Some(SaveFormat::Jxl),
+            "bmp" => Some(SaveFormat::Bmp),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SaveFormat::Jpeg => "jpg",
+            SaveFormat::Webp => "webp",
+            SaveFormat::Png => "png",
+            SaveFormat::Tif => "tif",
+            SaveFormat::Gif => "gif",
+            SaveFormat::Jp2 => "jp2",
+            SaveFormat::J2k => "j2k",
+            SaveFormat::Jxl => "jxl",
+            SaveFormat::Bmp => "bmp",
+        }
+    }
+
+    /// A formátum fájljába kerülhet-e EXIF blokk (mentés ablak és parancssor ugyanígy dönt)
+    pub fn supports_exif(&self) -> bool {
+        matches!(self, SaveFormat::Jpeg | SaveFormat::Webp | SaveFormat::Jp2 | SaveFormat::Bmp)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Resolution {
+    pub xres: f32,
+    pub yres: f32,
+    pub dpi: bool,
+}
+
+/// Átméretezés szűrője a pontos kimeneti mérethez
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ResizeFilter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+impl ResizeFilter {
+    pub const ALL: [ResizeFilter; 5] = [ResizeFilter::Nearest, ResizeFilter::Triangle,
+        ResizeFilter::CatmullRom, ResizeFilter::Gaussian, ResizeFilter::Lanczos3];
+
+    pub fn filter_type(self) -> image::imageops::FilterType {
+        match self {
+            ResizeFilter::Nearest => image::imageops::FilterType::Nearest,
+            ResizeFilter::Triangle => image::imageops::FilterType::Triangle,
+            ResizeFilter::CatmullRom => image::imageops::FilterType::CatmullRom,
+            ResizeFilter::Gaussian => image::imageops::FilterType::Gaussian,
+            ResizeFilter::Lanczos3 => image::imageops::FilterType::Lanczos3,
+        }
+    }
+}
+
+/// Mentéskori pontos kimeneti méret a nagyítás helyett, a hozzá tartozó DPI-vel
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct OutputSize {
+    pub width: u32,
+    pub height: u32,
+    pub filter: ResizeFilter,
+    pub dpi: Option<f32>,
+    pub keep_aspect: bool, // a szélesség a mérvadó, a magasság a mentett kép (kivágás) arányát követi
+}
+
+impl OutputSize {
+    /// A kimenet mérete egy `source` méretű képhez; arány tartásnál a kivágás később is változhat
+    pub fn size_for(&self, source: (u32, u32)) -> (u32, u32) {
+        if self.keep_aspect && source.0 > 0 {
+            let height = (self.width as f32 * source.1 as f32 / source.0 as f32).round() as u32;
+            (self.width, height.clamp(1, 65535))
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// A kész (forgatott, vágott, korrigált) kép a kért méretre
+    pub fn apply(&self, img: &image::DynamicImage) -> image::DynamicImage {
+        let (width, height) = self.size_for((img.width(), img.height()));
+        if img.width() == width && img.height() == height {
+            return img.clone();
+        }
+        img.resize_exact(width.max(1), height.max(1), self.filter.filter_type())
+    }
+
+    pub fn resolution(&self) -> Option<Resolution> {
+        self.dpi.map(|dpi| Resolution { xres: dpi, yres: dpi, dpi: true })
+    }
+}
+
+#[derive(Clone)]
+pub struct AnimatedImage {
+    //pub anim_frames: Vec<egui::TextureHandle>, // GPU textúrák // old
+    pub anim_frames: Vec<image::DynamicImage>,
+    pub delays: Vec<std::time::Duration>, // Időzítések
+}
+
+/// Beolvasott kép a metaadataival
+#[derive(Clone)]
+pub struct LoadedImage {
+    pub image: image::DynamicImage,
+    pub format: SaveFormat,
+    pub resolution: Option<Resolution>,
+    pub exif: Option<ExifBlock>,
+    pub exif_orientation: f32, // 0.0, ha nincs Orientation tag
+    pub animation: Option<AnimatedImage>,
+    pub warnings: Vec<String>,
+    pub exif_error: Option<String>, // a hibás EXIF blokk oka, az Info ablakba
+}
+
+#[derive(Clone, Debug)]
+pub struct EncodeOptions {
+    pub format: SaveFormat,
+    pub quality: u8,    // JPEG, WebP, JP2 és JXL
+    pub lossless: bool, // WebP
+}
+
+pub fn is_fully_opaque(img: &image::RgbaImage) -> bool {
+    // A .pixels() iterátoron keresztül megnézzük, van-e 255-nél kisebb alfa érték
+    // Az .all() rövidzárral működik: megáll, amint talál egy nem 255-öst
+    img.pixels().all(|p| p[3] == 255)
+}
+
+fn start_cmp(buf: &[u8], pos: usize) -> usize {
+    let s = buf.get(pos..).unwrap_or(&[]);
+    if s.starts_with(b"JXL \x0d\x0a\x87\x0a") { return 0; }
+    if s.starts_with(b"jP  \x0d\x0a\x87\x0a") { return 1; }
+    if s.starts_with(b"brobExif") { return 2; }
+    if s.starts_with(b"Exif\0\0") { return 3; }
+    if s.starts_with(b"uuid") { return 4; }
+    if s.starts_with(b"II*\0") { return 5; }
+    if s.starts_with(b"MM\0*") { return 6; }
+    if s.starts_with(b"jp2c") { return 7; }
+    if s.starts_with(&[0x05,0x37,0xcd,0xab,0x9d,0x0c,0x44,0x31, 0xa7,0x2a,0xfa,0x56,0x1f,0x2a,0x11,0x3e]) { return 8; }
+    100 // Ismeretlen
+}
+
+/// JXL / JP2 dobozokból kikeresi a nyers EXIF blokkot ("Exif\0\0" előtaggal)
+pub fn scan_exif(buf: &[u8]) -> Option<Vec<u8>> {
+    if buf.len() < 12 || u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) != 0xc {return None; }
+    let header_type = start_cmp(buf, 4);
+    if header_type != 0 && header_type != 1 { return None; }
+
+    let mut pos: usize = 0xc;
+    while pos + 8 < buf.len() {
+        let box_len = u32::from_be_bytes([buf[pos], buf[pos+1], buf[pos+2], buf[pos+3]]) as usize;
+        let box_type_pos = pos + 4;        
+        if box_len < 8 { break; }
+        // a doboz tartalma, ha belefér a fájlba
+        let Some(body) = buf.get(pos + 8..pos.saturating_add(box_len)) else { break; };
+        match start_cmp(buf, box_type_pos) {
+            2 => { // brobExif
+                let mut decompressed = Vec::new();
+                let mut reader = brotli::Decompressor::new(body.get(4..)?, 4096); // 'brobExif' után
+                if reader.read_to_end(&mut decompressed).is_ok() {
+                        let mut data = b"Exif\0\0".to_vec();
+                        data.extend_from_slice(decompressed.get(4..)?);
+                        return Some(data);
+                }
+            },
+            3 => { // Exif\0\0
+                let mut data = b"Exif\0\0".to_vec();
+                data.extend_from_slice(body.get(4..)?);
+                return Some(data);
+           },
+            4 => { // uuid
+                if start_cmp(buf, box_type_pos + 4) == 8 { // good_uuid
+                    let exif_start = box_type_pos + 20;
+                    if start_cmp(buf, exif_start) == 5 || start_cmp(buf, exif_start) == 6 {
+                        let mut data = b"Exif\0\0".to_vec();
+                        data.extend_from_slice(buf.get(exif_start .. pos + box_len)?);
+                        return Some(data);
+                    }
+                }
+            },
+            7 => break, // jp2c - vége
+            _ => {},
+        }
+        pos += box_len;
+    }
+    None
+}
+// alternate good_uuid? [0x05,0x37,0xcd,0xf5,0xa5,0x8c,0x44,0xcd,0xa3,0x2c,0xad,0x72,0x20,0x29,0xad,0x52]
+
+
+
+/// EXIF blokk értelmezése: (blokk, felbontás, orientáció)
+pub fn read_exif(raw: &[u8]) -> Result<(ExifBlock, Option<Resolution>, f32), String> {
+    let mut exifblock = ExifBlock::default();
+    let result = exifblock.open(raw, raw.len())?;
+    let mut resolution = None;
+    let mut res = Resolution { xres:0.0, yres:0.0, dpi: true};
+    if let Some(xres) = result.get_num_field("XResolution") {
+        res.xres = xres;
+    }
+    if let Some(mut yres) = result.get_num_field("YResolution") {
+        if yres == 0.0 { yres = res.xres; }
+        res.yres = yres;
+    }
+    if let Some(unit) = result.get_num_field("ResolutionUnit") {
+        res.dpi = unit as u32 == 2;
+        resolution = Some(res);
+    }
+    let orientation = result.get_num_field("Orientation").unwrap_or(0.0);
+    Ok((result, resolution, orientation))
+}
+
+impl LoadedImage {
+    /// Az EXIF szerinti állás; a pixeleket nem forgatjuk be, a nézet alkalmazza
+    pub fn orientation(&self) -> Orientation {
+        Orientation::from_exif(self.exif_orientation as u16)
+    }
+
+    fn add_exif(&mut self, raw: &[u8]) {
+        match read_exif(raw) {
+            Ok((exif, resolution, orientation)) => {
+                if resolution.is_some() {
+                    self.resolution = resolution;
+                }
+                if orientation != 0.0 {
+                    self.exif_orientation = orientation;
+                }
+                self.exif = Some(exif);
+            }
+            Err(e) => {
+                self.warnings.push(t!("error-exif", error = e));
+                self.exif_error = Some(e);
+            }
+        }
+    }
+}
+
+/// Kép beolvasása, a formátumot a kiterjesztésből állapítja meg
+pub fn load_image_file(path: &Path) -> Result<LoadedImage, String> {
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    load_image_as(path, SaveFormat::from_extension(ext).unwrap_or(SaveFormat::Bmp))
+}
+
+/// Kép, felbontás, EXIF és animáció beolvasása a megadott formátummal
+pub fn load_image_as(filepath: &Path, format: SaveFormat) -> Result<LoadedImage, String> {
+    load_image_cancellable(filepath, format, &AtomicBool::new(false))
+}
+
+fn check_cancel(cancel: &AtomicBool) -> Result<(), String> {
+    if cancel.load(Ordering::Relaxed) { Err(t!("load-cancelled")) } else { Ok(()) }
+}
+
+/// Mint a `load_image_as`, de a `cancel` beállítására a lépések között (animációnál képkockánként) abbahagyja
+pub fn load_image_cancellable(filepath: &Path, format: SaveFormat, cancel: &AtomicBool) -> Result<LoadedImage, String> {
+    let file_error = |e: std::io::Error| format!("{}: {}", filepath.display(), e);
+    let mut warnings = Vec::new();
+    let mut resolution: Option<Resolution> = None;
+
+    let image = match format {
+        SaveFormat::J2k | SaveFormat::Jp2 => {
+            let buffer = std::fs::read(filepath).map_err(file_error)?;
+            let (img, xres, yres, dpi, warning) = my_jp2_sys::load_jp2_from_memory(&buffer).map_err(|e| e.to_string())?;
+            if !warning.is_empty() {
+                warnings.push(t!("warning", text = warning));
+            }
+            if xres != 0.0 || yres != 0.0 {
+                resolution = Some( Resolution{xres, yres, dpi:dpi!=0} );
+            }
+            image::DynamicImage::ImageRgba8(img)
+        },
+        SaveFormat::Jxl => {
+            let data = std::fs::read(filepath).map_err(file_error)?;
+            let jxl_image = jxl_oxide::JxlImage::builder().read(data.as_slice()).map_err(|e| t!("error-open", error = format!("{:?}", e)))?;
+            let render = jxl_image.render_frame(0).map_err(|e| t!("error-render", error = format!("{:?}", e)))?;
+            let fb = render.image_all_channels(); // PixelBuffer
+            let width = fb.width() as u32;
+            let height = fb.height() as u32;
+            let channels = fb.channels();
+            let mut rgba_data = Vec::with_capacity((width * height * 4) as usize);
+            let buf = fb.buf();
+            for i in 0..(width * height) as usize {
+                for c in 0..channels {
+                    let val: f32 = buf[i * channels + c];
+                    rgba_data.push((val.clamp(0.0, 1.0) * 255.0) as u8);
+                }
+                if channels == 3 {
+                    rgba_data.push(255);
+                }
+            }
+            let buffer = image::RgbaImage::from_raw(width, height, rgba_data)
+                .ok_or(t!("error-jxl-buffer", file = filepath.display()))?;
+            image::DynamicImage::ImageRgba8(buffer)
+        },
+        _ => {
+            image::open(filepath).map_err(|e| format!("{}: {}", filepath.display(), e))?
+        }
+    };
+
+    check_cancel(cancel)?;
+    let mut loaded = LoadedImage {
+        image,
+        format,
+        resolution,
+        exif: None,
+        exif_orientation: 0.0,
+        animation: None,
+        warnings,
+        exif_error: None,
+    };
+
+    match format { // get resolution && exif
+        SaveFormat::Jxl | SaveFormat::Jp2 | SaveFormat::J2k => {
+            if let Ok(buf) = std::fs::read(filepath) { // read exif info
+                if let Some(raw) = scan_exif(&buf) {
+                    loaded.add_exif(&raw);
+                }
+            }
+        }
+        SaveFormat::Tif => {
+            if let Ok(file) = std::fs::File::open(filepath) {
+                if let Ok(mut decoder) = tiff::decoder::Decoder::new(file) {
+                    if let Ok(tiff::decoder::ifd::Value::Rational(n, d)) =
+                        decoder.get_tag(tiff::tags::Tag::XResolution)
+                    {
+                        let xres = n as f32 / d as f32;
+                        if let Ok(tiff::decoder::ifd::Value::Rational(n, d)) =
+                            decoder.get_tag(tiff::tags::Tag::YResolution)
+                        {
+                            let yres = n as f32 / d as f32;
+                            if let Ok(unit) = decoder.get_tag(tiff::tags::Tag::ResolutionUnit) {
+                                let dpi = unit == tiff::decoder::ifd::Value::Unsigned(2);
+                                loaded.resolution = Some(Resolution { xres, yres, dpi });
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        SaveFormat::Bmp => {
+            if let Ok(mut file) = std::fs::File::open(filepath) {
+                let mut buffer = [0u8; 8];
+                if file.seek(std::io::SeekFrom::Start(38)).is_ok()
+                    && file.read_exact(&mut buffer).is_ok()
+                {
+                    let x_ppm = u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]);
+                    let y_ppm = u32::from_le_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+                    if x_ppm > 0 && y_ppm > 0 {
+                        let xres = (x_ppm as f32 / 39.3701).round();
+                        let yres = (y_ppm as f32 / 39.3701).round();
+                        loaded.resolution = Some(Resolution {
+                            xres,
+                            yres,
+                            dpi: true,
+                        });
+                    }
+                }
+            }
+        },
+        SaveFormat::Png => {
+            if let Ok(file) = std::fs::File::open(filepath) {
+                let reader = std::io::BufReader::new(file);
+                let decoder = png::Decoder::new(reader);
+                if let Ok(reader) = decoder.read_info() {
+                    if let Some(phys) = reader.info().pixel_dims {
+                        if phys.unit == png::Unit::Meter {
+                            let x_ppm = phys.xppu;
+                            let y_ppm = phys.yppu;
+                            let xres = (x_ppm as f32 / 39.3701).round();
+                            let yres = (y_ppm as f32 / 39.3701).round();
+                            loaded.resolution = Some(Resolution {
+                                xres,
+                                yres,
+                                dpi: true,
+                            });
+                        }
+                    }
+                }
+            }
+        },
+        SaveFormat::Jpeg => {
+            if let Ok(mut file) = std::fs::File::open(filepath) {
+                let mut header = [0u8; 18];
+                if file.read_exact(&mut header).is_ok() {
+                    // Ellenőrizzük a JFIF mágiát: [FF D8 FF E0 ... 'J' 'F' 'I' 'F']
+                    if header[0..4] == [0xFF, 0xD8, 0xFF, 0xE0] && &header[6..10] == b"JFIF" {
+                        let unit = header[13]; // 1 = DPI (dots per inch), 2 = DPC (dots per cm)
+                        let xres = u16::from_be_bytes([header[14], header[15]]) as f32;
+                        let yres = u16::from_be_bytes([header[16], header[17]]) as f32;
+                        if xres > 0.0 && yres > 0.0 && (unit == 1 || unit == 2) {
+                            loaded.resolution = Some(Resolution {
+                                xres,
+                                yres,
+                                dpi: unit == 1,
+                            });
+                        }
+                    }
+                }
+            }
+        },
+        _ => {}
+    }
+
+    if let Ok(buffer) = std::fs::read(filepath) { // read exif info
+        if format == SaveFormat::Webp {
+            if let Ok(webp) = img_parts::webp::WebP::from_bytes(buffer.clone().into()) {
+                if let Some(exif_bytes) = webp.exif() {
+                    let mut data = exif_bytes.to_vec().clone();
+                    if !data.starts_with(b"Exif\0\0") {
+                        let mut legacy_format = b"Exif\0\0".to_vec();
+                        legacy_format.extend_from_slice(&data);
+                        data = legacy_format;
+                    }
+                    loaded.add_exif(&data);
+                }
+            }
+        }
+        else if format == SaveFormat::Jpeg {
+            if let Ok(jpeg) = img_parts::jpeg::Jpeg::from_bytes(buffer.into()) {
+                let raw_exif = jpeg.segments().iter()
+                    .find(|s: &&img_parts::jpeg::JpegSegment| s.marker() == 0xE1 && s.contents().starts_with(b"Exif\0\0")) // nem XMP
+                    .map(|s: &img_parts::jpeg::JpegSegment| s.contents().to_vec());
+                    
+                if let Some(data) = raw_exif {
+                    loaded.add_exif(&data);
+                }
+            }
+        }
+        else if format == SaveFormat::Bmp {
+            if buffer.len() > 14 {
+                let offset = u32::from_le_bytes(buffer[10..14].try_into().unwrap()) as usize;
+                if offset > 54 && offset <= buffer.len() {
+                    let potential_exif = &buffer[54..offset];
+                    if let Some(pos) = potential_exif.windows(4).position(|w| w == b"Exif" || w == b"II*" || w == b"MM*") {
+                        let start = 54 + pos;
+                        let mut data = buffer[start..offset].to_vec();
+                        if !data.starts_with(b"Exif\0\0") {
+                            let mut legacy_format = b"Exif\0\0".to_vec();
+                            legacy_format.extend_from_slice(&data);
+                            data = legacy_format;
+                        }
+                        loaded.add_exif(&data);
+                    }
+                }
+            }
+        }
+        else if format == SaveFormat::Png {
+            if let Ok(png) = img_parts::png::Png::from_bytes(buffer.clone().into()) {
+                if let Some(exif_chunk) = png.chunk_by_type(*b"eXIf") {
+                    let raw_content = exif_chunk.contents();
+                    let mut data = b"Exif\0\0".to_vec();
+                    data.extend_from_slice(raw_content);
+                    loaded.add_exif(&data);
+                }
+            }
+        }
+    }
+    // Csak GIF és WebP esetén próbáljuk meg az animációt betölteni
+    if format == SaveFormat::Gif || format == SaveFormat::Webp {
+        loaded.animation = load_animation(filepath, format, cancel);
+        check_cancel(cancel)?;
+    }
+    Ok(loaded)
+}
+
+/// GIF / WebP képkockák és időzítések; None, ha nem animált (vagy közben megszakították)
+pub fn load_animation(path: &Path, format: SaveFormat, cancel: &AtomicBool) -> Option<AnimatedImage> {
+    let file = std::fs::File::open(path).ok()?;
+    let reader = std::io::BufReader::new(file);
+
+    // Képkockák kinyerése formátum szerint
+    let frames = match format {
+        SaveFormat::Gif => image::codecs::gif::GifDecoder::new(reader).ok()?.into_frames(),
+        SaveFormat::Webp => image::codecs::webp::WebPDecoder::new(reader).ok()?.into_frames(),
+        _ => return None,
+    };
+
+    let mut images = Vec::new();
+    let mut delays = Vec::new();
+
+    for frame in frames {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let frame = frame.ok()?;
+        // Késleltetés kinyerése (ms)
+        let (num, den) = frame.delay().numer_denom_ms();
+        let delay_ms = if den == 0 { 100 } else { (num / den).max(20) }; // Biztonsági minimum 10ms
+        delays.push(std::time::Duration::from_millis(delay_ms as u64));
+
+        let rgba = frame.into_buffer();
+        images.push(image::DynamicImage::ImageRgba8(rgba));
+    }
+    if images.len() <= 1 { return None; }
+    Some(AnimatedImage {
+        anim_frames: images,
+        delays,
+    })
+}
+
+/// Nagyítás, orientáció, finom forgatás és kivágás alkalmazása (a színkorrekció előtti lépés)
+pub fn resize_and_orient(img: &image::DynamicImage, color_settings: &ColorSettings, magnify: f32) -> image::DynamicImage {
+    let new_width = (img.width() as f32 * magnify).round() as u32;
+    let new_height = (img.height() as f32 * magnify).round() as u32;
+    let processed_img = if (magnify - 1.0).abs() > 0.001 {
+        img.resize(new_width, new_height, image::imageops::FilterType::Lanczos3)
+    } else {
+        img.clone()
+    };
+    let mut oriented = color_settings.orientation.apply(processed_img);
+    if color_settings.rotation.abs() >= 0.001 {
+        oriented = image::DynamicImage::ImageRgba8(rotate_fine(&oriented.to_rgba8(), color_settings.rotation));
+    }
+    match color_settings.crop {
+        Some(crop) if !crop.is_full() => {
+            let (x, y, w, h) = crop.pixels(oriented.width(), oriented.height());
+            oriented.crop_imm(x, y, w, h)
+        }
+        _ => oriented,
+    }
+}
+
+/// Nagyítás, orientáció és színkorrekció CPU-n ("Save View" kép)
+pub fn apply_color_settings(img: &image::DynamicImage, color_settings: &ColorSettings, magnify: f32, look: Option<&CubeLut>) -> image::DynamicImage {
+    let mut rgba_image = resize_and_orient(img, color_settings, magnify).to_rgba8();
+    if color_settings.is_setted() || color_settings.is_blured() {
+        let mut lut = Lut4ColorSettings::default();
+        lut.update_lut(color_settings, look);
+        let hist = (0..1024).map(|_| AtomicU32::new(0)).collect::<Vec<_>>();
+        lut.apply_lut(&mut rgba_image, &hist);
+    }
+    image::DynamicImage::ImageRgba8(rgba_image)
+}
+
+/// A módosított képhez igazítja az EXIF thumbnailt, felbontást és képméretet.
+/// Hibánál nem szabad a régi blokkot kiírni: az eredeti Orientation még egyszer elforgatná a képet.
+pub fn patch_exif_for_image(exif: &ExifBlock, img: &image::DynamicImage, resolution: &Option<Resolution>, size: (u32, u32)) -> Result<ExifBlock, String> {
+    let mut exif = exif.clone();
+    // a régi bélyegkép a régi pixeleket mutatná
+    let thumbnail = exif.generate_fitted_thumbnail(&img.to_rgba8());
+    exif.patch_thumbnail(&thumbnail)?;
+    if let Some(res) = resolution {
+        exif.patch_exifdata( res.xres, res.yres, size.0, size.1)?; // méretek és Orientation = 1 is
+    } else {
+        exif.patch_dimensions(size.0, size.1)?;
+        exif.patch_orientation(1)?; // a nézet pixelei már egyenesek
+    }
+    Ok(exif)
+}
+
+/// Kép kódolása a megadott formátumba. Ha `animation` adott (GIF / WebP), az összes képkocka kerül a fájlba.
+/// A kódolt bájtok mellett a kódoló figyelmeztetései (a hívó dönti el, hol jelennek meg).
+pub fn encode_image(img: &image::DynamicImage, animation: Option<&AnimatedImage>, resolution: &Option<Resolution>,
+                    exif: Option<&ExifBlock>, options: &EncodeOptions) -> Result<(Vec<u8>, Vec<String>), String> {
+    let mut warnings = Vec::new();
+    let data = match options.format {
+        SaveFormat::J2k | SaveFormat::Jp2 => {
+            let mut res = Resolution{xres:0.0,yres:0.0,dpi:false};
+            if let Some(resol) = resolution.clone() {
+                res = resol;
+            }
+            let jp2:u8 = if options.format == SaveFormat::Jp2 {1} else {0};
+            let opaque = is_fully_opaque(&img.to_rgba8());
+            let dynamic_img = if opaque {
+                image::DynamicImage::ImageRgb8(img.to_rgb8())
+            } else {
+                image::DynamicImage::ImageRgba8(img.to_rgba8())
+            };
+            let exif_raw = exif.map(|e| e.raw_exif.clone()).unwrap_or_default();
+            let (jp2_data, warning) = my_jp2_sys::save_rgba_to_jp2(&dynamic_img, jp2, options.quality, res.xres, res.yres, res.dpi, exif_raw)
+                .map_err(|e| e.to_string())?;
+            if !warning.is_empty() {
+                warnings.push(t!("warning", text = warning));
+            }
+            Ok(jp2_data)
+        }
+        SaveFormat::Jxl => {
+            let width = img.width();
+            let height = img.height();
+            let rgba = img.to_rgb8();
+            let distance = if options.quality > 0 { options.quality as f32 } else { 0.1 };
+            jxl_encoder::LossyConfig::new(distance)
+                .encode(&rgba, width, height, jxl_encoder::PixelLayout::Rgb8)
+                .map_err(|e| t!("error-encode", format = "JXL", error = format!("{:?}", e)))
+        }
+        SaveFormat::Jpeg => {
+            let mut buffer = Vec::new();
+            let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, options.quality);
+            img.write_with_encoder(encoder).map_err(|e| t!("error-encode", format = "JPEG", error = e))?;
+            let mut jpeg = img_parts::jpeg::Jpeg::from_bytes(buffer.into()).map_err(|e| e.to_string())?;
+            if let Some(res) = resolution.clone() {
+                let dpi_unit = if res.dpi { 1u8 } else { 2u8 }; 
+                let x_res = res.xres as u16;
+                let y_res = res.yres as u16;
+                // JFIF APP0 adatok
+                let jfif_data = vec![
+                    b'J', b'F', b'I', b'F', 0,
+                    1, 1,
+                    dpi_unit,
+                    (x_res >> 8) as u8, (x_res & 0xFF) as u8,
+                    (y_res >> 8) as u8, (y_res & 0xFF) as u8,
+                    0, 0,
+                ];
+                let new_seg = img_parts::jpeg::JpegSegment::new_with_contents(
+                    0xE0, 
+                    img_parts::Bytes::from(jfif_data)
+                );
+                // APP0 (0xE0) keresése és frissítése
+                let app0_pos = jpeg.segments().iter().position(|s| s.marker() == 0xE0);
+                if let Some(pos) = app0_pos {
+                    jpeg.segments_mut()[pos] = new_seg;
+                } else {
+                    jpeg.segments_mut().insert(0, new_seg);
+                }
+            }
+            if let Some(exif) = exif {
+                let exif_segment = img_parts::jpeg::JpegSegment::new_with_contents(
+                    0xE1, 
+                    img_parts::Bytes::from(exif.raw_exif.clone())
+                );
+                jpeg.segments_mut().insert(1, exif_segment);
+            }
+            let mut output = Vec::new();
+            jpeg.encoder().write_to(&mut output).map_err(|e| e.to_string())?;
+            Ok(output)
+        }
+        SaveFormat::Webp => {
+            let mut output_data = if let Some(anim) = animation {
+                use webp_animation::{Encoder, EncoderOptions, EncodingConfig, EncodingType, LossyEncodingConfig};
+                let first = anim.anim_frames.first().ok_or(t!("error-empty-animation"))?;
+                let w = first.width();
+                let h = first.height();
+                
+                let mut options_anim  = EncoderOptions::default();
+                let mut config  = EncodingConfig::default();
+                let lossy =  LossyEncodingConfig::default();
+                config.quality = options.quality as f32;
+                config.encoding_type = if options.lossless {EncodingType::Lossless} else {EncodingType::Lossy(lossy)} ;
+                config.method = 3;
+                options_anim.kmin  = 3;
+                options_anim.kmax  = 5;
+                options_anim.encoding_config = Some(config);
+                let mut encoder = Encoder::new_with_options((w,h),options_anim)
+                    .map_err(|e| t!("error-webp-anim-encoder", error = format!("{:?}", e)))?;
+                let mut timestamp: i32 = 0;
+
+                for (i, frame_img) in anim.anim_frames.iter().enumerate() {
+                    let raw_data = frame_img.to_rgba8();
+                    encoder.add_frame(raw_data.as_raw(), timestamp).map_err(|e| format!("{:?}", e))?;
+                    timestamp += anim.delays[i].as_millis() as i32;
+                }
+                
+                let final_webp_data = encoder.finalize(timestamp)
+                    .map_err(|e| t!("error-anim-finish", error = format!("{:?}", e)))?;
+                final_webp_data.to_vec()
+            }
+            else {
+                let encoder = webp::Encoder::from_image(img)
+                    .map_err(|e| t!("error-webp-encoder", error = e))?;
+                let memory = if options.lossless {
+                    encoder.encode_lossless()
+                } else {
+                    encoder.encode(options.quality as f32)
+                };
+                memory.to_vec()
+            };
+            if let Some(exif) = exif {
+                let mut webp = img_parts::webp::WebP::from_bytes(img_parts::Bytes::copy_from_slice(&output_data))
+                    .map_err(|e| t!("error-webp-structure", error = e))?;
+                webp.set_exif(Some(img_parts::Bytes::from(exif.raw_exif.clone())));
+                let mut buf = Vec::new();
+                webp.encoder().write_to(&mut buf).map_err(|e| e.to_string())?;
+                output_data = buf;
+            }
+            Ok(output_data)
+        }
+        SaveFormat::Tif => {
+            let mut output = Cursor::new(Vec::new());
+            {
+                let rgb_data = img.to_rgba8(); 
+                let (x, y, unit) = if let Some(res) = resolution {
+                    ((res.xres * 1000.0) as u32, (res.yres * 1000.0) as u32, if res.dpi { 2u16 } else { 3u16 })
+                } else {
+                    (72000, 72000, 2u16)
+                };
+                let tiff_error = |e: tiff::TiffError| t!("error-write", format = "TIFF", error = e);
+                let mut tiff_writer = tiff::encoder::TiffEncoder::new(&mut output)
+                    .map_err(tiff_error)?
+                    .with_compression(tiff::encoder::Compression::Deflate(tiff::encoder::DeflateLevel::Best));
+                let mut col = tiff_writer.new_image::<tiff::encoder::colortype::RGBA8>(img.width(), img.height()).map_err(tiff_error)?;
+
+                col.encoder().write_tag(tiff::tags::Tag::XResolution, tiff::encoder::Rational { n: x, d: 1000 }).map_err(tiff_error)?;
+                col.encoder().write_tag(tiff::tags::Tag::YResolution, tiff::encoder::Rational { n: y, d: 1000 }).map_err(tiff_error)?;
+                col.encoder().write_tag(tiff::tags::Tag::ResolutionUnit, unit).map_err(tiff_error)?;
+                col.encoder().write_tag(tiff::tags::Tag::Software, "IView 2026").map_err(tiff_error)?;
+                col.encoder().write_tag(tiff::tags::Tag::DateTime, chrono::Local::now().format("%Y:%m:%d %H:%M:%S").to_string().as_str()).map_err(tiff_error)?;
+
+                col.write_data(rgb_data.as_raw()).map_err(tiff_error)?;
+            }
+            Ok(output.into_inner())
+        }
+        SaveFormat::Png => {
+            let mut buffer = Vec::new();
+            {
+                let mut png_encoder = png::Encoder::new(&mut buffer, img.width(), img.height());
+                let color_type = match img.color() {
+                    image::ColorType::Rgb8 => png::ColorType::Rgb,
+                    image::ColorType::Rgba8 => png::ColorType::Rgba,
+                    _ => png::ColorType::Rgba,
+                };
+                png_encoder.set_color(color_type);
+                png_encoder.set_depth(png::BitDepth::Eight);
+                if let Some(res) = resolution {
+                    let (dpm_x, dpm_y) = if res.dpi {
+                        ((res.xres / 0.0254 + 0.5) as u32, (res.yres / 0.0254 + 0.5) as u32)
+                    } else {
+                        ((res.xres * 100.0 + 0.5) as u32, (res.yres * 100.0 + 0.5) as u32)
+                    };
+                    png_encoder.set_pixel_dims(Some(png::PixelDimensions {
+                        xppu: dpm_x, yppu: dpm_y, unit: png::Unit::Meter, }));
+                }
+                let mut writer = png_encoder.write_header().map_err(|e| e.to_string())?;
+                writer.write_image_data(img.as_bytes()).map_err(|e| t!("error-write", format = "PNG", error = e))?;
+            }
+
+            if let Some(exif) = exif {
+                let clean_exif = exif.raw_exif[6..].to_vec();
+                let mut png_parts = img_parts::png::Png::from_bytes(buffer.into()).map_err(|e| e.to_string())?;
+                let exif_chunk = img_parts::png::PngChunk::new(*b"eXIf", img_parts::Bytes::copy_from_slice(&clean_exif));
+                let pos = png_parts.chunks().len() - 1;
+                png_parts.chunks_mut().insert(pos, exif_chunk);
+                let mut output = Vec::new();
+                png_parts.encoder().write_to(&mut output).map_err(|e| t!("error-file-write", format = "PNG", error = e))?;
+                Ok(output)
+            } else {
+                Ok(buffer)
+            }
+        }
+        SaveFormat::Bmp => {
+            let mut buffer = Cursor::new(Vec::new());
+            img.write_to(&mut buffer, image::ImageFormat::Bmp)
+                .map_err(|e| t!("error-encode", format = "BMP", error = e))?;
+            let mut bmp_data = buffer.into_inner();
+            if let Some(res) = resolution.clone() {
+                let (dpm_x, dpm_y) = if res.dpi {
+                    ((res.xres / 0.0254 + 0.5) as u32, (res.yres / 0.0254 + 0.5) as u32)
+                } else {
+                    ((res.xres * 100.0 + 0.5) as u32, (res.yres * 100.0 + 0.5) as u32)
+                };
+                let dpm_x_bytes = dpm_x.to_le_bytes();
+                let dpm_y_bytes = dpm_y.to_le_bytes();
+                if bmp_data.len() > 46 {
+                    bmp_data[38..42].copy_from_slice(&dpm_x_bytes);
+                    bmp_data[42..46].copy_from_slice(&dpm_y_bytes);
+                }
+            }
+            if let Some(exif) = exif {
+                let original_pixel_offset = u32::from_le_bytes(bmp_data[10..14].try_into().unwrap()) as usize;
+                let exif_to_insert = exif.raw_exif.clone();
+                // 2026-os tipp: A BMP-be érdemes egy extra 4 bájtos hosszt vagy azonosítót 
+                // tenni az EXIF elé, de a nyers "Exif\0\0" is megteszi.
+                let mut new_bmp = Vec::with_capacity(bmp_data.len() + exif_to_insert.len());
+                new_bmp.extend_from_slice(&bmp_data[..original_pixel_offset]);
+                new_bmp.extend_from_slice(&exif_to_insert);
+                new_bmp.extend_from_slice(&bmp_data[original_pixel_offset..]);
+                let new_pixel_offset = (original_pixel_offset + exif_to_insert.len()) as u32;
+                let new_file_size = new_bmp.len() as u32;
+                new_bmp[2..6].copy_from_slice(&new_file_size.to_le_bytes());
+                new_bmp[10..14].copy_from_slice(&new_pixel_offset.to_le_bytes());
+                bmp_data = new_bmp;
+            }
+            Ok(bmp_data)
+        }
+        SaveFormat::Gif => {
+            let mut output = Vec::new();
+            if let Some(anim) = animation {
+                use gif::{Encoder, Frame, Repeat, DisposalMethod};
+                let first = anim.anim_frames.first().ok_or(t!("error-empty-animation"))?;
+                let w = first.width() as u16;
+                let h = first.height() as u16;
+
+                let mut encoder = Encoder::new(&mut output, w, h, &[]).map_err(|e| t!("error-encoder", format = "GIF", error = e))?;
+                // Végtelen ismétlés beállítása
+                encoder.set_repeat(Repeat::Infinite).map_err(|e| e.to_string())?;
+
+                // Képkockák hozzáadása (Szekvenciális paletta-számítás és írás)
+                for (i, frame_img) in anim.anim_frames.iter().enumerate() {
+                    // A GIF-nek 8-bites paletta kell (256 szín)
+                    let mut frame = Frame::from_rgba_speed( w, h, &mut frame_img.to_rgba8().into_raw(), 
+                     10, // Sebesség (1-30), a 10 egy jó kompromisszum
+                    );
+                    frame.dispose = DisposalMethod::Background;
+                    frame.delay = (anim.delays[i].as_millis() / 10) as u16;
+                    encoder.write_frame(&frame).map_err(|e| t!("error-write", format = "GIF", error = e))?;
+                }
+            }
+            else {
+                img.write_to(&mut Cursor::new(&mut output), image::ImageFormat::Gif)
+                    .map_err(|e| e.to_string())?;
+            }
+            Ok(output)
+        }
+    }?;
+    Ok((data, warnings))
+}
+
+/// `encode_image` eredményének kiírása fájlba; siker esetén a kódoló figyelmeztetései
+pub fn save_image(path: &Path, img: &image::DynamicImage, animation: Option<&AnimatedImage>, resolution: &Option<Resolution>,
+                  exif: Option<&ExifBlock>, options: &EncodeOptions) -> Result<Vec<String>, String> {
+    let (data, warnings) = encode_image(img, animation, resolution, exif, options)?;
+    std::fs::write(path, data).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(warnings)
+}
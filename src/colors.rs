@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+use crate::t;
+
 const TWO_PI: f32 = PI * 2.0;
 
 ///////////////////////////////////////////////////////////////////////////
@@ -87,6 +89,74 @@ impl Orientation {
         };
     }
 
+    /// Az EXIF Orientation tag (1..8) szerinti állás, amiben a kép egyenesen látszik
+    pub fn from_exif(value: u16) -> Orientation {
+        match value {
+            2 => Orientation::Rotate0F,
+            3 => Orientation::Rotate180,
+            4 => Orientation::Rotate180F,
+            5 => Orientation::Rotate270F,
+            6 => Orientation::Rotate90,
+            7 => Orientation::Rotate90F,
+            8 => Orientation::Rotate270,
+            _ => Orientation::Rotate0,
+        }
+    }
+
+    /// Vízszintes tükrözés, utána ennyiszer 90° jobbra
+    fn parts(self) -> (bool, u8) {
+        match self {
+            Orientation::Rotate0    => (false, 0),
+            Orientation::Rotate90   => (false, 1),
+            Orientation::Rotate180  => (false, 2),
+            Orientation::Rotate270  => (false, 3),
+            Orientation::Rotate0F   => (true, 0),
+            Orientation::Rotate90F  => (true, 1),
+            Orientation::Rotate180F => (true, 2),
+            Orientation::Rotate270F => (true, 3),
+        }
+    }
+
+    fn from_parts(flip: bool, quarters: u8) -> Orientation {
+        match (flip, quarters % 4) {
+            (false, 0) => Orientation::Rotate0,
+            (false, 1) => Orientation::Rotate90,
+            (false, 2) => Orientation::Rotate180,
+            (false, _) => Orientation::Rotate270,
+            (true, 0)  => Orientation::Rotate0F,
+            (true, 1)  => Orientation::Rotate90F,
+            (true, 2)  => Orientation::Rotate180F,
+            (true, _)  => Orientation::Rotate270F,
+        }
+    }
+
+    /// Előbb ez, utána a `next` állás (pl. az EXIF szerinti, majd a felhasználó forgatása)
+    pub fn then(self, next: Orientation) -> Orientation {
+        let (flip, quarters) = self.parts();
+        let (next_flip, next_quarters) = next.parts();
+        // tükrözés után a korábbi forgatás iránya megfordul
+        let quarters = if next_flip { 4 - quarters } else { quarters };
+        Orientation::from_parts(flip != next_flip, quarters + next_quarters)
+    }
+
+    /// Felcseréli-e a szélességet és a magasságot
+    pub fn swaps_axes(self) -> bool {
+        self.parts().1 % 2 == 1
+    }
+
+    pub fn apply(self, img: image::DynamicImage) -> image::DynamicImage {
+        match self {
+            Orientation::Rotate0    => img,
+            Orientation::Rotate90   => img.rotate90(),
+            Orientation::Rotate180  => img.rotate180(),
+            Orientation::Rotate270  => img.rotate270(),
+            Orientation::Rotate0F   => img.fliph(),
+            Orientation::Rotate180F => img.flipv(),
+            Orientation::Rotate90F  => img.fliph().rotate90(),
+            Orientation::Rotate270F => img.flipv().rotate90(),
+        }
+    }
+
 }
 
 
@@ -97,7 +167,33 @@ fn r(th: f32) -> f32 {
     (ra * rb) / (rb * th.cos()).hypot(ra * th.sin())
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy)]
+/// Kivágás az elforgatott kép méretének arányában (0.0 .. 1.0), így független a nagyítástól
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct CropRect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl CropRect {
+    pub const FULL: CropRect = CropRect { x: 0.0, y: 0.0, w: 1.0, h: 1.0 };
+
+    /// A kivágás pixelben (x, y, szélesség, magasság) egy adott méretű képen, legalább 1x1
+    pub fn pixels(&self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let x = ((self.x * width as f32).round() as u32).min(width.saturating_sub(1));
+        let y = ((self.y * height as f32).round() as u32).min(height.saturating_sub(1));
+        let w = ((self.w * width as f32).round() as u32).clamp(1, (width - x).max(1));
+        let h = ((self.h * height as f32).round() as u32).clamp(1, (height - y).max(1));
+        (x, y, w, h)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.x <= 0.0001 && self.y <= 0.0001 && self.w >= 0.9999 && self.h >= 0.9999
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
 #[serde(default)]
 pub struct ColorSettings {
     //pub is_default: bool,
@@ -118,6 +214,10 @@ pub struct ColorSettings {
     pub transparency_tolerance: f32, // 0.0 - 1.0
     pub use_transparency: bool,
     pub rough_transparency: bool,
+    pub use_look: bool,      // betöltött .cube LUT alkalmazása
+    pub look_intensity: f32, // 0.0 .. 1.0
+    pub rotation: f32,          // -45.0 .. 45.0 fok, realy image setting, az orientáció után
+    pub crop: Option<CropRect>, // realy image setting, a forgatás után
 }
 impl Default for ColorSettings {
     fn default() -> Self {
@@ -139,6 +239,10 @@ impl Default for ColorSettings {
             transparency_tolerance: 0.0,
             use_transparency: false,
             rough_transparency: false,
+            use_look: false,
+            look_intensity: 1.0,
+            rotation: 0.0,
+            crop: None,
         }
     }
 }
@@ -152,7 +256,11 @@ impl ColorSettings {
             self.hue_shift.abs() < 0.001 &&
             self.saturation.abs() < 0.001 &&
             self.show_r && self.show_g && self.show_b &&
-            !self.invert)
+            !self.invert &&
+            !self.is_look())
+    }
+    pub fn is_look(&self) -> bool {
+        self.use_look && self.look_intensity >= 0.001
     }
     pub fn is_blured(&self) -> bool {
         self.sharpen_amount.abs() >= 0.001 || self.use_transparency
@@ -296,6 +404,19 @@ impl ColorSettings {
 
 }
 
+/// R, G, B és világosság hisztogram (4 x 256 elem)
+pub fn rgba_histogram(img: &image::RgbaImage) -> Vec<u32> {
+    let mut hist = vec![0u32; 1024];
+    img.pixels().for_each(|p| {
+        hist[p[0] as usize ] += 1;
+        hist[p[1] as usize + 256] += 1;
+        hist[p[2] as usize + 512] += 1;
+        let gray = (p[0] as f32 * 0.299 + p[1] as f32 * 0.587 + p[2] as f32 * 0.114) as usize;
+        hist[gray + 768] += 1;
+    });
+    hist
+}
+
 #[derive(Clone)]
 pub struct Lut4ColorSettings {
     pub size : usize,
@@ -344,7 +465,7 @@ impl Lut4ColorSettings {
         s
     }
 
-    pub fn update_lut(&mut self, colset: &ColorSettings) {
+    pub fn update_lut(&mut self, colset: &ColorSettings, look: Option<&CubeLut>) {
         let mut idx = 0;
         for b in 0..self.size {
             for g in 0..self.size {
@@ -354,6 +475,11 @@ impl Lut4ColorSettings {
                     let b_f = b as f32 / (self.size - 1) as f32;
                     let mut color = [r_f, g_f, b_f, 1.0];
                     colset.convert(&mut color);
+                    if let Some(look) = look {
+                        if colset.is_look() {
+                            look.blend(&mut color, colset.look_intensity);
+                        }
+                    }
                     self.data[idx  ] = (color[0] * 255.0) as u8;
                     self.data[idx+1] = (color[1] * 255.0) as u8;
                     self.data[idx+2] = (color[2] * 255.0) as u8;
@@ -558,3 +684,184 @@ impl Lut4ColorSettings {
 
 ///////////////////////////////////////////////////////////////////////////
 
+
+/// Adobe .cube formátumú 3D LUT (tetszőleges rácsméret, DOMAIN_MIN/MAX)
+#[derive(Clone)]
+pub struct CubeLut {
+    pub path: std::path::PathBuf,
+    pub title: String,
+    pub size: usize,
+    pub domain_min: [f32; 3],
+    pub domain_max: [f32; 3],
+    pub data: Vec<[f32; 3]>, // r fut leggyorsabban, aztán g, aztán b
+}
+
+impl CubeLut {
+
+    pub fn load(path: &std::path::Path) -> Result<CubeLut, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut lut = CubeLut {
+            path: path.to_path_buf(),
+            title: path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+            size: 0,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            data: Vec::new(),
+        };
+        let parse3 = |parts: &[&str], line_no: usize| -> Result<[f32; 3], String> {
+            if parts.len() != 3 {
+                return Err(t!("lut-expected-values", line = line_no));
+            }
+            let mut v = [0.0f32; 3];
+            for i in 0..3 {
+                v[i] = parts[i].parse::<f32>().map_err(|_| t!("lut-bad-number", line = line_no, value = parts[i]))?;
+            }
+            Ok(v)
+        };
+        for (n, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts[0] {
+                "TITLE" => {
+                    lut.title = line[5..].trim().trim_matches('"').to_string();
+                }
+                "LUT_3D_SIZE" => {
+                    let size = parts.get(1).and_then(|s| s.parse::<usize>().ok())
+                        .ok_or(t!("lut-bad-keyword", line = n + 1, keyword = "LUT_3D_SIZE"))?;
+                    if !(2..=256).contains(&size) {
+                        return Err(t!("lut-size-range", line = n + 1, size = size));
+                    }
+                    lut.size = size;
+                    lut.data.reserve(size * size * size);
+                }
+                "LUT_1D_SIZE" => {
+                    return Err(t!("lut-1d"));
+                }
+                "DOMAIN_MIN" => { lut.domain_min = parse3(&parts[1..], n + 1)?; }
+                "DOMAIN_MAX" => { lut.domain_max = parse3(&parts[1..], n + 1)?; }
+                "LUT_3D_INPUT_RANGE" => {
+                    let lo = parts.get(1).and_then(|s| s.parse::<f32>().ok());
+                    let hi = parts.get(2).and_then(|s| s.parse::<f32>().ok());
+                    match (lo, hi) {
+                        (Some(lo), Some(hi)) => { lut.domain_min = [lo; 3]; lut.domain_max = [hi; 3]; }
+                        _ => return Err(t!("lut-bad-keyword", line = n + 1, keyword = "LUT_3D_INPUT_RANGE")),
+                    }
+                }
+                _ => {
+                    if parts[0].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                        continue; // ismeretlen kulcsszó
+                    }
+                    if lut.size == 0 {
+                        return Err(t!("lut-data-before-size", line = n + 1));
+                    }
+                    lut.data.push(parse3(&parts, n + 1)?);
+                }
+            }
+        }
+        if lut.size == 0 {
+            return Err(t!("lut-missing-size"));
+        }
+        if lut.data.len() != lut.size * lut.size * lut.size {
+            return Err(t!("lut-entries", expected = lut.size * lut.size * lut.size, found = lut.data.len()));
+        }
+        for i in 0..3 {
+            if lut.domain_max[i] <= lut.domain_min[i] {
+                return Err(t!("lut-domain"));
+            }
+        }
+        Ok(lut)
+    }
+
+    /// Trilineáris mintavétel, a bemenet a DOMAIN_MIN .. DOMAIN_MAX tartományra skálázva
+    pub fn sample(&self, color: [f32; 4]) -> [f32; 3] {
+        let n = self.size;
+        let mut i0 = [0usize; 3];
+        let mut i1 = [0usize; 3];
+        let mut f = [0.0f32; 3];
+        for c in 0..3 {
+            let t = ((color[c] - self.domain_min[c]) / (self.domain_max[c] - self.domain_min[c])).clamp(0.0, 1.0);
+            let p = t * (n - 1) as f32;
+            i0[c] = (p.floor() as usize).min(n - 1);
+            i1[c] = (i0[c] + 1).min(n - 1);
+            f[c] = p - i0[c] as f32;
+        }
+        let at = |r: usize, g: usize, b: usize| self.data[(b * n + g) * n + r];
+        let mut out = [0.0f32; 3];
+        for (c, value) in out.iter_mut().enumerate() {
+            let c00 = at(i0[0], i0[1], i0[2])[c] * (1.0 - f[0]) + at(i1[0], i0[1], i0[2])[c] * f[0];
+            let c10 = at(i0[0], i1[1], i0[2])[c] * (1.0 - f[0]) + at(i1[0], i1[1], i0[2])[c] * f[0];
+            let c01 = at(i0[0], i0[1], i1[2])[c] * (1.0 - f[0]) + at(i1[0], i0[1], i1[2])[c] * f[0];
+            let c11 = at(i0[0], i1[1], i1[2])[c] * (1.0 - f[0]) + at(i1[0], i1[1], i1[2])[c] * f[0];
+            let c0 = c00 * (1.0 - f[1]) + c10 * f[1];
+            let c1 = c01 * (1.0 - f[1]) + c11 * f[1];
+            *value = c0 * (1.0 - f[2]) + c1 * f[2];
+        }
+        out
+    }
+
+    pub fn blend(&self, color: &mut [f32; 4], intensity: f32) {
+        let looked = self.sample(*color);
+        for c in 0..3 {
+            color[c] = (color[c] + (looked[c] - color[c]) * intensity).clamp(0.0, 1.0);
+        }
+    }
+
+    /// A LUT átmintavételezése a Lut4ColorSettings / GPU elrendezésére (size^3 RGBA)
+    pub fn to_rgba8(&self, size: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(size * size * size * 4);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let color = [
+                        r as f32 / (size - 1) as f32,
+                        g as f32 / (size - 1) as f32,
+                        b as f32 / (size - 1) as f32,
+                        1.0,
+                    ];
+                    let out = self.sample(color);
+                    data.push((out[0].clamp(0.0, 1.0) * 255.0 + 0.5) as u8);
+                    data.push((out[1].clamp(0.0, 1.0) * 255.0 + 0.5) as u8);
+                    data.push((out[2].clamp(0.0, 1.0) * 255.0 + 0.5) as u8);
+                    data.push(255);
+                }
+            }
+        }
+        data
+    }
+}
+
+/// Az aktuális színkorrekciós lánc kiírása .cube fájlba (33x33x33)
+pub fn save_cube(path: &std::path::Path, colset: &ColorSettings, look: Option<&CubeLut>) -> Result<(), String> {
+    use std::fmt::Write;
+    let size = 33;
+    let mut text = String::with_capacity(size * size * size * 28 + 128);
+    let title = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let _ = writeln!(text, "# Created by IView");
+    let _ = writeln!(text, "TITLE \"{}\"", title);
+    let _ = writeln!(text, "LUT_3D_SIZE {}", size);
+    let _ = writeln!(text, "DOMAIN_MIN 0.0 0.0 0.0");
+    let _ = writeln!(text, "DOMAIN_MAX 1.0 1.0 1.0");
+    for b in 0..size {
+        for g in 0..size {
+            for r in 0..size {
+                let mut color = [
+                    r as f32 / (size - 1) as f32,
+                    g as f32 / (size - 1) as f32,
+                    b as f32 / (size - 1) as f32,
+                    1.0,
+                ];
+                colset.convert(&mut color);
+                if let Some(look) = look {
+                    if colset.is_look() {
+                        look.blend(&mut color, colset.look_intensity);
+                    }
+                }
+                let _ = writeln!(text, "{:.6} {:.6} {:.6}", color[0], color[1], color[2]);
+            }
+        }
+    }
+    std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
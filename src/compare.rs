@@ -0,0 +1,358 @@
+/*
+iview/src/compare.rs
+
+Comparing the current image with a second one: side by side, blink or pixel difference
+
+*/
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::codec::*;
+use crate::colors::*;
+use crate::metrics::*;
+use crate::exif_my::ExifBlock;
+use crate::file_handlers::image_file_dialog;
+use crate::bindings::Action;
+use crate::image_processing::*;
+use crate::pf32::Pf32;
+use crate::ImageViewer;
+use iview::t;
+
+pub const COMPARE_INFO_HEIGHT: f32 = 120.0;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum CompareMode {
+    SideBySide, // két kép egymás mellett
+    Blink,      // egy helyen, K-val váltogatva
+    Difference, // a két kép pixelenkénti eltérése
+}
+
+/// A második kép, amihez az aktuálisat hasonlítjuk
+pub struct Compare {
+    pub path: PathBuf,
+    pub format: SaveFormat,
+    pub original: image::DynamicImage,
+    pub rgba_image: Option<Arc<image::RgbaImage>>, // elforgatva, színkorrekcióval, mint az első kép
+    pub tiles: TileSet,
+    pub image_size: Pf32,
+    pub file_size: u64,
+    pub exif: Option<ExifBlock>,
+    pub mode: CompareMode,
+    pub show_second: bool, // villogtatásnál a második kép látszik
+    pub heat_map: bool,    // különbség hőtérképként, egyébként felerősítve
+    pub amplify: u8,
+    pub metrics: Option<DiffMetrics>, // az eredeti (korrekció nélküli) képekből
+    pub diff_tiles: TileSet,
+    pub diff_size: Pf32,
+    pub diff_for: Option<(Orientation, f32, Option<CropRect>, DiffStyle)>, // ehhez az álláshoz, szöghöz, kivágáshoz készült a különbség kép
+    pub image_for: Option<(ColorSettings, Option<PathBuf>)>, // ezekkel a beállításokkal és LUT-tal készült a rgba_image
+}
+
+impl Compare {
+    pub fn pick_color(&self, pixel_x: u32, pixel_y: u32) -> Option<egui::Color32> {
+        let rgba_image = self.rgba_image.as_ref()?;
+        if pixel_x < rgba_image.width() && pixel_y < rgba_image.height() {
+            let pixel = rgba_image.get_pixel(pixel_x, pixel_y);
+            return Some(egui::Color32::from_rgba_unmultiplied(pixel[0], pixel[1], pixel[2], pixel[3]));
+        }
+        None
+    }
+
+    pub fn diff_style(&self) -> DiffStyle {
+        if self.heat_map { DiffStyle::HeatMap } else { DiffStyle::Amplified(self.amplify) }
+    }
+
+    /// Új első kép: a különbséget és a mérőszámokat újra kell számolni
+    pub fn invalidate_diff(&mut self) {
+        self.metrics = None;
+        self.diff_for = None;
+    }
+
+    /// A panelen látható kép darabjai és mérete
+    pub fn shown<'a>(&'a self, first: (&'a TileSet, Pf32), second: bool) -> (&'a TileSet, Pf32) {
+        match self.mode {
+            CompareMode::Difference => (&self.diff_tiles, self.diff_size),
+            _ if second => (&self.tiles, self.image_size),
+            _ => first,
+        }
+    }
+}
+
+/// EXIF mezők név szerint (ismétlődő névnél az első), az összehasonlításhoz
+fn exif_values(exif: &Option<ExifBlock>) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    if let Some(exif) = exif {
+        for entry in &exif.entry_data_vector {
+            let mut text = exif.display_value(entry);
+            if text.chars().count() > 40 {
+                text = format!("{}…", text.chars().take(40).collect::<String>());
+            }
+            values.entry(entry.name.clone()).or_insert(text);
+        }
+    }
+    values
+}
+
+fn psnr_text(psnr: f64) -> String {
+    if psnr.is_infinite() { "∞".to_string() } else { format!("{:.2} dB", psnr) }
+}
+
+fn size_text(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+impl ImageViewer {
+
+    pub fn open_compare_dialog(&mut self, ctx: &egui::Context) {
+        let mut dialog = image_file_dialog().set_title(t!("compare-title"));
+        if let Some(folder) = &self.image_folder {
+            dialog = dialog.set_directory(folder);
+        }
+        if let Some(path) = dialog.pick_file() {
+            self.open_compare(ctx, &path);
+        }
+    }
+
+    /// A vágólapon lévő kép a második
+    pub fn open_compare_clipboard(&mut self, ctx: &egui::Context) {
+        match save_clipboard_image() {
+            Some(path) => self.open_compare(ctx, &path),
+            None => println!("{}", t!("compare-no-clipboard")),
+        }
+    }
+
+    pub fn open_compare(&mut self, ctx: &egui::Context, path: &PathBuf) {
+        let loaded = match load_image_file(path) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                println!("{}", t!("error", error = e));
+                return;
+            }
+        };
+        // a második kép a saját EXIF állásában, egyenesen
+        let original = loaded.orientation().apply(loaded.image);
+        let (mode, heat_map, amplify) = match &self.compare {
+            Some(c) => (c.mode, c.heat_map, c.amplify),
+            None => (CompareMode::SideBySide, false, 8),
+        };
+        self.compare = Some(Compare {
+            path: path.clone(),
+            format: loaded.format,
+            image_size: (original.width(), original.height()).into(),
+            original,
+            rgba_image: None,
+            tiles: TileSet::default(),
+            file_size: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+            exif: loaded.exif,
+            mode,
+            show_second: false,
+            heat_map,
+            amplify,
+            metrics: None,
+            diff_tiles: TileSet::default(),
+            diff_size: (0.0, 0.0).into(),
+            diff_for: None,
+            image_for: None,
+        });
+        self.review_compare(ctx);
+        self.want_magnify = -1.0;
+    }
+
+    pub fn close_compare(&mut self) {
+        if self.compare.take().is_some() {
+            self.want_magnify = -1.0;
+        }
+    }
+
+    /// A második kép ugyanazzal az orientációval és színkorrekcióval, mint az első
+    pub fn review_compare(&mut self, ctx: &egui::Context) {
+        let Some(compare) = &mut self.compare else {
+            return;
+        };
+        let default_settings = ColorSettings::default();
+        let settings = if self.show_original_only { &default_settings } else { &self.color_settings };
+        // a korrekció CPU-n fut: csak akkor számoljuk újra, ha a beállítás vagy a LUT változott
+        let image_key = (*settings, self.look.as_ref().map(|look| look.path.clone()));
+        if compare.image_for.as_ref() != Some(&image_key) {
+            let rgba_image = Arc::new(apply_color_settings(&compare.original, settings, 1.0, self.look.as_ref()).to_rgba8());
+            compare.image_size = rgba_image.dimensions().into();
+            compare.tiles = TileSet::new(ctx, rgba_image.clone(), "cmp");
+            compare.rgba_image = Some(rgba_image);
+            compare.image_for = Some(image_key);
+        }
+
+        // különbség: az eredeti pixeleken, csak az állást, a kiegyenesítést és a kivágást követi
+        let key = (self.color_settings.orientation, self.color_settings.rotation, self.color_settings.crop, compare.diff_style());
+        if compare.mode != CompareMode::Difference || compare.diff_for == Some(key) {
+            return;
+        }
+        let Some(original) = &self.original_image else {
+            return;
+        };
+        let first = self.exif_orientation.apply(original.clone()).to_rgba8();
+        let second = compare.original.to_rgba8();
+        if compare.metrics.is_none() {
+            compare.metrics = Some(compare_images(&first, &second));
+        }
+        let diff = image::DynamicImage::ImageRgba8(difference_image(&first, &second, key.3));
+        let diff = resize_and_orient(&diff, &self.color_settings, 1.0).to_rgba8();
+        compare.diff_size = diff.dimensions().into();
+        compare.diff_tiles = TileSet::new(ctx, Arc::new(diff), "dif");
+        compare.diff_for = Some(key);
+    }
+
+    pub fn set_compare_mode(&mut self, ctx: &egui::Context, mode: CompareMode) {
+        if let Some(compare) = &mut self.compare {
+            compare.mode = mode;
+            compare.show_second = false;
+            self.want_magnify = -1.0;
+            self.review_compare(ctx);
+        }
+    }
+
+    /// Egymás mellett, villogtatva vagy a különbség
+    pub fn toggle_compare_mode(&mut self, ctx: &egui::Context) {
+        if let Some(compare) = &self.compare {
+            let mode = match compare.mode {
+                CompareMode::SideBySide => CompareMode::Blink,
+                CompareMode::Blink => CompareMode::Difference,
+                CompareMode::Difference => CompareMode::SideBySide,
+            };
+            self.set_compare_mode(ctx, mode);
+        }
+    }
+
+    /// Villogtatásnál a másik kép, különbségnél a másik megjelenítés
+    pub fn blink_compare(&mut self, ctx: &egui::Context) {
+        if let Some(compare) = &mut self.compare {
+            match compare.mode {
+                CompareMode::Blink => compare.show_second = !compare.show_second,
+                CompareMode::Difference => {
+                    compare.heat_map = !compare.heat_map;
+                    self.review_compare(ctx);
+                }
+                CompareMode::SideBySide => {}
+            }
+        }
+    }
+
+    /// Hány kép látszik egymás mellett
+    pub fn compare_panes(&self) -> usize {
+        match &self.compare {
+            Some(compare) if compare.mode == CompareMode::SideBySide => 2,
+            _ => 1,
+        }
+    }
+
+    /// Közös információs sáv: méret, felbontás, eltérés mérőszámai és az eltérő EXIF mezők
+    pub fn draw_compare_info(&mut self, ctx: &egui::Context) {
+        let Some(compare) = &self.compare else {
+            return;
+        };
+        let first_name = self.image_full_path.as_deref().map(file_name).unwrap_or_default();
+        let first_size = self.file_meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        let first_exif = exif_values(&self.exif);
+        let second_exif = exif_values(&compare.exif);
+        let mut names: Vec<&String> = first_exif.keys().chain(second_exif.keys()).collect();
+        names.sort();
+        names.dedup();
+        let missing = "-".to_string();
+        let mut heat_map = compare.heat_map;
+        let mut amplify = compare.amplify;
+
+        egui::TopBottomPanel::bottom("compare_info")
+            .exact_height(COMPARE_INFO_HEIGHT)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| {
+                    egui::Grid::new("compare_grid").striped(true).show(ui, |ui| {
+                        let active = |second: bool| {
+                            let shown = compare.mode == CompareMode::Blink && compare.show_second == second;
+                            if shown { " ◀" } else { "" }
+                        };
+                        ui.label("");
+                        ui.label(egui::RichText::new(format!("A: {}{}", first_name, active(false))).strong());
+                        ui.label(egui::RichText::new(format!("B: {}{}", file_name(&compare.path), active(true))).strong());
+                        ui.end_row();
+
+                        ui.label(t!("compare-file-size"));
+                        ui.label(size_text(first_size));
+                        ui.label(size_text(compare.file_size));
+                        ui.end_row();
+
+                        ui.label(t!("compare-dimensions"));
+                        let (w, h) = if self.exif_orientation.swaps_axes() {
+                            (self.original_image_size.y, self.original_image_size.x)
+                        } else {
+                            (self.original_image_size.x, self.original_image_size.y)
+                        };
+                        ui.label(format!("{} x {} ({:?})", w, h, self.image_format));
+                        ui.label(format!("{} x {} ({:?})", compare.original.width(), compare.original.height(), compare.format));
+                        ui.end_row();
+
+                        if let (CompareMode::Difference, Some(m)) = (compare.mode, &compare.metrics) {
+                            let per_channel = |f: &dyn Fn(&ChannelError) -> String| {
+                                format!("R {}  G {}  B {}  A {}", f(&m.channels[0]), f(&m.channels[1]), f(&m.channels[2]), f(&m.channels[3]))
+                            };
+                            ui.label(t!("compare-difference"));
+                            ui.checkbox(&mut heat_map, format!("{}{}", t!("compare-heat-map"), self.bindings.hint(ctx, Action::Blink)));
+                            ui.add_enabled(!heat_map, egui::Slider::new(&mut amplify, 1..=64).text(t!("compare-amplify")));
+                            ui.end_row();
+
+                            if !m.same_size {
+                                ui.label(t!("compare-area"));
+                                ui.label(t!("compare-sizes-differ", width = m.width, height = m.height));
+                                ui.end_row();
+                            }
+                            ui.label(t!("compare-psnr"));
+                            ui.label(psnr_text(m.psnr));
+                            ui.label(per_channel(&|c| psnr_text(c.psnr)));
+                            ui.end_row();
+
+                            ui.label(t!("compare-ssim"));
+                            ui.label(format!("{:.5}", m.ssim));
+                            ui.end_row();
+
+                            ui.label(t!("compare-error"));
+                            ui.label(per_channel(&|c| format!("{:.3}", c.mean)));
+                            ui.label(per_channel(&|c| c.max.to_string()));
+                            ui.end_row();
+
+                            ui.label(t!("compare-differing"));
+                            let percent = if m.total_pixels == 0 { 0.0 } else { m.differing_pixels as f64 * 100.0 / m.total_pixels as f64 };
+                            ui.label(t!("compare-pixels-of", count = m.differing_pixels, total = m.total_pixels, percent = format!("{:.3}", percent)));
+                            ui.end_row();
+                        }
+
+                        for name in names {
+                            let a = first_exif.get(name).unwrap_or(&missing);
+                            let b = second_exif.get(name).unwrap_or(&missing);
+                            if a != b {
+                                ui.label(format!("{}:", name));
+                                ui.label(a);
+                                ui.label(b);
+                                ui.end_row();
+                            }
+                        }
+                    });
+                });
+            });
+
+        if let Some(compare) = &mut self.compare {
+            if compare.heat_map != heat_map || compare.amplify != amplify {
+                compare.heat_map = heat_map;
+                compare.amplify = amplify;
+                self.review_compare(ctx);
+            }
+        }
+    }
+}
@@ -0,0 +1,370 @@
+/*
+iview/src/crop.rs
+
+Crop tool: rubber-band rectangle with handles, fixed aspect ratios
+and composition guides, fine rotation with a horizon line.
+The crop and the rotation live in ColorSettings.
+
+*/
+
+use crate::colors::CropRect;
+use crate::geometry::{straighten_angle, MAX_FINE_ROTATION};
+use crate::ImageViewer;
+use iview::t;
+
+pub const CROP_BAR_HEIGHT: f32 = 60.0;
+
+// a fogantyúk érzékeny sávja képernyő pontban
+const HANDLE: f32 = 8.0;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CropRatio {
+    Free,
+    Fixed(u32, u32), // 1:1, 3:2, 4:3, 16:9
+    Original,        // az elforgatott kép arányai
+    Custom,          // CropOptions::custom
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CropGuide {
+    None,
+    Thirds,
+    Golden,
+    Grid,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CropOptions {
+    pub ratio: CropRatio,
+    pub custom: (u32, u32),
+    pub portrait: bool, // a rögzített arány álló helyzetben
+    pub guide: CropGuide,
+}
+
+impl Default for CropOptions {
+    fn default() -> Self {
+        Self {
+            ratio: CropRatio::Free,
+            custom: (5, 4),
+            portrait: false,
+            guide: CropGuide::Thirds,
+        }
+    }
+}
+
+/// Mit húz az egér: a téglalap melyik oldalait, vagy az egészet
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CropDrag {
+    New(egui::Pos2),            // új téglalap a kezdőpontból
+    Move(egui::Rect, egui::Pos2), // a téglalap és az egér a húzás kezdetén
+    Sides { left: bool, right: bool, top: bool, bottom: bool },
+}
+
+/// Kép pixelben a kivágás
+fn crop_to_px(crop: &CropRect, size: egui::Vec2) -> egui::Rect {
+    egui::Rect::from_min_size(egui::pos2(crop.x * size.x, crop.y * size.y), egui::vec2(crop.w * size.x, crop.h * size.y))
+}
+
+fn px_to_crop(rect: egui::Rect, size: egui::Vec2) -> CropRect {
+    CropRect { x: rect.min.x / size.x, y: rect.min.y / size.y, w: rect.width() / size.x, h: rect.height() / size.y }
+}
+
+/// Téglalap a rögzített sarokból a húzott pont felé, adott aránnyal, a képen belül
+fn ratio_from_anchor(anchor: egui::Pos2, to: egui::Pos2, ratio: Option<f32>, size: egui::Vec2) -> egui::Rect {
+    let to = to.clamp(egui::Pos2::ZERO, size.to_pos2());
+    let Some(ratio) = ratio else {
+        return egui::Rect::from_two_pos(anchor, to);
+    };
+    let (sx, sy) = (if to.x >= anchor.x { 1.0 } else { -1.0 }, if to.y >= anchor.y { 1.0 } else { -1.0 });
+    let mut w = (to.x - anchor.x).abs().max((to.y - anchor.y).abs() * ratio);
+    let mut h = w / ratio;
+    // ami kilóg, azt arányosan visszavesszük
+    let max_w = if sx > 0.0 { size.x - anchor.x } else { anchor.x };
+    let max_h = if sy > 0.0 { size.y - anchor.y } else { anchor.y };
+    let scale = (max_w / w.max(0.001)).min(max_h / h.max(0.001)).min(1.0);
+    w *= scale;
+    h *= scale;
+    egui::Rect::from_two_pos(anchor, anchor + egui::vec2(sx * w, sy * h))
+}
+
+/// A meglévő téglalap az adott arányra, a közepét megtartva
+fn fit_ratio(rect: egui::Rect, ratio: f32, size: egui::Vec2) -> egui::Rect {
+    let center = rect.center();
+    let mut w = rect.width().max(rect.height() * ratio);
+    let mut h = w / ratio;
+    let scale = (2.0 * center.x.min(size.x - center.x) / w.max(0.001))
+        .min(2.0 * center.y.min(size.y - center.y) / h.max(0.001))
+        .min(1.0);
+    w *= scale;
+    h *= scale;
+    egui::Rect::from_center_size(center, egui::vec2(w, h))
+}
+
+impl ImageViewer {
+
+    /// A kivágás kívánt aránya (szélesség / magasság), ha van
+    pub fn crop_ratio(&self) -> Option<f32> {
+        let options = &self.crop_options;
+        let (w, h) = match options.ratio {
+            CropRatio::Free => return None,
+            CropRatio::Fixed(w, h) => (w as f32, h as f32),
+            CropRatio::Original => return Some(self.image_size.x / self.image_size.y.max(1.0)),
+            CropRatio::Custom => (options.custom.0.max(1) as f32, options.custom.1.max(1) as f32),
+        };
+        Some(if options.portrait { h / w } else { w / h })
+    }
+
+    pub fn toggle_crop_tool(&mut self) {
+        if self.crop_tool {
+            self.close_crop_tool();
+        } else {
+            self.crop_tool = self.original_image.is_some();
+        }
+    }
+
+    pub fn close_crop_tool(&mut self) {
+        self.crop_tool = false;
+        self.horizon_tool = false;
+    }
+
+    /// Finom forgatás fokban; a kép újraépül
+    pub fn set_fine_rotation(&mut self, ctx: &egui::Context, degrees: f32) {
+        let degrees = degrees.clamp(-MAX_FINE_ROTATION, MAX_FINE_ROTATION);
+        if degrees != self.color_settings.rotation {
+            self.color_settings.rotation = degrees;
+            self.review(ctx, true, false);
+        }
+    }
+
+    /// Vízszint rajzolása: a kép fölött húzott vonalból a kiegyenesítő forgatás
+    pub fn draw_horizon(&self, ui: &mut egui::Ui, image_rect: egui::Rect) -> Option<f32> {
+        let id = ui.id().with("horizon_line");
+        let response = ui.interact(image_rect.intersect(ui.clip_rect()), id, egui::Sense::drag())
+            .on_hover_cursor(egui::CursorIcon::Crosshair);
+        if response.drag_started() {
+            if let Some(p) = response.interact_pointer_pos() {
+                ui.data_mut(|d| d.insert_temp(id, p));
+            }
+        }
+        if !response.dragged() && !response.drag_stopped() {
+            return None;
+        }
+        let start = ui.data(|d| d.get_temp::<egui::Pos2>(id))?;
+        let end = response.interact_pointer_pos().or(ui.ctx().pointer_latest_pos())?;
+        let painter = ui.painter();
+        painter.line_segment([start, end], egui::Stroke::new(3.0, egui::Color32::from_black_alpha(160)));
+        painter.line_segment([start, end], egui::Stroke::new(1.0, egui::Color32::YELLOW));
+        if response.drag_stopped() {
+            ui.data_mut(|d| d.remove::<egui::Pos2>(id));
+            // a kép nagyítása egyenletes, a képernyőn mért szög a képen is annyi
+            return straighten_angle((start.x, start.y), (end.x, end.y));
+        }
+        None
+    }
+
+    /// Arány váltásakor a meglévő kivágás is igazodik
+    fn apply_crop_ratio(&mut self) {
+        let size: egui::Vec2 = self.image_size.into();
+        if let (Some(ratio), Some(crop)) = (self.crop_ratio(), self.color_settings.crop) {
+            self.color_settings.crop = Some(px_to_crop(fit_ratio(crop_to_px(&crop, size), ratio, size), size));
+        }
+    }
+
+    /// A kivágás kirajzolása a kép fölé; aktív eszköznél a húzás eredménye az új kivágás
+    pub fn draw_crop(&self, ui: &mut egui::Ui, image_rect: egui::Rect) -> Option<CropRect> {
+        let crop = match self.color_settings.crop {
+            Some(crop) => crop,
+            None if self.crop_tool => CropRect::FULL,
+            None => return None,
+        };
+        let size: egui::Vec2 = self.image_size.into();
+        let scale = image_rect.width() / size.x.max(1.0);
+        let to_screen = |p: egui::Pos2| image_rect.min + p.to_vec2() * scale;
+        let to_image = |p: egui::Pos2| ((p - image_rect.min) / scale).to_pos2();
+        let px = crop_to_px(&crop, size);
+        let rect = egui::Rect::from_min_max(to_screen(px.min), to_screen(px.max));
+
+        // a kivágáson kívüli rész sötétebb
+        let painter = ui.painter();
+        let shade = egui::Color32::from_black_alpha(if self.crop_tool { 150 } else { 110 });
+        painter.rect_filled(egui::Rect::from_min_max(image_rect.min, egui::pos2(image_rect.max.x, rect.min.y)), 0.0, shade);
+        painter.rect_filled(egui::Rect::from_min_max(egui::pos2(image_rect.min.x, rect.max.y), image_rect.max), 0.0, shade);
+        painter.rect_filled(egui::Rect::from_min_max(egui::pos2(image_rect.min.x, rect.min.y), egui::pos2(rect.min.x, rect.max.y)), 0.0, shade);
+        painter.rect_filled(egui::Rect::from_min_max(egui::pos2(rect.max.x, rect.min.y), egui::pos2(image_rect.max.x, rect.max.y)), 0.0, shade);
+        painter.rect_stroke(rect, 0.0, egui::Stroke::new(1.0, egui::Color32::WHITE), egui::StrokeKind::Middle);
+        if !self.crop_tool {
+            return None;
+        }
+
+        // kompozíciós segédvonalak
+        let lines: Vec<f32> = match self.crop_options.guide {
+            CropGuide::None => Vec::new(),
+            CropGuide::Thirds => vec![1.0 / 3.0, 2.0 / 3.0],
+            CropGuide::Golden => vec![0.382, 0.618],
+            CropGuide::Grid => (1..8).map(|i| i as f32 / 8.0).collect(),
+        };
+        let guide = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(140));
+        for t in lines {
+            let x = rect.min.x + rect.width() * t;
+            let y = rect.min.y + rect.height() * t;
+            painter.line_segment([egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)], guide);
+            painter.line_segment([egui::pos2(rect.min.x, y), egui::pos2(rect.max.x, y)], guide);
+        }
+        for corner in [rect.left_top(), rect.right_top(), rect.left_bottom(), rect.right_bottom()] {
+            painter.rect_filled(egui::Rect::from_center_size(corner, egui::vec2(HANDLE, HANDLE)), 1.0, egui::Color32::WHITE);
+        }
+
+        // mi van az egér alatt
+        let hit = |p: egui::Pos2| {
+            let near = |a: f32, b: f32| (a - b).abs() <= HANDLE;
+            let inside_y = p.y >= rect.min.y - HANDLE && p.y <= rect.max.y + HANDLE;
+            let inside_x = p.x >= rect.min.x - HANDLE && p.x <= rect.max.x + HANDLE;
+            let (left, right) = (inside_y && near(p.x, rect.min.x), inside_y && near(p.x, rect.max.x));
+            let (top, bottom) = (inside_x && near(p.y, rect.min.y), inside_x && near(p.y, rect.max.y));
+            if left || right || top || bottom {
+                CropDrag::Sides { left, right, top, bottom }
+            } else if rect.contains(p) {
+                CropDrag::Move(px, to_image(p))
+            } else {
+                CropDrag::New(to_image(p))
+            }
+        };
+
+        let id = ui.id().with("crop_rect");
+        let response = ui.interact(image_rect.intersect(ui.clip_rect()), id, egui::Sense::drag());
+        if let Some(p) = response.hover_pos() {
+            let cursor = match hit(p) {
+                CropDrag::Sides { left, right, top, bottom } => {
+                    if (left && top) || (right && bottom) { egui::CursorIcon::ResizeNwSe }
+                    else if (right && top) || (left && bottom) { egui::CursorIcon::ResizeNeSw }
+                    else if left || right { egui::CursorIcon::ResizeHorizontal }
+                    else { egui::CursorIcon::ResizeVertical }
+                }
+                CropDrag::Move(..) => egui::CursorIcon::Move,
+                CropDrag::New(_) => egui::CursorIcon::Crosshair,
+            };
+            ui.ctx().set_cursor_icon(cursor);
+        }
+        if response.drag_started() {
+            if let Some(p) = response.interact_pointer_pos() {
+                ui.data_mut(|d| d.insert_temp(id, hit(p)));
+            }
+        }
+        if !response.dragged() {
+            return None;
+        }
+        let (Some(p), Some(drag)) = (response.interact_pointer_pos(), ui.data(|d| d.get_temp::<CropDrag>(id))) else {
+            return None;
+        };
+        let p = to_image(p);
+        let ratio = self.crop_ratio();
+        let new = match drag {
+            CropDrag::New(start) => ratio_from_anchor(start, p, ratio, size),
+            CropDrag::Move(start_rect, start) => {
+                let delta = (p - start)
+                    .max(-start_rect.min.to_vec2())
+                    .min(size - start_rect.max.to_vec2());
+                start_rect.translate(delta)
+            }
+            CropDrag::Sides { left, right, top, bottom } => {
+                let p = p.clamp(egui::Pos2::ZERO, size.to_pos2());
+                let mut r = px;
+                if left { r.min.x = p.x; }
+                if right { r.max.x = p.x; }
+                if top { r.min.y = p.y; }
+                if bottom { r.max.y = p.y; }
+                let corner = (left || right) && (top || bottom);
+                match ratio {
+                    // sarokból: a szemközti sarok marad
+                    Some(_) if corner => {
+                        let anchor = egui::pos2(if left { px.max.x } else { px.min.x }, if top { px.max.y } else { px.min.y });
+                        ratio_from_anchor(anchor, p, ratio, size)
+                    }
+                    // oldalról: a másik irány középről igazodik
+                    Some(ratio) => fit_ratio(egui::Rect::from_two_pos(r.min, r.max), ratio, size),
+                    None => egui::Rect::from_two_pos(r.min, r.max),
+                }
+            }
+        };
+        if new.width() < 1.0 || new.height() < 1.0 {
+            return None;
+        }
+        Some(px_to_crop(new, size))
+    }
+
+    /// Eszközsáv az arányokkal, segédvonalakkal és a kiegyenesítéssel, a kép alatt
+    pub fn draw_crop_bar(&mut self, ctx: &egui::Context) {
+        if !self.crop_tool {
+            return;
+        }
+        let before = self.crop_options;
+        let mut rotation = self.color_settings.rotation;
+        egui::TopBottomPanel::bottom("crop_bar")
+            .exact_height(CROP_BAR_HEIGHT)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let options = &mut self.crop_options;
+                    ui.label(t!("crop-label"));
+                    ui.selectable_value(&mut options.ratio, CropRatio::Free, t!("crop-free"));
+                    ui.selectable_value(&mut options.ratio, CropRatio::Fixed(1, 1), "1:1");
+                    ui.selectable_value(&mut options.ratio, CropRatio::Fixed(3, 2), "3:2");
+                    ui.selectable_value(&mut options.ratio, CropRatio::Fixed(4, 3), "4:3");
+                    ui.selectable_value(&mut options.ratio, CropRatio::Fixed(16, 9), "16:9");
+                    ui.selectable_value(&mut options.ratio, CropRatio::Original, t!("crop-original"));
+                    ui.selectable_value(&mut options.ratio, CropRatio::Custom, t!("crop-custom"));
+                    if options.ratio == CropRatio::Custom {
+                        ui.add(egui::DragValue::new(&mut options.custom.0).range(1..=100));
+                        ui.label(":");
+                        ui.add(egui::DragValue::new(&mut options.custom.1).range(1..=100));
+                    }
+                    ui.checkbox(&mut options.portrait, t!("crop-portrait"));
+                    ui.separator();
+                    egui::ComboBox::from_id_salt("crop_guide")
+                        .selected_text(match options.guide {
+                            CropGuide::None => t!("crop-guide-none"),
+                            CropGuide::Thirds => t!("crop-guide-thirds"),
+                            CropGuide::Golden => t!("crop-guide-golden"),
+                            CropGuide::Grid => t!("crop-guide-grid"),
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut options.guide, CropGuide::None, t!("crop-guide-none"));
+                            ui.selectable_value(&mut options.guide, CropGuide::Thirds, t!("crop-guide-thirds"));
+                            ui.selectable_value(&mut options.guide, CropGuide::Golden, t!("crop-guide-golden"));
+                            ui.selectable_value(&mut options.guide, CropGuide::Grid, t!("crop-guide-grid"));
+                        });
+                    ui.separator();
+                    if let Some(crop) = self.color_settings.crop {
+                        let (_, _, w, h) = crop.pixels(self.image_size.x as u32, self.image_size.y as u32);
+                        ui.label(format!("{} x {} px", w, h));
+                    }
+                    if ui.button(t!("crop-reset")).clicked() {
+                        self.color_settings.crop = None;
+                    }
+                    if ui.button(t!("crop-done")).clicked() {
+                        self.close_crop_tool();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("crop-straighten"));
+                    ui.add(egui::Slider::new(&mut rotation, -MAX_FINE_ROTATION..=MAX_FINE_ROTATION)
+                        .step_by(0.1)
+                        .fixed_decimals(1)
+                        .suffix("°"));
+                    if ui.button("0°").clicked() {
+                        rotation = 0.0;
+                    }
+                    if ui.selectable_label(self.horizon_tool, t!("crop-draw-horizon")).clicked() {
+                        self.horizon_tool = !self.horizon_tool;
+                    }
+                    if self.horizon_tool {
+                        ui.label(t!("crop-horizon-hint"));
+                    }
+                });
+            });
+        let after = self.crop_options;
+        if before.ratio != after.ratio || before.custom != after.custom || before.portrait != after.portrait {
+            self.apply_crop_ratio();
+        }
+        self.set_fine_rotation(ctx, rotation);
+    }
+}
@@ -0,0 +1,425 @@
+/*
+iview/src/exif_display.rs
+
+Human-readable EXIF values: exposure time, aperture, focal length with the
+35 mm equivalent, the coded values by name, GPS in degrees, minutes, seconds
+and decimal; the tags grouped by IFD with a text filter and a JSON export
+
+*/
+
+use serde_json::{json, Map, Value};
+
+use crate::exif_my::{ExifBlock, ExifTagEntry};
+use crate::exif_writer::Ifd;
+use crate::i18n::tr;
+use crate::t;
+
+/// Kódolt értékű tagok és a katalógus kulcsuk előtagja: `exif-<előtag>-<kód>`
+const CODED_TAGS: [(&str, &str); 38] = [
+    ("Orientation", "orientation"),
+    ("ResolutionUnit", "unit"),
+    ("FocalPlaneResolutionUnit", "unit"),
+    ("Compression", "compression"),
+    ("PhotometricInterpretation", "photometric"),
+    ("YCbCrPositioning", "ycbcr-positioning"),
+    ("ExposureProgram", "exposure-program"),
+    ("MeteringMode", "metering"),
+    ("LightSource", "light-source"),
+    ("ColorSpace", "color-space"),
+    ("SensingMethod", "sensing"),
+    ("FileSource", "file-source"),
+    ("SceneType", "scene-type"),
+    ("CustomRendered", "custom-rendered"),
+    ("ExposureMode", "exposure-mode"),
+    ("WhiteBalance", "white-balance"),
+    ("SceneCaptureType", "scene-capture"),
+    ("GainControl", "gain-control"),
+    ("Contrast", "contrast"),
+    ("Saturation", "saturation"),
+    ("Sharpness", "sharpness"),
+    ("SubjectDistanceRange", "distance-range"),
+    ("SensitivityType", "sensitivity-type"),
+    ("CompositeImage", "composite"),
+    ("InteropIndex", "interop"),
+    ("GPSLatitudeRef", "gps-lat-ref"),
+    ("GPSDestLatitudeRef", "gps-lat-ref"),
+    ("GPSLongitudeRef", "gps-lon-ref"),
+    ("GPSDestLongitudeRef", "gps-lon-ref"),
+    ("GPSAltitudeRef", "gps-altitude-ref"),
+    ("GPSStatus", "gps-status"),
+    ("GPSMeasureMode", "gps-measure-mode"),
+    ("GPSSpeedRef", "gps-speed-ref"),
+    ("GPSTrackRef", "gps-direction-ref"),
+    ("GPSImgDirectionRef", "gps-direction-ref"),
+    ("GPSDestBearingRef", "gps-direction-ref"),
+    ("GPSDestDistanceRef", "gps-distance-ref"),
+    ("GPSDifferential", "gps-differential"),
+];
+
+/// Ennyi elemnél hosszabb tömbből csak az eleje látszik
+const MAX_LIST_ITEMS: usize = 16;
+/// Ennyi bájtnál hosszabb bináris értékből csak az eleje látszik hexában
+const MAX_HEX_BYTES: usize = 32;
+
+/// A csoport címe az Info ablakhoz
+pub fn group_title(ifd: Ifd) -> String {
+    match ifd {
+        Ifd::Ifd0 => t!("exif-group-ifd0"),
+        Ifd::Exif => t!("exif-group-exif"),
+        Ifd::Gps => t!("exif-group-gps"),
+        Ifd::Interop => t!("exif-group-interop"),
+        Ifd::Ifd1 => t!("exif-group-thumbnail"),
+    }
+}
+
+/// A bejegyzés típusa, ahogy az open() beírta ("URATIONAL", "STRING" ...)
+fn format_of(entry: &ExifTagEntry) -> &str {
+    entry.value.get("type").and_then(|t| t.as_str()).unwrap_or("")
+}
+
+fn count_of(entry: &ExifTagEntry) -> usize {
+    entry.value.get("count").and_then(|c| c.as_u64()).unwrap_or(0) as usize
+}
+
+/// A számértékek; a törtek kiszámolva (a nullás nevezőjűek kimaradnak)
+fn numbers(entry: &ExifTagEntry) -> Vec<f64> {
+    let Some(val) = entry.value.get("val") else {
+        return Vec::new();
+    };
+    match format_of(entry) {
+        "URATIONAL" | "SRATIONAL" => {
+            let ratio = |v: &Value| -> Option<f64> {
+                let den = v.get(1)?.as_f64()?;
+                if den == 0.0 { None } else { Some(v.get(0)?.as_f64()? / den) }
+            };
+            match val.as_array() {
+                Some(items) if items.first().is_some_and(|v| v.is_array()) => items.iter().filter_map(ratio).collect(),
+                _ => ratio(val).into_iter().collect(),
+            }
+        }
+        _ => match val {
+            Value::Number(n) => n.as_f64().into_iter().collect(),
+            Value::Array(items) => items.iter().filter_map(|v| v.as_f64()).collect(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+fn number(entry: &ExifTagEntry) -> Option<f64> {
+    numbers(entry).first().copied()
+}
+
+/// Legfeljebb `decimals` tizedes, a felesleges nullák nélkül
+fn number_text(x: f64, decimals: usize) -> String {
+    let mut text = format!("{:.*}", decimals, x);
+    if text.contains('.') {
+        text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    if text == "-0" { "0".to_string() } else { text }
+}
+
+fn list_text(values: &[f64], decimals: usize, separator: &str) -> String {
+    values.iter().map(|x| number_text(*x, decimals)).collect::<Vec<_>>().join(separator)
+}
+
+/// Rövid záridő törtként (1/125 s), hosszabb másodpercben
+fn exposure_text(seconds: f64) -> String {
+    if seconds > 0.0 && seconds < 0.25 {
+        format!("1/{} s", (1.0 / seconds).round())
+    } else {
+        format!("{} s", number_text(seconds, 1))
+    }
+}
+
+fn aperture_text(f_number: f64) -> String {
+    format!("f/{}", number_text(f_number, 1))
+}
+
+/// "0230" -> 2.30
+fn version_text(bytes: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(bytes).ok()?.trim_end_matches('\0');
+    if text.len() != 4 || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let major: u32 = text[..2].parse().ok()?;
+    Some(format!("{}.{}", major, &text[2..]))
+}
+
+/// "2024:05:01 12:00:00" -> "2024-05-01 12:00:00"
+fn date_text(text: &str) -> String {
+    let parts = (text.get(..4), text.get(4..5), text.get(5..7), text.get(7..8), text.get(8..));
+    match parts {
+        (Some(year), Some(":"), Some(month), Some(":"), Some(rest)) => format!("{}-{}-{}", year, month, rest),
+        _ => text.to_string(),
+    }
+}
+
+/// UserComment és társai: 8 bájt karakterkód ("ASCII", "UNICODE", "JIS", nullák), utána a szöveg
+fn comment_text(bytes: &[u8], motorola: bool) -> String {
+    let text = if bytes.len() < 8 {
+        String::from_utf8_lossy(bytes).to_string()
+    } else if bytes.starts_with(b"UNICODE") {
+        let units: Vec<u16> = bytes[8..].chunks_exact(2)
+            .map(|c| if motorola { u16::from_be_bytes([c[0], c[1]]) } else { u16::from_le_bytes([c[0], c[1]]) })
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(&bytes[8..]).to_string()
+    };
+    text.trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string()
+}
+
+/// Bináris érték: olvasható ASCII szövegként, különben hexában (a hosszú csak az elején)
+fn bytes_text(bytes: &[u8]) -> String {
+    let trimmed = match bytes.iter().rposition(|&b| b != 0) {
+        Some(last) => &bytes[..=last],
+        None => &[][..],
+    };
+    if !trimmed.is_empty() && trimmed.iter().all(|&b| (0x20..0x7f).contains(&b)) {
+        return String::from_utf8_lossy(trimmed).trim().to_string();
+    }
+    let hex = bytes.iter().take(MAX_HEX_BYTES).map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ");
+    if bytes.len() > MAX_HEX_BYTES {
+        format!("{} … {}", hex, t!("exif-value-bytes", size = bytes.len()))
+    } else {
+        hex
+    }
+}
+
+/// Fok, perc, másodperc és tizedes fok; a perc tizedesként is jöhet, ezért a tizedes fokból bontjuk újra
+fn dms_text(values: &[f64], reference: &str) -> Option<String> {
+    let decimal = values.first()? + values.get(1).unwrap_or(&0.0) / 60.0 + values.get(2).unwrap_or(&0.0) / 3600.0;
+    let degrees = decimal.trunc();
+    let minutes = ((decimal - degrees) * 60.0).trunc();
+    let seconds = (decimal - degrees - minutes / 60.0) * 3600.0;
+    let signed = if reference == "S" || reference == "W" { -decimal } else { decimal };
+    let dms = format!("{}° {}' {:.2}\" {}", degrees, minutes, seconds, reference);
+    Some(format!("{} ({:.6})", dms.trim_end(), signed))
+}
+
+fn flash_text(code: i64) -> String {
+    if code & 0x20 != 0 {
+        return t!("exif-flash-none");
+    }
+    let mut parts = vec![if code & 1 != 0 { t!("exif-flash-fired") } else { t!("exif-flash-not-fired") }];
+    match (code >> 3) & 3 {
+        1 => parts.push(t!("exif-flash-compulsory")),
+        2 => parts.push(t!("exif-flash-suppressed")),
+        3 => parts.push(t!("exif-flash-auto")),
+        _ => {}
+    }
+    match (code >> 1) & 3 {
+        2 => parts.push(t!("exif-flash-no-return")),
+        3 => parts.push(t!("exif-flash-return")),
+        _ => {}
+    }
+    if code & 0x40 != 0 {
+        parts.push(t!("exif-flash-red-eye"));
+    }
+    parts.join(", ")
+}
+
+fn components_text(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|b| match b {
+            0 => "-",
+            1 => "Y",
+            2 => "Cb",
+            3 => "Cr",
+            4 => "R",
+            5 => "G",
+            6 => "B",
+            _ => "?",
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl ExifBlock {
+
+    /// Az érték olvasható alakban; amit nem ismerünk, az típusa szerint
+    pub fn display_value(&self, entry: &ExifTagEntry) -> String {
+        self.coded_value(entry)
+            .or_else(|| self.readable_value(entry))
+            .unwrap_or_else(|| self.plain_value(entry))
+    }
+
+    /// A látható bejegyzések IFD-nként (IFD0, Exif, GPS, Interop, bélyegkép) az olvasható értékkel;
+    /// a szűrő a névben és az értékben keres, kis-nagybetű mindegy
+    pub fn grouped_fields(&self, filter: &str) -> Vec<(Ifd, Vec<(&ExifTagEntry, String)>)> {
+        let filter = filter.trim().to_lowercase();
+        Ifd::ALL.iter()
+            .filter_map(|&ifd| {
+                let rows: Vec<(&ExifTagEntry, String)> = self.entry_data_vector.iter()
+                    .filter(|entry| entry.ifd == ifd.name())
+                    .map(|entry| (entry, self.display_value(entry)))
+                    .filter(|(entry, text)| {
+                        filter.is_empty()
+                            || entry.name.to_lowercase().contains(&filter)
+                            || text.to_lowercase().contains(&filter)
+                    })
+                    .collect();
+                if rows.is_empty() { None } else { Some((ifd, rows)) }
+            })
+            .collect()
+    }
+
+    /// A szűrt csoportok JSON-ban: IFD -> tag -> {type, count, val, text}
+    pub fn grouped_json(&self, filter: &str) -> String {
+        let mut groups = Map::new();
+        for (ifd, rows) in self.grouped_fields(filter) {
+            let mut tags = Map::new();
+            for (entry, text) in rows {
+                let mut value = entry.value.clone();
+                if let Value::Object(map) = &mut value {
+                    map.insert("text".into(), json!(text));
+                }
+                tags.insert(entry.name.clone(), value);
+            }
+            groups.insert(ifd.name().to_string(), Value::Object(tags));
+        }
+        serde_json::to_string_pretty(&Value::Object(groups)).unwrap_or_default()
+    }
+
+    /// Ugyanabban az IFD-ben levő másik tag (pl. a hozzá tartozó Ref vagy mértékegység)
+    fn sibling(&self, entry: &ExifTagEntry, name: &str) -> Option<&ExifTagEntry> {
+        self.entry_data_vector.iter().find(|e| e.ifd == entry.ifd && e.name == name)
+    }
+
+    fn sibling_text(&self, entry: &ExifTagEntry, name: &str) -> String {
+        self.sibling(entry, name)
+            .and_then(|e| e.value.get("val"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    }
+
+    /// Az érték nyers bájtjai a blokkból (a szövegek az open()-ben az első nullánál elvágódnak)
+    fn raw_bytes(&self, entry: &ExifTagEntry) -> Option<&[u8]> {
+        match format_of(entry) {
+            "UNDEFINED" | "BYTE" | "SBYTE" | "STRING" | "UTF_8" => {
+                self.raw_exif.get(entry.offset..entry.offset.checked_add(count_of(entry))?)
+            }
+            _ => None,
+        }
+    }
+
+    /// A kódolt értékek neve a katalógusból; ismeretlen kódnál None
+    fn coded_value(&self, entry: &ExifTagEntry) -> Option<String> {
+        let prefix = CODED_TAGS.iter().find(|(name, _)| *name == entry.name)?.1;
+        let code = match format_of(entry) {
+            "STRING" | "UTF_8" => entry.value.get("val")?.as_str()?.trim().to_string(),
+            "UNDEFINED" | "BYTE" => self.raw_bytes(entry)?.first()?.to_string(),
+            _ => (number(entry)? as i64).to_string(),
+        };
+        let key = format!("exif-{}-{}", prefix, code);
+        let text = tr(&key);
+        if text == key { None } else { Some(text) }
+    }
+
+    /// A mértékegységgel, tört alakban vagy összetetten kiírt tagok
+    fn readable_value(&self, entry: &ExifTagEntry) -> Option<String> {
+        let value = number(entry);
+        let text = match entry.name.as_str() {
+            "ExposureTime" => exposure_text(value?),
+            "ShutterSpeedValue" => exposure_text(2f64.powf(-value?)),
+            "FNumber" => aperture_text(value?),
+            "ApertureValue" | "MaxApertureValue" => aperture_text(2f64.powf(value? / 2.0)),
+            "ExposureBiasValue" => {
+                let ev = value?;
+                if ev.abs() < 0.005 { "0 EV".to_string() } else { format!("{}{} EV", if ev > 0.0 { "+" } else { "-" }, number_text(ev.abs(), 2)) }
+            }
+            "BrightnessValue" => format!("{} EV", number_text(value?, 2)),
+            "FocalLength" => {
+                let equiv = self.find_tag("FocalLengthIn35mmFilm", 0, true).and_then(|e| number(&e)).unwrap_or(0.0);
+                if equiv > 0.0 {
+                    t!("exif-focal-equiv", focal = number_text(value?, 1), equiv = number_text(equiv, 0))
+                } else {
+                    format!("{} mm", number_text(value?, 1))
+                }
+            }
+            "FocalLengthIn35mmFilm" => format!("{} mm", number_text(value?, 0)),
+            "SubjectDistance" => format!("{} m", number_text(value?, 2)),
+            "ISOSpeedRatings" | "ISOSpeed" | "StandardOutputSensitivity" | "RecommendedExposureIndex" => {
+                format!("ISO {}", list_text(&numbers(entry), 0, ", "))
+            }
+            "XResolution" | "YResolution" => {
+                let unit = self.sibling(entry, "ResolutionUnit").and_then(number);
+                format!("{} {}", number_text(value?, 2), if unit == Some(3.0) { "dpcm" } else { "dpi" })
+            }
+            "DigitalZoomRatio" => format!("{}×", number_text(value?, 2)),
+            "Flash" => flash_text(value? as i64),
+            "ExifVersion" | "FlashPixVersion" | "InteropVersion" => version_text(self.raw_bytes(entry)?)?,
+            "GPSVersionID" => list_text(&numbers(entry), 0, "."),
+            "ComponentsConfiguration" => components_text(self.raw_bytes(entry)?),
+            "UserComment" | "GPSProcessingMethod" | "GPSAreaInformation" => comment_text(self.raw_bytes(entry)?, self.motorola_order),
+            "DateTime" | "DateTimeOriginal" | "DateTimeDigitized" | "GPSDateStamp" => date_text(entry.value.get("val")?.as_str()?),
+            "GPSLatitude" | "GPSLongitude" | "GPSDestLatitude" | "GPSDestLongitude" => {
+                dms_text(&numbers(entry), &self.sibling_text(entry, &format!("{}Ref", entry.name)))?
+            }
+            "GPSAltitude" => {
+                let below = self.sibling(entry, "GPSAltitudeRef").and_then(number) == Some(1.0);
+                format!("{} m", number_text(if below { -value? } else { value? }, 1))
+            }
+            "GPSTimeStamp" => {
+                let hms = numbers(entry);
+                format!("{:02}:{:02}:{:02} UTC", *hms.first()? as u32, *hms.get(1)? as u32, hms.get(2)?.round() as u32)
+            }
+            "GPSSpeed" => {
+                let unit = match self.sibling_text(entry, "GPSSpeedRef").as_str() {
+                    "M" => "mph",
+                    "N" => "kn",
+                    _ => "km/h",
+                };
+                format!("{} {}", number_text(value?, 1), unit)
+            }
+            "GPSDestDistance" => {
+                let unit = match self.sibling_text(entry, "GPSDestDistanceRef").as_str() {
+                    "M" => "mi",
+                    "N" => "nmi",
+                    _ => "km",
+                };
+                format!("{} {}", number_text(value?, 2), unit)
+            }
+            "GPSTrack" | "GPSImgDirection" | "GPSDestBearing" => {
+                let degrees = format!("{}°", number_text(value?, 1));
+                match self.sibling_text(entry, &format!("{}Ref", entry.name)).as_str() {
+                    "T" => format!("{} ({})", degrees, t!("exif-gps-direction-ref-T")),
+                    "M" => format!("{} ({})", degrees, t!("exif-gps-direction-ref-M")),
+                    _ => degrees,
+                }
+            }
+            "GPSHPositioningError" => format!("{} m", number_text(value?, 1)),
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    /// Ismeretlen tag: a típusa szerint, a hosszú tömbök és bináris adatok rövidítve
+    fn plain_value(&self, entry: &ExifTagEntry) -> String {
+        let Some(val) = entry.value.get("val") else {
+            return entry.value.to_string();
+        };
+        match format_of(entry) {
+            "URATIONAL" | "SRATIONAL" => list_text(&numbers(entry), 4, ", "),
+            "UNDEFINED" => match self.raw_bytes(entry) {
+                Some(bytes) => bytes_text(bytes),
+                None => t!("exif-value-bytes", size = count_of(entry)),
+            },
+            "BYTE" | "SBYTE" if !val.is_array() => t!("exif-value-bytes", size = count_of(entry)),
+            _ => match val {
+                Value::String(s) => s.trim().to_string(),
+                Value::Array(items) => {
+                    let mut text = items.iter().take(MAX_LIST_ITEMS).map(|v| v.to_string()).collect::<Vec<_>>().join(", ");
+                    if items.len() > MAX_LIST_ITEMS {
+                        text.push_str(&format!(", … ({})", items.len()));
+                    }
+                    text
+                }
+                Value::Null => String::new(),
+                other => other.to_string(),
+            },
+        }
+    }
+}
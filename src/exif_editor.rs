@@ -0,0 +1,278 @@
+/*
+iview/src/exif_editor.rs
+
+EXIF tag editor in the Info window: the tags of every IFD with editable
+values, tags added and deleted, the thumbnail regenerated or removed, the
+byte order switched; applied to the view's EXIF (written on save) or
+straight into the JPEG file without touching the image data
+
+*/
+
+use crate::codec::{read_exif, SaveFormat};
+use crate::colors::*;
+use crate::exif_my::ExifBlock;
+use crate::sidecar::*;
+use crate::ImageViewer;
+use iview::exif_writer::*;
+use iview::jpeg_lossless::write_jpeg_exif_file;
+use iview::t;
+
+/// Ennél hosszabb értéket (pl. MakerNote) nem szerkesztünk szövegként
+const MAX_EDIT_TEXT: usize = 512;
+
+struct EditRow {
+    ifd: Ifd,
+    tag: u16,
+    format: u16,
+    original: String,
+    text: String,
+}
+
+#[derive(PartialEq)]
+pub enum ExifEditorAction {
+    None,
+    Apply,
+    WriteFile,
+    Close,
+}
+
+pub struct ExifEditor {
+    ifds: ExifIfds,
+    rows: Vec<EditRow>,
+    names: ExifBlock, // a tag név táblák
+    new_ifd: Ifd,
+    new_tag: String,
+    new_format: u16,
+    new_value: String,
+    error: Option<String>,
+}
+
+/// A tag neve a táblákból, ismeretlennél az azonosítója
+fn tag_name(names: &ExifBlock, ifd: Ifd, tag: u16) -> String {
+    let name = if ifd == Ifd::Gps { names.get_gps_tag(tag).name } else { names.get_exif_tag(tag).name };
+    if name.starts_with('_') { format!("0x{:04X}", tag) } else { name }
+}
+
+impl ExifEditor {
+
+    /// EXIF nélküli képhez üres blokkal indul
+    pub fn new(exif: Option<&ExifBlock>) -> Result<ExifEditor, String> {
+        let ifds = match exif {
+            Some(exif) => exif.ifds()?,
+            None => ExifIfds::default(),
+        };
+        let mut rows = Vec::new();
+        for ifd in Ifd::ALL {
+            for entry in ifds.dir(ifd) {
+                let text = entry.value.to_text();
+                rows.push(EditRow { ifd, tag: entry.tag, format: entry.value.format(), original: text.clone(), text });
+            }
+        }
+        Ok(ExifEditor {
+            ifds,
+            rows,
+            names: ExifBlock::default(),
+            new_ifd: Ifd::Exif,
+            new_tag: String::new(),
+            new_format: 2,
+            new_value: String::new(),
+            error: None,
+        })
+    }
+
+    /// Név (kis-nagybetű mindegy) vagy 0x... azonosító
+    fn tag_id(&self, ifd: Ifd, text: &str) -> Option<u16> {
+        let text = text.trim();
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            return u16::from_str_radix(hex, 16).ok();
+        }
+        if ifd == Ifd::Gps {
+            self.names.gps_tags.iter().find(|t| t.name.eq_ignore_ascii_case(text)).map(|t| t.id)
+        } else {
+            self.names.exif_tags.iter().find(|t| t.name.eq_ignore_ascii_case(text)).map(|t| t.id)
+        }
+    }
+
+    /// A módosított szövegek visszaírása az IFD-kbe; a változatlan értékek bájtra pontosan megmaradnak
+    pub fn commit(&mut self) -> Result<ExifIfds, String> {
+        for row in &mut self.rows {
+            if row.text != row.original {
+                let value = TagValue::parse(row.format, &row.text)
+                    .map_err(|e| format!("{} {}: {}", row.ifd.name(), tag_name(&self.names, row.ifd, row.tag), e))?;
+                self.ifds.set(row.ifd, row.tag, value);
+                row.original = row.text.clone();
+            }
+        }
+        Ok(self.ifds.clone())
+    }
+
+    fn add_tag(&mut self) -> Result<(), String> {
+        let tag = self.tag_id(self.new_ifd, &self.new_tag).ok_or_else(|| t!("exif-unknown-tag", tag = self.new_tag.clone()))?;
+        let value = TagValue::parse(self.new_format, &self.new_value)?;
+        let text = value.to_text();
+        self.ifds.set(self.new_ifd, tag, value);
+        match self.rows.iter_mut().find(|r| r.ifd == self.new_ifd && r.tag == tag) {
+            Some(row) => {
+                row.format = self.new_format;
+                row.original = text.clone();
+                row.text = text;
+            }
+            None => self.rows.push(EditRow { ifd: self.new_ifd, tag, format: self.new_format, original: text.clone(), text }),
+        }
+        self.new_tag.clear();
+        self.new_value.clear();
+        Ok(())
+    }
+
+    /// A szerkesztő az Info ablakon belül; `image` a fájl pixelei (a bélyegképhez)
+    pub fn ui(&mut self, ui: &mut egui::Ui, image: Option<&image::DynamicImage>, can_write: bool) -> ExifEditorAction {
+        let mut action = ExifEditorAction::None;
+        ui.horizontal(|ui| {
+            ui.label(t!("exif-edit-byte-order"));
+            ui.radio_value(&mut self.ifds.motorola_order, false, "II (Intel)");
+            ui.radio_value(&mut self.ifds.motorola_order, true, "MM (Motorola)");
+        });
+        ui.horizontal(|ui| {
+            match &self.ifds.thumbnail {
+                Some(thumbnail) => ui.label(t!("exif-edit-thumbnail", size = thumbnail.len())),
+                None => ui.label(t!("exif-edit-no-thumbnail")),
+            };
+            if let Some(image) = image {
+                if ui.button(t!("exif-edit-thumbnail-regenerate")).clicked() {
+                    match thumbnail_jpeg(image) {
+                        Ok(thumbnail) => self.ifds.thumbnail = Some(thumbnail),
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+            }
+            if self.ifds.thumbnail.is_some() && ui.button(t!("exif-edit-thumbnail-remove")).clicked() {
+                self.ifds.thumbnail = None;
+            }
+        });
+
+        let mut delete = None;
+        egui::ScrollArea::vertical()
+            .id_salt("exif_editor_scroll")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("exif_editor_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (i, row) in self.rows.iter_mut().enumerate() {
+                            ui.label(row.ifd.name());
+                            ui.label(tag_name(&self.names, row.ifd, row.tag)).on_hover_text(format!("0x{:04X}", row.tag));
+                            ui.label(FORMATS.iter().find(|(f, _)| *f == row.format).map(|(_, n)| *n).unwrap_or("?"));
+                            if row.original.len() > MAX_EDIT_TEXT {
+                                ui.label(t!("exif-edit-bytes", size = self.ifds.get(row.ifd, row.tag).map(|v| v.count()).unwrap_or(0)));
+                            } else {
+                                ui.add(egui::TextEdit::singleline(&mut row.text).desired_width(220.0));
+                            }
+                            if ui.small_button("🗑").on_hover_text(t!("exif-edit-delete")).clicked() {
+                                delete = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        if let Some(i) = delete {
+            let row = self.rows.remove(i);
+            self.ifds.remove(row.ifd, row.tag);
+        }
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("exif_editor_new_ifd")
+                .selected_text(self.new_ifd.name())
+                .show_ui(ui, |ui| {
+                    for ifd in Ifd::ALL {
+                        ui.selectable_value(&mut self.new_ifd, ifd, ifd.name());
+                    }
+                });
+            ui.add(egui::TextEdit::singleline(&mut self.new_tag).hint_text(t!("exif-edit-tag-hint")).desired_width(140.0));
+            egui::ComboBox::from_id_salt("exif_editor_new_format")
+                .selected_text(FORMATS.iter().find(|(f, _)| *f == self.new_format).map(|(_, n)| *n).unwrap_or("?"))
+                .show_ui(ui, |ui| {
+                    for (format, name) in FORMATS {
+                        ui.selectable_value(&mut self.new_format, format, name);
+                    }
+                });
+            ui.add(egui::TextEdit::singleline(&mut self.new_value).hint_text(t!("exif-edit-value-hint")).desired_width(140.0));
+            if ui.button(t!("exif-edit-add")).clicked() {
+                self.error = self.add_tag().err();
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+        ui.horizontal(|ui| {
+            if ui.button(t!("exif-edit-apply")).on_hover_text(t!("exif-edit-apply-hint")).clicked() {
+                action = ExifEditorAction::Apply;
+            }
+            if ui.add_enabled(can_write, egui::Button::new(t!("exif-edit-write"))).on_hover_text(t!("exif-edit-write-hint")).clicked() {
+                action = ExifEditorAction::WriteFile;
+            }
+            if ui.button(t!("exif-edit-close")).clicked() {
+                action = ExifEditorAction::Close;
+            }
+        });
+        action
+    }
+}
+
+impl ImageViewer {
+
+    /// Megnyitja vagy bezárja a szerkesztőt a látható kép EXIF-jével
+    pub fn toggle_exif_editor(&mut self) {
+        if self.exif_editor.is_some() {
+            self.exif_editor = None;
+            return;
+        }
+        match ExifEditor::new(self.exif.as_ref()) {
+            Ok(editor) => self.exif_editor = Some(editor),
+            Err(e) => println!("{}", t!("error-exif", error = e)),
+        }
+    }
+
+    /// A szerkesztett blokk a nézet EXIF-je lesz (mentéskor ez kerül a fájlba); `write_file` esetén a JPEG-be is beírja
+    pub fn apply_exif_editor(&mut self, ctx: &egui::Context, write_file: bool) {
+        let Some(editor) = &mut self.exif_editor else {
+            return;
+        };
+        let mut exif = self.exif.clone().unwrap_or_default();
+        let result = editor.commit()
+            .and_then(|ifds| exif.rebuild(&ifds))
+            .and_then(|_| read_exif(&exif.raw_exif));
+        let (exif, resolution, orientation) = match result {
+            Ok(read) => read,
+            Err(e) => {
+                editor.error = Some(e);
+                return;
+            }
+        };
+        if write_file {
+            let Some((path, format, _)) = self.displayed_image.clone() else {
+                return;
+            };
+            if format != SaveFormat::Jpeg {
+                return;
+            }
+            println!("{}", t!("saving", file = path.display()));
+            if let Err(e) = rewrite_keeping_sidecar(&path, self.sidecar_mode, None, || write_jpeg_exif_file(&path, &exif.raw_exif)) {
+                editor.error = Some(e);
+                return;
+            }
+            self.file_meta = std::fs::metadata(&path).ok();
+        }
+        let orientation = Orientation::from_exif(orientation as u16);
+        if resolution.is_some() {
+            self.resolution = resolution;
+        }
+        self.exif = Some(exif);
+        self.exif_editor = ExifEditor::new(self.exif.as_ref()).ok();
+        if orientation != self.exif_orientation {
+            self.exif_orientation = orientation;
+            self.color_settings.crop = None; // a kivágás a korábbi állás szerint volt
+            self.review(ctx, true, true);
+        }
+    }
+}
@@ -4,6 +4,9 @@ use serde_json::{Value, Map, json};
 use std::io::Cursor;
 use image::codecs::jpeg::JpegEncoder;
 
+use crate::exif_writer::{ExifIfds, Ifd, TagValue};
+use crate::t;
+
 #[macro_export]
 macro_rules! apply_exif_tags {
     ($callback:ident) => {
@@ -494,6 +497,13 @@ impl FMT {
             _ => FMT::NUM_FORMATS,
         }
     }
+    /// Egy elem mérete bájtban (az UTF_8 kódja kívül esik a táblán, ismeretlen formátumnál 0)
+    pub fn bytes(&self) -> usize {
+        match self {
+            FMT::UTF_8 => 1,
+            f => BYTESPERFORMAT.get(f.clone() as usize).copied().unwrap_or(0),
+        }
+    }
     /*pub fn to(v:&str) -> Self {
         match v {
             "NONE"              => FMT::NONE,
@@ -534,6 +544,7 @@ pub struct ExifBlock {
     pub make : String,
     pub thumbnailsize: usize,
     pub thumbnailoffset: usize,
+    pub visited_dirs: Vec<usize>, // a bejárt IFD-k, a körkörös hivatkozás ellen
 }
 
 
@@ -554,6 +565,7 @@ impl Default for ExifBlock {
             make : "".into(),
             thumbnailsize: 0,
             thumbnailoffset: 0,
+            visited_dirs: Vec::new(),
         };
         tmp.init_exif_tags();
         tmp.init_gps_tags();
@@ -566,7 +578,8 @@ pub struct ExifTagEntry {
     pub name: String,
     pub value: serde_json::Value,
     pub offset: usize,
-    //pub ifd: String,    // parent pl. "IFD0", "GPS" is unknown now
+    #[serde(default)]
+    pub ifd: String,    // a tartalmazó IFD neve (Ifd::name), pl. "IFD0", "GPS"; az Exiflength-nél üres
 }
 
 impl ExifBlock {
@@ -686,133 +699,115 @@ impl ExifBlock {
         }
     }
     
-    fn read_buff_u16(&self, buff :&[u8], pos: usize) -> u16 {
-        let bytes = buff[pos..pos + 2].try_into().unwrap();
-        if self.motorola_order { u16::from_be_bytes(bytes) }
-        else { u16::from_le_bytes(bytes) }
+    /// `len` bájt a blokkból `pos`-tól; a blokkon túli hivatkozás hiba, nem pánik
+    fn bytes_at(&self, pos: usize, len: usize) -> Result<&[u8], String> {
+        pos.checked_add(len)
+            .and_then(|end| self.raw_exif.get(pos..end))
+            .ok_or_else(|| t!("exif-corrupt", reason = t!("exif-truncated")))
     }
-    
-    fn read_buff_u32(&self, buff :&[u8], pos: usize) -> u32 {
-        let bytes = buff[pos..pos + 4].try_into().unwrap();
-        if self.motorola_order { u32::from_be_bytes(bytes) }
-        else { u32::from_le_bytes(bytes) }
+
+    fn read_u16(&self, pos: usize) -> Result<u16, String> {
+        let mut bytes = [0u8; 2];
+        bytes.copy_from_slice(self.bytes_at(pos, 2)?);
+        Ok(if self.motorola_order { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
     }
 
-    
-    fn read_u16(&self, pos: usize) -> u16 {
-        let bytes = self.raw_exif[pos..pos + 2].try_into().unwrap();
-        if self.motorola_order { u16::from_be_bytes(bytes) }
-        else { u16::from_le_bytes(bytes) }
+    fn read_u32(&self, pos: usize) -> Result<u32, String> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.bytes_at(pos, 4)?);
+        Ok(if self.motorola_order { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
     }
-    
-    fn read_u32(&self, pos: usize) -> u32 {
-        let bytes = self.raw_exif[pos..pos + 4].try_into().unwrap();
-        if self.motorola_order { u32::from_be_bytes(bytes) }
-        else { u32::from_le_bytes(bytes) }
+
+    fn read_i32(&self, pos: usize) -> Result<i32, String> {
+        Ok(self.read_u32(pos)? as i32)
     }
-    
-    fn read_i32(&self, pos: usize) -> i32 {
-        let bytes = self.raw_exif[pos..pos + 4].try_into().unwrap();
-        if self.motorola_order { i32::from_be_bytes(bytes) }
-        else { i32::from_le_bytes(bytes) }
+
+    fn read_f32(&self, pos: usize) -> Result<f32, String> {
+        Ok(f32::from_bits(self.read_u32(pos)?))
     }
-    
-    fn read_f32(&self, pos: usize) -> f32 {
-        let bytes = self.raw_exif[pos..pos + 4].try_into().unwrap();
-        if self.motorola_order { f32::from_be_bytes(bytes) }
-        else { f32::from_le_bytes(bytes) }
+
+    fn read_f64(&self, pos: usize) -> Result<f64, String> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.bytes_at(pos, 8)?);
+        Ok(if self.motorola_order { f64::from_be_bytes(bytes) } else { f64::from_le_bytes(bytes) })
     }
-    
-    fn read_f64(&self, pos: usize) -> f64 {
-        let bytes = self.raw_exif[pos..pos + 4].try_into().unwrap();
-        if self.motorola_order { f64::from_be_bytes(bytes) }
-        else { f64::from_le_bytes(bytes) }
+
+    /// Egész érték (eltolás, hossz); a negatív 0 lesz
+    fn convert_format_usize(&self, valueptr: usize, format:& FMT) -> Result<usize, String> {
+       Ok(match format {
+            FMT::BYTE   => self.bytes_at(valueptr, 1)?[0] as usize,
+            FMT::SBYTE  => (self.bytes_at(valueptr, 1)?[0] as i8).max(0) as usize,
+            FMT::USHORT => self.read_u16(valueptr)? as usize,
+            FMT::SSHORT => (self.read_u16(valueptr)? as i16).max(0) as usize,
+            FMT::ULONG  => self.read_u32(valueptr)? as usize,
+            FMT::SLONG  => self.read_i32(valueptr)?.max(0) as usize,
+            _ => 0,
+        })
     }
 
-    fn convert_format_usize(&self, valueptr: usize, format:& FMT) -> usize {
-       match format {
-            FMT::BYTE   => (self.raw_exif[valueptr] as u8) as usize,
-            FMT::SBYTE  => (self.raw_exif[valueptr] as i8) as usize,
-            FMT::USHORT => (self.read_u16(valueptr)) as usize,
-            FMT::SSHORT => (self.read_u16(valueptr) as i16) as usize,
-            FMT::ULONG  => (self.read_u32(valueptr)) as usize,
-            FMT::SLONG  => (self.read_i32(valueptr)) as usize,
-            _ => (0) as usize, 
+    /// A beágyazott JPEG thumbnail nyers bájtjai, ha van
+    pub fn thumbnail_bytes(&self) -> Option<&[u8]> {
+        let offset = self.thumbnailoffset;
+        let length = self.thumbnailsize;
+        if length > 0 && offset > 0 {
+            self.raw_exif.get(offset..offset.checked_add(length)?)
+        } else {
+            None
         }
     }
 
-    pub fn patch_thumbnail(&mut self, new_thumb: &[u8]) {
+    /// A bélyegkép cseréje; azonos hossznál helyben, különben a blokk újraírásával (bármilyen méret).
+    /// Hibánál a blokk változatlan marad.
+    pub fn patch_thumbnail(&mut self, new_thumb: &[u8]) -> Result<(), String> {
         let offset = self.thumbnailoffset; // Ezt a open() során mentetted el
         let length = self.thumbnailsize;
-
-        if length > 0 && self.raw_exif.len() >= offset + length && new_thumb.len() == length {
+        if new_thumb.is_empty() {
+            return Ok(());
+        }
+        if length > 0 && new_thumb.len() == length && offset.checked_add(length).is_some_and(|end| end <= self.raw_exif.len()) {
             self.raw_exif[offset..offset + length].copy_from_slice(new_thumb);
+            Ok(())
+        } else {
+            self.edit(|ifds| ifds.thumbnail = Some(new_thumb.to_vec()))
+                .map_err(|e| t!("error-thumbnail", error = e))
         }
     }
 
-    pub fn patch_exifdata(&mut self, xres: f32, yres: f32, w: u32, h: u32) {
-        if let Some(entry) = self.find_tag("XResolution",0,true) {
-            let (nxf,nx) = if ((xres+0.5) as u32) as f32 == xres { (1.0,1u32) } else { (100000.0,100000u32) };
-            let dx = (xres * nxf + 0.5) as u32;
-            let mut bytes = if self.motorola_order { dx.to_be_bytes() } else { dx.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+4].copy_from_slice(&bytes);
-            bytes = if self.motorola_order { nx.to_be_bytes() } else { nx.to_le_bytes() };
-            self.raw_exif[entry.offset+4..entry.offset+8].copy_from_slice(&bytes);
-        }
-
-        if let Some(entry) = self.find_tag("YResolution",0,true) {
-            let (nyf,ny) = if ((yres+0.5) as u32) as f32 == yres { (1.0,1u32) } else { (100000.0,100000u32) };
-            let dy = (yres * nyf + 0.5) as u32;
-            let mut bytes = if self.motorola_order { dy.to_be_bytes() } else { dy.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+4].copy_from_slice(&bytes);
-            bytes = if self.motorola_order { ny.to_be_bytes() } else { ny.to_le_bytes() };
-            self.raw_exif[entry.offset+4..entry.offset+8].copy_from_slice(&bytes);
-        }
-
-        if let Some(entry) = self.find_tag("Orientation",0,true) {
-            let ori = 1u16;
-            let bytes = if self.motorola_order { ori.to_be_bytes() } else { ori.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+2].copy_from_slice(&bytes);
-        }
-
-        if let Some(entry) = self.find_tag("DateTime",0,true) {
-            let current_date = chrono::Local::now().format("%Y:%m:%d %H:%M:%S").to_string();
-            let bytes = current_date.as_bytes();
-            if bytes.len() <= 20 {
-                self.raw_exif[entry.offset..entry.offset + bytes.len()].copy_from_slice(bytes);
-            }
-        }
-
-        if let Some(entry) = self.find_tag("PixelXDimension",0,true) {
-            let bytes = if self.motorola_order { w.to_be_bytes() } else { w.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+4].copy_from_slice(&bytes);
-        }
-        else if let Some(entry) = self.find_tag("ImageWidth",0,true) {
-            let bytes = if self.motorola_order { w.to_be_bytes() } else { w.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+4].copy_from_slice(&bytes);
-        }
+    /// PixelXDimension / PixelYDimension (vagy ImageWidth / ImageLength) a mentett kép méretére
+    pub fn patch_dimensions(&mut self, w: u32, h: u32) -> Result<(), String> {
+        self.edit(|ifds| set_dimensions(ifds, w, h))
+    }
 
-        if let Some(entry) = self.find_tag("PixelYDimension",0,true) {
-            let bytes = if self.motorola_order { h.to_be_bytes() } else { h.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+4].copy_from_slice(&bytes);
-        }
-        else if let Some(entry) = self.find_tag("ImageLength",0,true) {
-            let bytes = if self.motorola_order { h.to_be_bytes() } else { h.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+4].copy_from_slice(&bytes);
-        }
+    /// Az Orientation tag átírása (1: a pixelek egyenesek); ha nincs ilyen tag, csak 1-től eltérőnél veszi fel
+    pub fn patch_orientation(&mut self, orientation: u16) -> Result<(), String> {
+        self.edit(|ifds| set_orientation(ifds, orientation))
+    }
 
-        if let Some(entry) = self.find_tag("Orientation",0,true) {
-            let ori = 1u16;
-            let bytes = if self.motorola_order { ori.to_be_bytes() } else { ori.to_le_bytes() };
-            self.raw_exif[entry.offset..entry.offset+2].copy_from_slice(&bytes);
-        }
+    /// Felbontás, dátum, méretek és állás a mentett képhez; a hiányzó tagokat felveszi
+    pub fn patch_exifdata(&mut self, xres: f32, yres: f32, w: u32, h: u32) -> Result<(), String> {
+        let current_date = chrono::Local::now().format("%Y:%m:%d %H:%M:%S").to_string();
+        self.edit(|ifds| {
+            ifds.set(Ifd::Ifd0, ExifTagId::XResolution as u16, TagValue::rational(xres as f64));
+            ifds.set(Ifd::Ifd0, ExifTagId::YResolution as u16, TagValue::rational(yres as f64));
+            ifds.set(Ifd::Ifd0, ExifTagId::DateTime as u16, TagValue::ascii(&current_date));
+            set_dimensions(ifds, w, h);
+            set_orientation(ifds, 1);
+        })
     }
 
     pub fn open(&mut self, exifsection: &[u8],  length: usize) -> Result<ExifBlock, String> {
+        let length = length.min(exifsection.len());
         let exifheader: [u8; 6] = *b"Exif\0\0";
-        if exifsection[0..6] != exifheader {
-            return Err("No exif header".into());
+        if length < 14 || exifsection[0..6] != exifheader {
+            return Err(t!("exif-no-header"));
         }
+        self.raw_exif = exifsection[..length].to_vec();
+        self.raw_exif_length = length;
+        self.lastexifrefd = 0;
+        self.dirwiththumbnailptrs = 0;
+        self.nesting_level = 0;
+        self.visited_dirs.clear();
+
         let motorola: [u8; 2] = *b"MM";
         let intel: [u8; 2] = *b"II";
         if exifsection[6..8] == motorola {
@@ -820,55 +815,57 @@ impl ExifBlock {
         } else if exifsection[6..8] == intel {
             self.motorola_order = false;
         }else{
-            return Err("Corrupt exif header: Invalid Exif alignment marker".into());
+            return Err(t!("exif-corrupt", reason = t!("exif-alignment")));
         }
 
-        if self.read_buff_u16(&exifsection,8) != 0x2a {
-            return Err("Corrupt exif header: Invalid Exif start (1)".into())
+        if self.read_u16(8)? != 0x2a {
+            return Err(t!("exif-corrupt", reason = t!("exif-start")))
         }
 
-        let firstoffset = self.read_buff_u32(&exifsection,10) as usize;
+        let firstoffset = self.read_u32(10)? as usize;
         if firstoffset < 8 || firstoffset > 32000 {
-            return Err("Corrupt exif header: Suspicious offset of first IFD value".into());
+            return Err(t!("exif-corrupt", reason = t!("exif-first-ifd")));
         }
-
-        self.raw_exif = exifsection.to_vec();
-        self.raw_exif_length = length;
-        self.lastexifrefd = 0;
-        self.dirwiththumbnailptrs = 0;
         
         // First directory starts 16 bytes in.  All offset are relative to 8 bytes in.
         self.nesting_level+=1;
-        let mut json = self.process_exif_dir(firstoffset+6, 6, length-6)?;
+        let mut json = self.process_exif_dir(firstoffset+6, 6, length-6, Ifd::Ifd0)?;
         self.nesting_level-=1;
         
-        if self.thumbnailsize != 0 && self.thumbnailoffset != 0 {
-            if self.thumbnailsize + self.thumbnailoffset <= length {
-                let raw_bytes = &self.raw_exif[self.thumbnailoffset..self.thumbnailoffset + self.thumbnailsize];
-                let value = json!(general_purpose::STANDARD.encode(raw_bytes));
-                json.insert("Thumbnail".to_string(), value);
-            }
+        if let Some(raw_bytes) = self.thumbnail_bytes() {
+            let value = json!(general_purpose::STANDARD.encode(raw_bytes));
+            json.insert("Thumbnail".to_string(), value);
         }
         let json_length = json!(length);
         json.insert("Exiflength".to_string(), json_length.clone());
-        self.entry_data_vector.push( ExifTagEntry{ name:"Exiflength".to_string(), value:json_length, offset:0} );
+        self.entry_data_vector.push( ExifTagEntry{ name:"Exiflength".to_string(), value:json_length, offset:0, ifd:String::new()} );
         
         self.json_data = Some(json);
         Ok(self.clone())
     }
 
 
+    /// Egy IFD-t csak egyszer járunk be, a körbe mutató hivatkozás hiba
+    fn enter_dir(&mut self, dirstart: usize) -> Result<(), String> {
+        if self.visited_dirs.contains(&dirstart) {
+            return Err(t!("exif-corrupt", reason = t!("exif-cycle")));
+        }
+        self.visited_dirs.push(dirstart);
+        Ok(())
+    }
+
     fn dir_entry_addr(start: usize, entry: usize) -> usize {
         start + 2 + 12 * entry
     }
 
 
-    fn process_exif_dir(&mut self, dirstart: usize, offsetbase: usize, exiflength: usize) ->  Result<Map<String, Value>, String> {
+    fn process_exif_dir(&mut self, dirstart: usize, offsetbase: usize, exiflength: usize, ifd: Ifd) ->  Result<Map<String, Value>, String> {
 
-        let numdirentries = self.read_u16(dirstart) as usize;
         if self.nesting_level > 4 {
-            return Err("Corrupt exif header: Maximum directory nesting exceeded".into());
+            return Err(t!("exif-corrupt", reason = t!("exif-nesting")));
         }
+        self.enter_dir(dirstart)?;
+        let numdirentries = self.read_u16(dirstart)? as usize;
 
         let dirend = Self::dir_entry_addr(dirstart, numdirentries);
         if dirend+4 > offsetbase+exiflength {
@@ -878,7 +875,7 @@ impl ExifBlock {
             }else{
                 // note: files that had thumbnails trimmed with jhead 1.3 or earlier
                 // might trigger this.
-                return Err("Corrupt exif header: Illegally sized directory".into());
+                return Err(t!("exif-corrupt", reason = t!("exif-dir-size")));
             }
         }
         if dirend > self.lastexifrefd { self.lastexifrefd = dirend; }
@@ -889,13 +886,14 @@ impl ExifBlock {
             let idx = de_idx as usize;
             let direntry = Self::dir_entry_addr(dirstart, idx);
             
-            let tag = self.get_exif_tag(self.read_u16(direntry));
-            let format = FMT::from(self.read_u16(direntry+2));
+            let tag = self.get_exif_tag(self.read_u16(direntry)?);
+            let format = FMT::from(self.read_u16(direntry+2)?);
             if format == FMT::NUM_FORMATS {
-                return Err(format!("Corrupt exif header: Illegal number format {:?} for tag {:?}", format, tag.name));
+                return Err(t!("exif-corrupt", reason = t!("exif-format", format = format!("{:?}", format), tag = tag.name)));
             }
-            let components = self.read_u32(direntry+4) as usize;
-            let bytecount = components * BYTESPERFORMAT[format.clone() as usize];
+            let components = self.read_u32(direntry+4)? as usize;
+            let bytecount = components.checked_mul(format.bytes())
+                .ok_or_else(|| t!("exif-corrupt", reason = t!("exif-components")))?;
             
             let mut json_tag: Map<String, Value> = Map::new();
             json_tag.insert("type".to_string(),json!(format));
@@ -903,9 +901,9 @@ impl ExifBlock {
             
             let valueptr = if bytecount > 4 {
                 // if its bigger than 4 bytes, the dir entry contains an offset.
-                let offsetval = self.read_u32(direntry+8) as usize;
-                if offsetval+bytecount > exiflength {
-                    return Err(format!("Corrupt exif header: Illegal value pointer for tag {:?}",tag.name));
+                let offsetval = self.read_u32(direntry+8)? as usize;
+                if offsetval.saturating_add(bytecount) > exiflength {
+                    return Err(t!("exif-corrupt", reason = t!("exif-pointer", tag = tag.name)));
                 }
                 offsetbase+offsetval
             }else{
@@ -921,9 +919,9 @@ impl ExifBlock {
 
             match tag.enu {
                 ExifTagId::GPSInfo => {
-                        let subdirstart = offsetbase + self.read_u32(valueptr) as usize;
+                        let subdirstart = offsetbase.saturating_add(self.read_u32(valueptr)? as usize);
                         if subdirstart < offsetbase || subdirstart > offsetbase+exiflength {
-                            return Err("Corrupt exif header: Illegal exif or interop ofset directory link".into());
+                            return Err(t!("exif-corrupt", reason = t!("exif-link")));
                         }else{
                             self.nesting_level+=1;
                             let json = self.process_gps_info(subdirstart, offsetbase, exiflength)?;
@@ -933,42 +931,44 @@ impl ExifBlock {
                         continue;
                     },
                 ExifTagId::EXIF_OFFSET | ExifTagId::INTEROP_OFFSET => {
-                        let subdirstart = offsetbase + self.read_u32(valueptr) as usize;
+                        let subifd = if tag.enu == ExifTagId::EXIF_OFFSET { Ifd::Exif } else { Ifd::Interop };
+                        let subdirstart = offsetbase.saturating_add(self.read_u32(valueptr)? as usize);
                         if subdirstart < offsetbase || subdirstart > offsetbase+exiflength {
-                            return Err("Corrupt exif header: Illegal exif or interop offset directory link".into());
+                            return Err(t!("exif-corrupt", reason = t!("exif-link")));
                         }else{
                             self.nesting_level+=1;
-                            let json = self.process_exif_dir(subdirstart, offsetbase, exiflength)?;
+                            let json = self.process_exif_dir(subdirstart, offsetbase, exiflength, subifd)?;
                             self.nesting_level-=1;
                             result.insert(tag.name.clone(), json!(json));
                         }
                         continue;
                     },
                 ExifTagId::ThumbnailOffset => {
-                        self.thumbnailoffset = self.convert_format_usize(valueptr, &format);
+                        // a TIFF fejléchez képest van megadva, a raw_exif elején ott a "Exif\0\0"
+                        self.thumbnailoffset = offsetbase.saturating_add(self.convert_format_usize(valueptr, &format)?);
                         self.dirwiththumbnailptrs = dirstart;
                     },
                 ExifTagId::ThumbnailLength => {
-                        self.thumbnailsize = self.convert_format_usize(valueptr, &format);
+                        self.thumbnailsize = self.convert_format_usize(valueptr, &format)?;
                     },
                  _ => {},
                 }
 
             let (value, insert_to_flat) = self.get_entry_value(format,valueptr,components,bytecount,
-                    tag.enu == ExifTagId::Make,   tag.enu == ExifTagId::MakerNote && self.make == "Canon");
+                    tag.enu == ExifTagId::Make,   tag.enu == ExifTagId::MakerNote && self.make == "Canon")?;
 
             let mut copy_json_tag = json_tag.clone();
             json_tag.insert("val".into(), value);
             let jsontag_value = serde_json::json!(json_tag);
 
             if insert_to_flat {
-                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value.clone(), offset:valueptr} );
+                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value.clone(), offset:valueptr, ifd:ifd.name().to_string()} );
             }
             else {
                 let data = "long data";
                 copy_json_tag.insert("val".into(), json!(data));
                 let jsontag_value = serde_json::json!(copy_json_tag);
-                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value, offset:valueptr} );
+                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value, offset:valueptr, ifd:ifd.name().to_string()} );
             }
             result.insert(tag.name, jsontag_value);
 
@@ -978,15 +978,17 @@ impl ExifBlock {
         // there's also a potential link to another directory at the end of each
         // directory.  this has got to be the result of a comitee!
         if Self::dir_entry_addr(dirstart, numdirentries) + 4 <= offsetbase+exiflength {
-             let offset = self.read_u32(dirstart+2+12*numdirentries) as usize;
+             let offset = self.read_u32(dirstart+2+12*numdirentries)? as usize;
              if offset != 0 {
-                let subdirstart = offsetbase + offset;
+                let subdirstart = offsetbase.saturating_add(offset);
                 if subdirstart > offsetbase+exiflength {
                 } else {
                    if subdirstart <= offsetbase+exiflength {
                       //inf->exiftext("%*ccontinued ",level*4,' ');
                       self.nesting_level+=1;
-                      let json = self.process_exif_dir(subdirstart, offsetbase, exiflength)?;
+                      // az IFD0 után az IFD1 (a bélyegkép) jön
+                      let next = if ifd == Ifd::Ifd0 { Ifd::Ifd1 } else { ifd };
+                      let json = self.process_exif_dir(subdirstart, offsetbase, exiflength, next)?;
                       self.nesting_level-=1;
                       result.insert("ExtraExifDir".to_string(), json!(json));
                    }
@@ -1002,12 +1004,13 @@ impl ExifBlock {
 
     //fn PrintFormatNumber(&mut self,valueptr: usize, format: FMT, bytecount: i32) {}
     fn process_gps_info(&mut self, dirstart: usize, offsetbase: usize, exiflength: usize) ->  Result<Map<String, Value>, String>  {
-        let numdirentries = self.read_u16(dirstart) as usize;
+        self.enter_dir(dirstart)?;
+        let numdirentries = self.read_u16(dirstart)? as usize;
         let dirend = Self::dir_entry_addr(dirstart, numdirentries);
         if dirend > (offsetbase+exiflength) {
             // Note: Files that had thumbnails trimmed with jhead 1.3 or earlier
             // might trigger this.
-            return Err("Corrupt exif header: Illegally sized directory".into());
+            return Err(t!("exif-corrupt", reason = t!("exif-dir-size")));
         }
 
         let mut result = Map::new();
@@ -1015,16 +1018,17 @@ impl ExifBlock {
         for de in 0..numdirentries {
             let idx = de as usize;
             let direntry = Self::dir_entry_addr(dirstart, idx);
-            let tag = self.get_gps_tag(self.read_u16(direntry));
-            let format = FMT::from(self.read_u16(direntry+2));
+            let tag = self.get_gps_tag(self.read_u16(direntry)?);
+            let format = FMT::from(self.read_u16(direntry+2)?);
             if format == FMT::NUM_FORMATS {
-                return Err(format!("Corrupt exif header: Illegal number format {:?} for tag {:?}", format, tag.name));
+                return Err(t!("exif-corrupt", reason = t!("exif-format", format = format!("{:?}", format), tag = tag.name)));
             }
-            let components = self.read_u32(direntry+4) as usize;
+            let components = self.read_u32(direntry+4)? as usize;
             if components < 1 || components > 32768 {
-                return Err(format!("Corrupt exif header: bad component number"));
+                return Err(t!("exif-corrupt", reason = t!("exif-components")));
             }
-            let bytecount = components * BYTESPERFORMAT[format.clone() as usize];
+            let bytecount = components.checked_mul(format.bytes())
+                .ok_or_else(|| t!("exif-corrupt", reason = t!("exif-components")))?;
 
             let mut json_tag: Map<String, Value> = Map::new();
             json_tag.insert("type".to_string(),json!(format));
@@ -1032,9 +1036,9 @@ impl ExifBlock {
             
             let valueptr = if bytecount > 4 {
                 // if its bigger than 4 bytes, the dir entry contains an offset.
-                let offsetval = self.read_u32(direntry+8) as usize;
-                if offsetval+bytecount > exiflength {
-                    return Err(format!("Corrupt exif header: Illegal value pointer for tag {:?}",tag.name));
+                let offsetval = self.read_u32(direntry+8)? as usize;
+                if offsetval.saturating_add(bytecount) > exiflength {
+                    return Err(t!("exif-corrupt", reason = t!("exif-pointer", tag = tag.name)));
                 }
                 offsetbase+offsetval
             }else{
@@ -1048,20 +1052,20 @@ impl ExifBlock {
                 self.lastexifrefd = valueptr+bytecount;
             }
 
-            let (value, insert_to_flat) = self.get_entry_value(format,valueptr,components,bytecount, false, false);
+            let (value, insert_to_flat) = self.get_entry_value(format,valueptr,components,bytecount, false, false)?;
 
             let mut copy_json_tag = json_tag.clone();
             json_tag.insert("val".into(), value);
             let jsontag_value = serde_json::json!(json_tag);
 
             if insert_to_flat {
-                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value.clone(), offset:valueptr} );
+                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value.clone(), offset:valueptr, ifd:Ifd::Gps.name().to_string()} );
             }
             else {
                 let data = "long data";
                 copy_json_tag.insert("val".into(), json!(data));
                 let jsontag_value = serde_json::json!(copy_json_tag);
-                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value, offset:valueptr} );
+                self.entry_data_vector.push( ExifTagEntry{ name:tag.name.clone(), value:jsontag_value, offset:valueptr, ifd:Ifd::Gps.name().to_string()} );
             }
             result.insert(tag.name, jsontag_value);
 
@@ -1072,114 +1076,132 @@ impl ExifBlock {
 
 
     fn get_entry_value(&mut self, format: FMT, mut valueptr: usize, components: usize,
-            bytecount: usize, is_make: bool, _is_note: bool) -> ( Value, bool) {
+            bytecount: usize, is_make: bool, _is_note: bool) -> Result<(Value, bool), String> {
         match format {
             FMT::UNDEFINED | FMT::STRING | FMT::UTF_8 => {
-                let raw_bytes = &self.raw_exif[valueptr..valueptr + bytecount];
+                let raw_bytes = self.bytes_at(valueptr, bytecount)?;
                 let clean_bytes = raw_bytes.split(|&b| b == 0).next().unwrap_or(&[]);
                 let text = String::from_utf8_lossy(clean_bytes).to_string();
                 if is_make {
                     self.make = text.clone();
                 }
-                return ( json!(text), true);
+                return Ok(( json!(text), true));
             },
             FMT::BYTE   => {
                 //if is_note {
                 //    json!(process_maker_note(valueptr, bytecount, offsetbase, exiflength))
                 //}
-                let raw_bytes = &self.raw_exif[valueptr..valueptr + bytecount];
-                if bytecount<=120 { return (json!(raw_bytes),true); }
-                else { return (json!(general_purpose::STANDARD.encode(raw_bytes)), false); }
+                let raw_bytes = self.bytes_at(valueptr, bytecount)?;
+                if bytecount<=120 { return Ok((json!(raw_bytes),true)); }
+                else { return Ok((json!(general_purpose::STANDARD.encode(raw_bytes)), false)); }
             },
             FMT::SBYTE  =>
                 if bytecount<=120 {
-                    let signed_bytes: Vec<i8> = self.raw_exif[valueptr..valueptr + bytecount]
+                    let signed_bytes: Vec<i8> = self.bytes_at(valueptr, bytecount)?
                         .iter().map(|&b| b as i8).collect();
-                    return (json!(signed_bytes),true);
+                    return Ok((json!(signed_bytes),true));
                 }
                 else {
-                    let raw_bytes = &self.raw_exif[valueptr..valueptr + bytecount];
-                    return (json!(general_purpose::STANDARD.encode(raw_bytes)), false);
+                    let raw_bytes = self.bytes_at(valueptr, bytecount)?;
+                    return Ok((json!(general_purpose::STANDARD.encode(raw_bytes)), false));
                 },
-            FMT::USHORT => if components == 1 { return (json!(self.read_u16(valueptr)),true); },
-            FMT::SSHORT => if components == 1 { return (json!(self.read_u16(valueptr) as i16),true); },
-            FMT::ULONG  => if components == 1 { return (json!(self.read_u32(valueptr)),true); },
-            FMT::SLONG  => if components == 1 { return (json!(self.read_i32(valueptr)),true); },
-            FMT::URATIONAL | FMT::SRATIONAL => if components == 1 {
-                let num = self.read_u32(valueptr);
-                let den = self.read_u32(valueptr + 4);
-                return (json!([num, den]),true);
+            FMT::USHORT => if components == 1 { return Ok((json!(self.read_u16(valueptr)?),true)); },
+            FMT::SSHORT => if components == 1 { return Ok((json!(self.read_u16(valueptr)? as i16),true)); },
+            FMT::ULONG  => if components == 1 { return Ok((json!(self.read_u32(valueptr)?),true)); },
+            FMT::SLONG  => if components == 1 { return Ok((json!(self.read_i32(valueptr)?),true)); },
+            FMT::URATIONAL => if components == 1 {
+                let num = self.read_u32(valueptr)?;
+                let den = self.read_u32(valueptr + 4)?;
+                return Ok((json!([num, den]),true));
+            },
+            FMT::SRATIONAL => if components == 1 {
+                let num = self.read_i32(valueptr)?;
+                let den = self.read_i32(valueptr + 4)?;
+                return Ok((json!([num, den]),true));
             },
-            FMT::SINGLE => if components == 1 { return (json!(self.read_f32(valueptr)),true); },
-            FMT::DOUBLE => if components == 1 { return (json!(self.read_f64(valueptr)),true); },
-            _ => return (json!(null),false), // Ismeretlen formátum esetén
+            FMT::SINGLE => if components == 1 { return Ok((json!(self.read_f32(valueptr)?),true)); },
+            FMT::DOUBLE => if components == 1 { return Ok((json!(self.read_f64(valueptr)?),true)); },
+            _ => return Ok((json!(null),false)), // Ismeretlen formátum esetén
         }
         
         match format {
             FMT::USHORT => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    values.push(self.read_u16(valueptr));
+                    values.push(self.read_u16(valueptr)?);
                     valueptr += 2;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
             },
             FMT::SSHORT => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    values.push(self.read_u16(valueptr) as i16);
+                    values.push(self.read_u16(valueptr)? as i16);
                     valueptr+= 2;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
             },
             FMT::ULONG  => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    values.push(self.read_u32(valueptr));
+                    values.push(self.read_u32(valueptr)?);
                     valueptr += 4;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
             },
             FMT::SLONG  => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    values.push(self.read_i32(valueptr));
+                    values.push(self.read_i32(valueptr)?);
                     valueptr += 4;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
             },
-            FMT::URATIONAL | FMT::SRATIONAL => {
+            FMT::URATIONAL => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    let num = self.read_u32(valueptr);
-                    let den = self.read_u32(valueptr + 4);
+                    let num = self.read_u32(valueptr)?;
+                    let den = self.read_u32(valueptr + 4)?;
                     values.push([num, den]);
                     valueptr += 8;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
+            },
+            FMT::SRATIONAL => {
+                let mut values = Vec::new();        
+                for _i in 0..components {
+                    let num = self.read_i32(valueptr)?;
+                    let den = self.read_i32(valueptr + 4)?;
+                    values.push([num, den]);
+                    valueptr += 8;
+                } 
+                return Ok((json!(values), true));
             },
             FMT::SINGLE => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    values.push(self.read_f32(valueptr));
+                    values.push(self.read_f32(valueptr)?);
                     valueptr += 4;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
             },
             FMT::DOUBLE => {
                 let mut values = Vec::new();        
                 for _i in 0..components {
-                    values.push(self.read_f64(valueptr));
+                    values.push(self.read_f64(valueptr)?);
                     valueptr += 8;
                 } 
-                return (json!(values), true);
+                return Ok((json!(values), true));
             },
-            _ => return (json!(null),false),
+            _ => return Ok((json!(null),false)),
         }
     }
 
     pub fn generate_fitted_thumbnail(&self, img: &image::RgbaImage) -> Vec<u8> {
         let  max_size = self.thumbnailsize;
+        if max_size == 0 {
+            return Vec::new(); // nincs bélyegkép, nem is lesz
+        }
         let thumb = image::DynamicImage::ImageRgba8(img.clone())
             .thumbnail(160, 120)
             .to_rgb8();
@@ -1203,7 +1225,7 @@ impl ExifBlock {
             quality -= 10; // Lépésenként rontjuk a minőséget
         }
         if result.len() > max_size {
-            return Vec::new();
+            return result; // nem fér a régi helyére, a patch_thumbnail újraírja a blokkot
         }
 
         // 3. Kitöltés (Padding)
@@ -1217,6 +1239,33 @@ impl ExifBlock {
 
 }
 
+/// PixelX/YDimension az Exif IFD-ben (és ImageWidth/ImageLength, ha az IFD0-ban is van); ULONG marad ULONG
+fn set_dimensions(ifds: &mut ExifIfds, w: u32, h: u32) {
+    for (pixel_tag, main_tag, v) in [(ExifTagId::PixelXDimension, ExifTagId::ImageWidth, w), (ExifTagId::PixelYDimension, ExifTagId::ImageLength, h)] {
+        let (pixel_tag, main_tag) = (pixel_tag as u16, main_tag as u16);
+        let typed = |old: Option<&TagValue>| match old {
+            Some(TagValue::Long(_)) => TagValue::Long(vec![v]),
+            _ => TagValue::integer(v),
+        };
+        let has_main = ifds.get(Ifd::Ifd0, main_tag).is_some();
+        if has_main {
+            let value = typed(ifds.get(Ifd::Ifd0, main_tag));
+            ifds.set(Ifd::Ifd0, main_tag, value);
+        }
+        if !has_main || ifds.get(Ifd::Exif, pixel_tag).is_some() {
+            let value = typed(ifds.get(Ifd::Exif, pixel_tag));
+            ifds.set(Ifd::Exif, pixel_tag, value);
+        }
+    }
+}
+
+fn set_orientation(ifds: &mut ExifIfds, orientation: u16) {
+    let tag = ExifTagId::Orientation as u16;
+    if orientation != 1 || ifds.get(Ifd::Ifd0, tag).is_some() {
+        ifds.set(Ifd::Ifd0, tag, TagValue::Short(vec![orientation]));
+    }
+}
+
 
 
 /*
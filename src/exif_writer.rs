@@ -0,0 +1,687 @@
+/*
+iview/src/exif_writer.rs
+
+EXIF writer: the TIFF structure of an ExifBlock read into typed IFD entries
+(IFD0, Exif, GPS, Interop, IFD1 with the thumbnail), edited, and written
+back as a fresh, valid block in either byte order
+
+*/
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+
+use crate::exif_my::ExifBlock;
+use crate::t;
+
+/// Az APP1 szegmensbe ennyi fér ("Exif\0\0" fejléccel együtt)
+pub const MAX_EXIF_SIZE: usize = 65533;
+
+pub const TAG_EXIF_POINTER: u16 = 0x8769;
+pub const TAG_GPS_POINTER: u16 = 0x8825;
+pub const TAG_INTEROP_POINTER: u16 = 0xA005;
+pub const TAG_THUMBNAIL_OFFSET: u16 = 0x0201;
+pub const TAG_THUMBNAIL_LENGTH: u16 = 0x0202;
+pub const TAG_MAKER_NOTE: u16 = 0x927C;
+const TAG_COMPRESSION: u16 = 0x0103;
+const TAG_STRIP_OFFSETS: u16 = 0x0111;
+const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+
+/// Fájlon belüli helyre mutató tagok (csíkok, csempék, SubIFD-k, előnézet); az új szerkezetben
+/// a régi címük már semmire sem mutatna, és az író nem követi őket, ezért kimaradnak
+const OFFSET_TAGS: [u16; 9] = [0x0111, 0x0117, 0x0120, 0x0121, 0x0144, 0x0145, 0x014A, 0x0201, 0x0202];
+
+/// A csíkokban tárolt (tömörítetlen) bélyegkép leírása: méret, színtér, csíkolás
+const STRIP_IMAGE_TAGS: [u16; 9] = [0x0100, 0x0101, 0x0102, 0x0103, 0x0106, 0x0115, 0x0116, 0x011C, 0x0212];
+
+fn drop_offset_tag(ifd: Ifd, tag: u16) -> bool {
+    if OFFSET_TAGS.contains(&tag) {
+        println!("{}", t!("warning-exif-offset-tag", tag = format!("0x{:04X}", tag), ifd = ifd.name()));
+        return true;
+    }
+    false
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ifd {
+    Ifd0,    // a fő kép
+    Exif,    // Exif IFD (fényképezési adatok)
+    Gps,
+    Interop,
+    Ifd1,    // a bélyegkép
+}
+
+impl Ifd {
+    pub const ALL: [Ifd; 5] = [Ifd::Ifd0, Ifd::Exif, Ifd::Gps, Ifd::Interop, Ifd::Ifd1];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ifd::Ifd0 => "IFD0",
+            Ifd::Exif => "Exif",
+            Ifd::Gps => "GPS",
+            Ifd::Interop => "Interop",
+            Ifd::Ifd1 => "IFD1",
+        }
+    }
+}
+
+/// Egy tag értéke a TIFF típusa szerint; a szövegek nyers bájtok (a lezáró nullával), így változatlanul visszaírhatók
+#[derive(Clone, Debug, PartialEq)]
+pub enum TagValue {
+    Byte(Vec<u8>),
+    Ascii(Vec<u8>),
+    Short(Vec<u16>),
+    Long(Vec<u32>),
+    Rational(Vec<(u32, u32)>),
+    SByte(Vec<i8>),
+    Undefined(Vec<u8>),
+    SShort(Vec<i16>),
+    SLong(Vec<i32>),
+    SRational(Vec<(i32, i32)>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+    Utf8(Vec<u8>),
+}
+
+/// A szerkesztőben választható típusok (TIFF kód, név)
+pub const FORMATS: [(u16, &str); 13] = [
+    (1, "BYTE"), (2, "STRING"), (3, "USHORT"), (4, "ULONG"), (5, "URATIONAL"), (6, "SBYTE"), (7, "UNDEFINED"),
+    (8, "SSHORT"), (9, "SLONG"), (10, "SRATIONAL"), (11, "SINGLE"), (12, "DOUBLE"), (129, "UTF_8"),
+];
+
+fn element_size(format: u16) -> Option<usize> {
+    match format {
+        1 | 2 | 6 | 7 | 129 => Some(1),
+        3 | 8 => Some(2),
+        4 | 9 | 11 => Some(4),
+        5 | 10 | 12 => Some(8),
+        _ => None,
+    }
+}
+
+fn get_u16(bytes: &[u8], motorola: bool) -> u16 {
+    let b = [bytes[0], bytes[1]];
+    if motorola { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) }
+}
+
+fn get_u32(bytes: &[u8], motorola: bool) -> u32 {
+    let b = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if motorola { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) }
+}
+
+fn get_u64(bytes: &[u8], motorola: bool) -> u64 {
+    let mut b = [0u8; 8];
+    b.copy_from_slice(&bytes[..8]);
+    if motorola { u64::from_be_bytes(b) } else { u64::from_le_bytes(b) }
+}
+
+fn put_u16(out: &mut Vec<u8>, v: u16, motorola: bool) {
+    out.extend_from_slice(&if motorola { v.to_be_bytes() } else { v.to_le_bytes() });
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32, motorola: bool) {
+    out.extend_from_slice(&if motorola { v.to_be_bytes() } else { v.to_le_bytes() });
+}
+
+fn patch_u32(out: &mut [u8], pos: usize, v: u32, motorola: bool) {
+    out[pos..pos + 4].copy_from_slice(&if motorola { v.to_be_bytes() } else { v.to_le_bytes() });
+}
+
+/// Tizedes törtből racionális szám (a pontos egészeket egész marad)
+fn to_rational(v: f64) -> (i64, i64) {
+    if v.fract() == 0.0 {
+        return (v as i64, 1);
+    }
+    let (mut n, mut d) = ((v * 10000.0).round() as i64, 10000i64);
+    let (mut a, mut b) = (n.abs(), d);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a > 1 {
+        n /= a;
+        d /= a;
+    }
+    (n, d)
+}
+
+impl TagValue {
+    /// `bytes` pontosan count * elemméret hosszú
+    fn read(format: u16, bytes: &[u8], motorola: bool) -> Option<TagValue> {
+        let size = element_size(format)?;
+        let items = bytes.chunks_exact(size);
+        Some(match format {
+            1 => TagValue::Byte(bytes.to_vec()),
+            2 => TagValue::Ascii(bytes.to_vec()),
+            3 => TagValue::Short(items.map(|b| get_u16(b, motorola)).collect()),
+            4 => TagValue::Long(items.map(|b| get_u32(b, motorola)).collect()),
+            5 => TagValue::Rational(items.map(|b| (get_u32(b, motorola), get_u32(&b[4..], motorola))).collect()),
+            6 => TagValue::SByte(bytes.iter().map(|&b| b as i8).collect()),
+            7 => TagValue::Undefined(bytes.to_vec()),
+            8 => TagValue::SShort(items.map(|b| get_u16(b, motorola) as i16).collect()),
+            9 => TagValue::SLong(items.map(|b| get_u32(b, motorola) as i32).collect()),
+            10 => TagValue::SRational(items.map(|b| (get_u32(b, motorola) as i32, get_u32(&b[4..], motorola) as i32)).collect()),
+            11 => TagValue::Float(items.map(|b| f32::from_bits(get_u32(b, motorola))).collect()),
+            12 => TagValue::Double(items.map(|b| f64::from_bits(get_u64(b, motorola))).collect()),
+            129 => TagValue::Utf8(bytes.to_vec()),
+            _ => return None,
+        })
+    }
+
+    fn write(&self, out: &mut Vec<u8>, motorola: bool) {
+        match self {
+            TagValue::Byte(v) | TagValue::Ascii(v) | TagValue::Undefined(v) | TagValue::Utf8(v) => out.extend_from_slice(v),
+            TagValue::SByte(v) => out.extend(v.iter().map(|&b| b as u8)),
+            TagValue::Short(v) => v.iter().for_each(|&x| put_u16(out, x, motorola)),
+            TagValue::SShort(v) => v.iter().for_each(|&x| put_u16(out, x as u16, motorola)),
+            TagValue::Long(v) => v.iter().for_each(|&x| put_u32(out, x, motorola)),
+            TagValue::SLong(v) => v.iter().for_each(|&x| put_u32(out, x as u32, motorola)),
+            TagValue::Rational(v) => v.iter().for_each(|&(n, d)| {
+                put_u32(out, n, motorola);
+                put_u32(out, d, motorola);
+            }),
+            TagValue::SRational(v) => v.iter().for_each(|&(n, d)| {
+                put_u32(out, n as u32, motorola);
+                put_u32(out, d as u32, motorola);
+            }),
+            TagValue::Float(v) => v.iter().for_each(|&x| put_u32(out, x.to_bits(), motorola)),
+            TagValue::Double(v) => v.iter().for_each(|&x| {
+                let bytes = if motorola { x.to_bits().to_be_bytes() } else { x.to_bits().to_le_bytes() };
+                out.extend_from_slice(&bytes);
+            }),
+        }
+    }
+
+    pub fn format(&self) -> u16 {
+        match self {
+            TagValue::Byte(_) => 1,
+            TagValue::Ascii(_) => 2,
+            TagValue::Short(_) => 3,
+            TagValue::Long(_) => 4,
+            TagValue::Rational(_) => 5,
+            TagValue::SByte(_) => 6,
+            TagValue::Undefined(_) => 7,
+            TagValue::SShort(_) => 8,
+            TagValue::SLong(_) => 9,
+            TagValue::SRational(_) => 10,
+            TagValue::Float(_) => 11,
+            TagValue::Double(_) => 12,
+            TagValue::Utf8(_) => 129,
+        }
+    }
+
+    pub fn format_name(&self) -> &'static str {
+        let format = self.format();
+        FORMATS.iter().find(|(f, _)| *f == format).map(|(_, name)| *name).unwrap_or("?")
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            TagValue::Byte(v) | TagValue::Ascii(v) | TagValue::Undefined(v) | TagValue::Utf8(v) => v.len(),
+            TagValue::SByte(v) => v.len(),
+            TagValue::Short(v) => v.len(),
+            TagValue::SShort(v) => v.len(),
+            TagValue::Long(v) => v.len(),
+            TagValue::SLong(v) => v.len(),
+            TagValue::Rational(v) => v.len(),
+            TagValue::SRational(v) => v.len(),
+            TagValue::Float(v) => v.len(),
+            TagValue::Double(v) => v.len(),
+        }
+    }
+
+    fn byte_size(&self) -> usize {
+        self.count() * element_size(self.format()).unwrap_or(1)
+    }
+
+    /// Az első elem egészként (mutatók, méretek)
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            TagValue::Byte(v) => v.first().map(|&x| x as u32),
+            TagValue::Short(v) => v.first().map(|&x| x as u32),
+            TagValue::Long(v) => v.first().copied(),
+            _ => None,
+        }
+    }
+
+    /// Szerkeszthető szöveg: számok szóközzel, törtek n/d alakban, a szöveg a lezáró nulla nélkül,
+    /// az UNDEFINED szövegként, ha olvasható, különben 0x... hexa
+    pub fn to_text(&self) -> String {
+        fn join<T: ToString>(v: &[T]) -> String {
+            v.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ")
+        }
+        match self {
+            TagValue::Ascii(v) | TagValue::Utf8(v) => {
+                let text = v.split(|&b| b == 0).next().unwrap_or(&[]);
+                String::from_utf8_lossy(text).to_string()
+            }
+            TagValue::Undefined(v) => {
+                if !v.is_empty() && v.iter().all(|&b| (0x20..0x7F).contains(&b)) {
+                    String::from_utf8_lossy(v).to_string()
+                } else {
+                    format!("0x{}", v.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+                }
+            }
+            TagValue::Byte(v) => join(v),
+            TagValue::SByte(v) => join(v),
+            TagValue::Short(v) => join(v),
+            TagValue::SShort(v) => join(v),
+            TagValue::Long(v) => join(v),
+            TagValue::SLong(v) => join(v),
+            TagValue::Float(v) => join(v),
+            TagValue::Double(v) => join(v),
+            TagValue::Rational(v) => v.iter().map(|(n, d)| format!("{}/{}", n, d)).collect::<Vec<_>>().join(" "),
+            TagValue::SRational(v) => v.iter().map(|(n, d)| format!("{}/{}", n, d)).collect::<Vec<_>>().join(" "),
+        }
+    }
+
+    /// A `to_text` fordítottja a megadott típusra; a törtek tizedes alakban is megadhatók
+    pub fn parse(format: u16, text: &str) -> Result<TagValue, String> {
+        let bad = || t!("exif-bad-value", value = text);
+        let items: Vec<&str> = text.split(|c: char| c.is_whitespace() || c == ',').filter(|s| !s.is_empty()).collect();
+        fn numbers<T: std::str::FromStr>(items: &[&str]) -> Option<Vec<T>> {
+            items.iter().map(|s| s.parse().ok()).collect()
+        }
+        fn fraction(s: &str) -> Option<(i64, i64)> {
+            match s.split_once('/') {
+                Some((n, d)) => Some((n.trim().parse().ok()?, d.trim().parse().ok()?)),
+                None => Some(to_rational(s.parse().ok()?)),
+            }
+        }
+        let fractions = || -> Option<Vec<(i64, i64)>> { items.iter().map(|s| fraction(s)).collect() };
+        let value = match format {
+            1 => TagValue::Byte(numbers(&items).ok_or_else(bad)?),
+            2 | 129 => {
+                let mut bytes = text.as_bytes().to_vec();
+                bytes.push(0);
+                if format == 2 { TagValue::Ascii(bytes) } else { TagValue::Utf8(bytes) }
+            }
+            3 => TagValue::Short(numbers(&items).ok_or_else(bad)?),
+            4 => TagValue::Long(numbers(&items).ok_or_else(bad)?),
+            5 => TagValue::Rational(fractions().ok_or_else(bad)?.into_iter()
+                .map(|(n, d)| Some((u32::try_from(n).ok()?, u32::try_from(d).ok()?)))
+                .collect::<Option<Vec<_>>>().ok_or_else(bad)?),
+            6 => TagValue::SByte(numbers(&items).ok_or_else(bad)?),
+            7 => match text.strip_prefix("0x") {
+                Some(hex) => {
+                    if hex.len() % 2 != 0 {
+                        return Err(bad());
+                    }
+                    TagValue::Undefined((0..hex.len()).step_by(2)
+                        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                        .collect::<Option<Vec<_>>>().ok_or_else(bad)?)
+                }
+                None => TagValue::Undefined(text.as_bytes().to_vec()),
+            },
+            8 => TagValue::SShort(numbers(&items).ok_or_else(bad)?),
+            9 => TagValue::SLong(numbers(&items).ok_or_else(bad)?),
+            10 => TagValue::SRational(fractions().ok_or_else(bad)?.into_iter()
+                .map(|(n, d)| Some((i32::try_from(n).ok()?, i32::try_from(d).ok()?)))
+                .collect::<Option<Vec<_>>>().ok_or_else(bad)?),
+            11 => TagValue::Float(numbers(&items).ok_or_else(bad)?),
+            12 => TagValue::Double(numbers(&items).ok_or_else(bad)?),
+            _ => return Err(bad()),
+        };
+        if value.count() == 0 {
+            return Err(bad());
+        }
+        Ok(value)
+    }
+
+    /// Egész érték a szokásos típussal: USHORT, ha belefér, különben ULONG
+    pub fn integer(v: u32) -> TagValue {
+        match u16::try_from(v) {
+            Ok(v) => TagValue::Short(vec![v]),
+            Err(_) => TagValue::Long(vec![v]),
+        }
+    }
+
+    pub fn rational(v: f64) -> TagValue {
+        let (n, d) = to_rational(v.max(0.0));
+        TagValue::Rational(vec![(n.clamp(0, u32::MAX as i64) as u32, d as u32)])
+    }
+
+    pub fn ascii(text: &str) -> TagValue {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        TagValue::Ascii(bytes)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IfdEntry {
+    pub tag: u16,
+    pub value: TagValue,
+}
+
+/// A teljes EXIF szerkezet; a mutató tagokat (Exif, GPS, Interop, bélyegkép) az író állítja elő
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ExifIfds {
+    pub motorola_order: bool,
+    dirs: [Vec<IfdEntry>; 5],
+    pub thumbnail: Option<Vec<u8>>,
+    /// A MakerNote eredeti helye: a gyártói adatok belső mutatói miatt lehetőleg ide kerül vissza
+    pub makernote_offset: Option<usize>,
+}
+
+/// Egy IFD bejegyzései: tag, érték, az érték helye
+type RawEntries = Vec<(u16, TagValue, usize)>;
+
+struct Reader<'a> {
+    data: &'a [u8],
+    motorola: bool,
+}
+
+impl Reader<'_> {
+    fn bytes(&self, pos: usize, len: usize) -> Option<&[u8]> {
+        self.data.get(pos..pos.checked_add(len)?)
+    }
+
+    fn u16(&self, pos: usize) -> Option<u16> {
+        self.bytes(pos, 2).map(|b| get_u16(b, self.motorola))
+    }
+
+    fn u32(&self, pos: usize) -> Option<u32> {
+        self.bytes(pos, 4).map(|b| get_u32(b, self.motorola))
+    }
+
+    /// Egy IFD bejegyzései és a következő IFD helye;
+    /// a hibás bejegyzések kimaradnak, a már bejárt helyre nem lép vissza
+    fn dir(&self, offset: usize, visited: &mut Vec<usize>) -> Option<(RawEntries, usize)> {
+        if visited.contains(&offset) {
+            return None;
+        }
+        visited.push(offset);
+        let count = self.u16(offset)? as usize;
+        self.bytes(offset + 2, count * 12)?;
+        let mut entries = Vec::new();
+        for i in 0..count {
+            let entry = offset + 2 + i * 12;
+            let (Some(tag), Some(format), Some(components)) = (self.u16(entry), self.u16(entry + 2), self.u32(entry + 4)) else {
+                continue;
+            };
+            let Some(size) = element_size(format).and_then(|s| s.checked_mul(components as usize)) else {
+                continue;
+            };
+            let pos = if size > 4 {
+                match self.u32(entry + 8) {
+                    Some(p) => p as usize,
+                    None => continue,
+                }
+            } else {
+                entry + 8
+            };
+            if let Some(value) = self.bytes(pos, size).and_then(|b| TagValue::read(format, b, self.motorola)) {
+                entries.push((tag, value, pos));
+            }
+        }
+        let next = self.u32(offset + 2 + count * 12).unwrap_or(0) as usize;
+        Some((entries, next))
+    }
+}
+
+impl ExifIfds {
+    /// TIFF adatokból ("Exif\0\0" nélkül)
+    pub fn parse(tiff: &[u8]) -> Result<ExifIfds, String> {
+        let motorola = match tiff.get(0..2) {
+            Some(b"MM") => true,
+            Some(b"II") => false,
+            _ => return Err(t!("exif-corrupt", reason = t!("exif-alignment"))),
+        };
+        let reader = Reader { data: tiff, motorola };
+        if reader.u16(2) != Some(0x2a) {
+            return Err(t!("exif-corrupt", reason = t!("exif-start")));
+        }
+        let first = reader.u32(4).ok_or_else(|| t!("exif-corrupt", reason = t!("exif-first-ifd")))? as usize;
+        let mut visited = Vec::new();
+        let (entries, next) = reader.dir(first, &mut visited)
+            .ok_or_else(|| t!("exif-corrupt", reason = t!("exif-first-ifd")))?;
+        let mut ifds = ExifIfds::new(motorola);
+        let mut links: Vec<(Ifd, usize)> = Vec::new();
+        for (tag, value, _) in entries {
+            match (tag, value.as_u32()) {
+                (TAG_EXIF_POINTER, Some(at)) => links.push((Ifd::Exif, at as usize)),
+                (TAG_GPS_POINTER, Some(at)) => links.push((Ifd::Gps, at as usize)),
+                (TAG_INTEROP_POINTER, Some(at)) => links.push((Ifd::Interop, at as usize)),
+                _ if drop_offset_tag(Ifd::Ifd0, tag) => {}
+                _ => ifds.dirs[Ifd::Ifd0 as usize].push(IfdEntry { tag, value }),
+            }
+        }
+        while let Some((ifd, at)) = links.pop() {
+            let Some((entries, _)) = reader.dir(at, &mut visited) else {
+                continue;
+            };
+            for (tag, value, pos) in entries {
+                match (tag, value.as_u32()) {
+                    (TAG_INTEROP_POINTER, Some(at)) if ifd == Ifd::Exif => links.push((Ifd::Interop, at as usize)),
+                    _ if drop_offset_tag(ifd, tag) => {}
+                    _ => {
+                        if ifd == Ifd::Exif && tag == TAG_MAKER_NOTE {
+                            ifds.makernote_offset = Some(pos);
+                        }
+                        ifds.dirs[ifd as usize].push(IfdEntry { tag, value });
+                    }
+                }
+            }
+        }
+        let ifd1 = if next != 0 { reader.dir(next, &mut visited) } else { None };
+        if let Some((entries, _)) = ifd1 {
+            let mut thumbnail = (None, None);
+            let mut strips = false;
+            for (tag, value, _) in entries {
+                match tag {
+                    TAG_THUMBNAIL_OFFSET => thumbnail.0 = value.as_u32(),
+                    TAG_THUMBNAIL_LENGTH => thumbnail.1 = value.as_u32(),
+                    // a csíkokban tárolt (tömörítetlen) bélyegkép nem költöztethető, kimarad
+                    TAG_STRIP_OFFSETS | TAG_STRIP_BYTE_COUNTS => strips = true,
+                    _ if drop_offset_tag(Ifd::Ifd1, tag) => {}
+                    _ => ifds.dirs[Ifd::Ifd1 as usize].push(IfdEntry { tag, value }),
+                }
+            }
+            if let (Some(offset), Some(length)) = thumbnail {
+                ifds.thumbnail = reader.bytes(offset as usize, length as usize).map(|b| b.to_vec());
+            }
+            // a kimaradt csíkos kép leírása se maradjon (pl. Compression = 1 egy JPEG bélyegkép mellett)
+            if strips {
+                ifds.dirs[Ifd::Ifd1 as usize].retain(|e| !STRIP_IMAGE_TAGS.contains(&e.tag));
+            }
+        }
+        Ok(ifds)
+    }
+
+    /// Üres blokk a megadott bájtsorrenddel
+    pub fn new(motorola_order: bool) -> ExifIfds {
+        ExifIfds { motorola_order, ..Default::default() }
+    }
+
+    pub fn dir(&self, ifd: Ifd) -> &[IfdEntry] {
+        &self.dirs[ifd as usize]
+    }
+
+    pub fn get(&self, ifd: Ifd, tag: u16) -> Option<&TagValue> {
+        self.dirs[ifd as usize].iter().find(|e| e.tag == tag).map(|e| &e.value)
+    }
+
+    /// Felülírja vagy felveszi a tagot
+    pub fn set(&mut self, ifd: Ifd, tag: u16, value: TagValue) {
+        let dir = &mut self.dirs[ifd as usize];
+        match dir.iter_mut().find(|e| e.tag == tag) {
+            Some(entry) => entry.value = value,
+            None => dir.push(IfdEntry { tag, value }),
+        }
+    }
+
+    /// Csak meglévő tagot ír felül; igaz, ha volt ilyen
+    pub fn replace(&mut self, ifd: Ifd, tag: u16, value: TagValue) -> bool {
+        match self.dirs[ifd as usize].iter_mut().find(|e| e.tag == tag) {
+            Some(entry) => {
+                entry.value = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove(&mut self, ifd: Ifd, tag: u16) -> bool {
+        let dir = &mut self.dirs[ifd as usize];
+        let len = dir.len();
+        dir.retain(|e| e.tag != tag);
+        dir.len() != len
+    }
+
+    /// Új TIFF szerkezet: IFD0, Exif, Interop, GPS, MakerNote, IFD1, bélyegkép sorrendben
+    pub fn to_tiff(&self) -> Vec<u8> {
+        let m = self.motorola_order;
+        let mut out = Vec::new();
+        out.extend_from_slice(if m { b"MM" } else { b"II" });
+        put_u16(&mut out, 0x2a, m);
+        put_u32(&mut out, 8, m);
+
+        let pointer = |tag| IfdEntry { tag, value: TagValue::Long(vec![0]) };
+        let has_interop = !self.dir(Ifd::Interop).is_empty();
+        let has_exif = !self.dir(Ifd::Exif).is_empty() || has_interop;
+        let has_gps = !self.dir(Ifd::Gps).is_empty();
+        let has_ifd1 = !self.dir(Ifd::Ifd1).is_empty() || self.thumbnail.is_some();
+
+        let mut ifd0 = self.dir(Ifd::Ifd0).to_vec();
+        if has_exif {
+            ifd0.push(pointer(TAG_EXIF_POINTER));
+        }
+        if has_gps {
+            ifd0.push(pointer(TAG_GPS_POINTER));
+        }
+        let (_, fields0, next0) = write_dir(&mut out, &ifd0, m, None);
+        let mut makernote = None;
+        if has_exif {
+            let mut exif = self.dir(Ifd::Exif).to_vec();
+            if has_interop {
+                exif.push(pointer(TAG_INTEROP_POINTER));
+            }
+            let (start, fields, _) = write_dir(&mut out, &exif, m, Some(TAG_MAKER_NOTE));
+            patch_u32(&mut out, fields0[&TAG_EXIF_POINTER], start as u32, m);
+            makernote = fields.get(&TAG_MAKER_NOTE).copied();
+            if has_interop {
+                let (interop, _, _) = write_dir(&mut out, self.dir(Ifd::Interop), m, None);
+                patch_u32(&mut out, fields[&TAG_INTEROP_POINTER], interop as u32, m);
+            }
+        }
+        if has_gps {
+            let (gps, _, _) = write_dir(&mut out, self.dir(Ifd::Gps), m, None);
+            patch_u32(&mut out, fields0[&TAG_GPS_POINTER], gps as u32, m);
+        }
+        if let (Some(field), Some(value)) = (makernote, self.get(Ifd::Exif, TAG_MAKER_NOTE)) {
+            // az eredeti helyére, ha odáig még nem értünk
+            if let Some(offset) = self.makernote_offset.filter(|&o| o >= out.len()) {
+                out.resize(offset, 0);
+            }
+            let offset = out.len() as u32;
+            patch_u32(&mut out, field, offset, m);
+            value.write(&mut out, m);
+        }
+        if has_ifd1 {
+            let mut ifd1 = self.dir(Ifd::Ifd1).to_vec();
+            if let Some(thumbnail) = &self.thumbnail {
+                if !ifd1.iter().any(|e| e.tag == TAG_COMPRESSION) {
+                    ifd1.push(IfdEntry { tag: TAG_COMPRESSION, value: TagValue::Short(vec![6]) }); // JPEG
+                }
+                ifd1.push(pointer(TAG_THUMBNAIL_OFFSET));
+                ifd1.push(IfdEntry { tag: TAG_THUMBNAIL_LENGTH, value: TagValue::Long(vec![thumbnail.len() as u32]) });
+            }
+            let (start1, fields1, _) = write_dir(&mut out, &ifd1, m, None);
+            patch_u32(&mut out, next0, start1 as u32, m);
+            if let Some(thumbnail) = &self.thumbnail {
+                let offset = out.len() as u32;
+                patch_u32(&mut out, fields1[&TAG_THUMBNAIL_OFFSET], offset, m);
+                out.extend_from_slice(thumbnail);
+            }
+        }
+        out
+    }
+
+    /// Az APP1 tartalma: "Exif\0\0" és a TIFF szerkezet
+    pub fn to_exif(&self) -> Vec<u8> {
+        let mut out = b"Exif\0\0".to_vec();
+        out.extend_from_slice(&self.to_tiff());
+        out
+    }
+}
+
+/// Egy IFD kiírása páros címre, utána a 4 bájtnál hosszabb értékei.
+/// Visszaadja az IFD kezdetét, a bejegyzések érték mezőjének helyét tag szerint
+/// (a 0 is valódi tag, pl. GPSVersionID) és a "következő IFD" mező helyét.
+/// A `defer` tag értéke nem kerül ki, a hívó teszi le.
+fn write_dir(out: &mut Vec<u8>, entries: &[IfdEntry], m: bool, defer: Option<u16>) -> (usize, HashMap<u16, usize>, usize) {
+    if out.len() % 2 == 1 {
+        out.push(0);
+    }
+    let mut entries: Vec<&IfdEntry> = entries.iter().collect();
+    entries.sort_by_key(|e| e.tag); // a TIFF növekvő sorrendet kér
+    entries.dedup_by_key(|e| e.tag);
+    let mut fields = HashMap::new();
+    let start = out.len();
+    put_u16(out, entries.len() as u16, m);
+    let mut data = Vec::new();
+    let data_start = out.len() + entries.len() * 12 + 4;
+    for entry in &entries {
+        put_u16(out, entry.tag, m);
+        put_u16(out, entry.value.format(), m);
+        put_u32(out, entry.value.count() as u32, m);
+        fields.insert(entry.tag, out.len());
+        let size = entry.value.byte_size();
+        if size <= 4 {
+            let start = out.len();
+            entry.value.write(out, m);
+            out.resize(start + 4, 0);
+        } else if Some(entry.tag) == defer {
+            put_u32(out, 0, m);
+        } else {
+            put_u32(out, (data_start + data.len()) as u32, m);
+            entry.value.write(&mut data, m);
+            if data.len() % 2 == 1 {
+                data.push(0);
+            }
+        }
+    }
+    let next = out.len();
+    put_u32(out, 0, m);
+    out.extend_from_slice(&data);
+    (start, fields, next)
+}
+
+/// Új bélyegkép a képből (160x120-ba férő JPEG); a hossza nincs a régihez kötve
+pub fn thumbnail_jpeg(img: &image::DynamicImage) -> Result<Vec<u8>, String> {
+    let thumb = img.thumbnail(160, 120).to_rgb8();
+    let mut result = Vec::new();
+    JpegEncoder::new_with_quality(&mut Cursor::new(&mut result), 85)
+        .encode_image(&thumb)
+        .map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+impl ExifBlock {
+
+    /// A blokk típusos IFD-kként
+    pub fn ifds(&self) -> Result<ExifIfds, String> {
+        match self.raw_exif.get(6..) {
+            Some(tiff) if self.raw_exif.starts_with(b"Exif\0\0") => ExifIfds::parse(tiff),
+            _ => Err(t!("exif-no-header")),
+        }
+    }
+
+    /// Új blokk írása az IFD-kből, és újraolvasása, hogy a mezők (thumbnail, json) is frissüljenek
+    pub fn rebuild(&mut self, ifds: &ExifIfds) -> Result<(), String> {
+        let raw = ifds.to_exif();
+        if raw.len() > MAX_EXIF_SIZE {
+            return Err(t!("exif-too-large", size = raw.len()));
+        }
+        *self = ExifBlock::default().open(&raw, raw.len())?;
+        Ok(())
+    }
+
+    /// Módosítás az IFD-ken keresztül, utána újraírás
+    pub fn edit(&mut self, change: impl FnOnce(&mut ExifIfds)) -> Result<(), String> {
+        let mut ifds = self.ifds()?;
+        change(&mut ifds);
+        self.rebuild(&ifds)
+    }
+}
@@ -0,0 +1,90 @@
+/*
+iview/src/geometry.rs
+
+Fine rotation (straightening) with bilinear resampling,
+cropped to the largest axis-aligned rectangle inside the rotated image
+
+*/
+
+use rayon::prelude::*;
+
+pub const MAX_FINE_ROTATION: f32 = 45.0;
+
+/// A w x h méretű, `angle` radiánnal elforgatott téglalapba írható legnagyobb területű tengelyirányú téglalap
+pub fn inner_rect_size(w: f32, h: f32, angle: f32) -> (f32, f32) {
+    if w <= 0.0 || h <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let width_is_longer = w >= h;
+    let (side_long, side_short) = if width_is_longer { (w, h) } else { (h, w) };
+    let (sin_a, cos_a) = (angle.sin().abs(), angle.cos().abs());
+    if side_short <= 2.0 * sin_a * cos_a * side_long || (sin_a - cos_a).abs() < 1e-10 {
+        // a rövidebb oldal szab határt: két sarok a hosszabb oldalakon
+        let x = 0.5 * side_short;
+        if width_is_longer { (x / sin_a, x / cos_a) } else { (x / cos_a, x / sin_a) }
+    } else {
+        let cos_2a = cos_a * cos_a - sin_a * sin_a;
+        ((w * cos_a - h * sin_a) / cos_2a, (h * cos_a - w * sin_a) / cos_2a)
+    }
+}
+
+/// Bilineáris mintavétel, a szélen a szélső pixellel
+fn sample(img: &image::RgbaImage, x: f32, y: f32) -> [u8; 4] {
+    let (w, h) = (img.width() as i64, img.height() as i64);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let px = |xi: i64, yi: i64| img.get_pixel(xi.clamp(0, w - 1) as u32, yi.clamp(0, h - 1) as u32).0;
+    let (xi, yi) = (x0 as i64, y0 as i64);
+    let (p00, p10, p01, p11) = (px(xi, yi), px(xi + 1, yi), px(xi, yi + 1), px(xi + 1, yi + 1));
+    let mut out = [0u8; 4];
+    for c in 0..4 {
+        let top = p00[c] as f32 * (1.0 - fx) + p10[c] as f32 * fx;
+        let bottom = p01[c] as f32 * (1.0 - fx) + p11[c] as f32 * fx;
+        out[c] = (top * (1.0 - fy) + bottom * fy + 0.5).clamp(0.0, 255.0) as u8;
+    }
+    out
+}
+
+/// Elforgatás `degrees` fokkal (pozitív: óramutató szerint), a legnagyobb belső téglalapra vágva
+pub fn rotate_fine(img: &image::RgbaImage, degrees: f32) -> image::RgbaImage {
+    if degrees.abs() < 0.001 || img.width() == 0 || img.height() == 0 {
+        return img.clone();
+    }
+    let angle = degrees.clamp(-MAX_FINE_ROTATION, MAX_FINE_ROTATION).to_radians();
+    let (src_w, src_h) = (img.width() as f32, img.height() as f32);
+    let (inner_w, inner_h) = inner_rect_size(src_w, src_h, angle);
+    let (out_w, out_h) = ((inner_w.floor() as u32).max(1), (inner_h.floor() as u32).max(1));
+    let (sin_a, cos_a) = angle.sin_cos();
+    let (src_cx, src_cy) = (src_w * 0.5, src_h * 0.5);
+    let (out_cx, out_cy) = (out_w as f32 * 0.5, out_h as f32 * 0.5);
+
+    let mut out = image::RgbaImage::new(out_w, out_h);
+    out.par_chunks_mut(out_w as usize * 4).enumerate().for_each(|(y, row)| {
+        let dy = y as f32 + 0.5 - out_cy;
+        for x in 0..out_w as usize {
+            let dx = x as f32 + 0.5 - out_cx;
+            // visszafelé forgatva keressük a forrás pontot
+            let sx = src_cx + dx * cos_a + dy * sin_a - 0.5;
+            let sy = src_cy - dx * sin_a + dy * cos_a - 0.5;
+            row[x * 4..x * 4 + 4].copy_from_slice(&sample(img, sx, sy));
+        }
+    });
+    out
+}
+
+/// A vízszintesnek (vagy függőlegesnek) szánt vonal alapján a kiegyenesítő forgatás fokban
+pub fn straighten_angle(from: (f32, f32), to: (f32, f32)) -> Option<f32> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    if dx.hypot(dy) < 4.0 {
+        return None;
+    }
+    let mut angle = dy.atan2(dx).to_degrees(); // -180 .. 180, lefelé pozitív
+    // a legközelebbi tengelyhez igazítunk
+    while angle > 45.0 {
+        angle -= 90.0;
+    }
+    while angle < -45.0 {
+        angle += 90.0;
+    }
+    Some(-angle)
+}
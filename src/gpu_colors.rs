@@ -1,11 +1,15 @@
 use eframe::wgpu;
-use crate::colors::ColorSettings;
+use crate::colors::{ColorSettings, CubeLut, rgba_histogram};
 //use wgpu::util::DeviceExt;
 use std::sync::Arc;
+use iview::t;
 
 // Ez kényszeríti a Rustot, hogy figyelje a shader fájlt
 const _: &str = include_str!("shaders.wgsl");
 
+// Darabolt feldolgozásnál ennyi pixel átfedés kell az élesítés mintavételezéséhez (r <= 10)
+const TILE_MARGIN: u32 = 16;
+
 // GPU-kompatibilis ColorSettings
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
@@ -35,7 +39,7 @@ pub struct GpuSharpenSettings {
     pub transparency_tolerance: f32,
     pub use_transparency: u32,
     pub rough_transparency: u32, // for gif
-    pub _padding: u32, // 16 bájtos igazítás
+    pub look_intensity: f32, // 0 = nincs .cube LUT
 }
 
 #[repr(C)]
@@ -46,6 +50,7 @@ pub struct GpuInterface {
     pipe_apply: wgpu::ComputePipeline,
     tex_identity: wgpu::Texture,
     pub tex_processed_lut: wgpu::Texture,
+    tex_look: wgpu::Texture,
     color_params_buffer: wgpu::Buffer,
     sharpen_params_buffer: wgpu::Buffer,
     hist_buffer: wgpu::Buffer,
@@ -62,7 +67,7 @@ impl GpuInterface {
     pub fn gpu_init(render_state: &egui_wgpu::RenderState) -> Option<Self> {
         let limits = render_state.adapter.limits();
         if limits.max_storage_textures_per_shader_stage < 1 {
-            eprintln!("Hiba: A GPU nem támogatja a Storage Texture-öket (VirtualBox/régi driver).");
+            eprintln!("{}", t!("gpu-no-storage-texture"));
             return None;
         }
 
@@ -83,6 +88,7 @@ impl GpuInterface {
 
         let tex_identity = device.create_texture(&lut_desc);
         let tex_processed_lut = device.create_texture(&lut_desc);
+        let tex_look = device.create_texture(&lut_desc);
         
         // Alap LUT feltöltése (Identity)
         let identity_data = create_3d_identity_data(); // 33x33x33x4 bájt
@@ -92,6 +98,12 @@ impl GpuInterface {
             wgpu::TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(33 * 4), rows_per_image: Some(33) },
             lut_desc.size,
         );
+        queue.write_texture(
+            tex_look.as_image_copy(),
+            &identity_data,
+            wgpu::TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(33 * 4), rows_per_image: Some(33) },
+            lut_desc.size,
+        );
 
         // Sampler az interpolációhoz
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -171,6 +183,7 @@ impl GpuInterface {
                 wgpu::BindGroupLayoutEntry { binding: 3, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None }, count: None },
                 wgpu::BindGroupLayoutEntry { binding: 4, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::StorageTexture { access: wgpu::StorageTextureAccess::WriteOnly, format: wgpu::TextureFormat::Rgba8Unorm, view_dimension: wgpu::TextureViewDimension::D2 }, count: None },
                 wgpu::BindGroupLayoutEntry { binding: 5, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Storage{read_only: false}, has_dynamic_offset: false, min_binding_size: None }, count: None },
+                wgpu::BindGroupLayoutEntry { binding: 6, visibility: wgpu::ShaderStages::COMPUTE, ty: wgpu::BindingType::Texture { sample_type: wgpu::TextureSampleType::Float { filterable: true }, view_dimension: wgpu::TextureViewDimension::D3, multisampled: false }, count: None },
             ],
         });
 
@@ -253,6 +266,7 @@ impl GpuInterface {
             pipe_apply,
             tex_identity,
             tex_processed_lut,
+            tex_look,
             color_params_buffer,
             sharpen_params_buffer,
             hist_buffer,
@@ -298,7 +312,7 @@ impl GpuInterface {
             transparency_tolerance: colset.transparency_tolerance,
             use_transparency: if colset.use_transparency { 1 } else { 0 },
             rough_transparency: if colset.rough_transparency { 1 } else { 0 },
-            _padding: 0,
+            look_intensity: if colset.is_look() { colset.look_intensity } else { 0.0 },
         };
         self.queue.write_buffer(&self.sharpen_params_buffer, 0, bytemuck::bytes_of(&gpu_filter));
 
@@ -322,6 +336,20 @@ impl GpuInterface {
         self.queue.submit(Some(encoder.finish()));
     }
     ///////////////////////////////////////////////////////////////////////////
+    /// Betölti a .cube LUT-ot (33x33x33-ra átmintavételezve), None esetén identitás.
+    pub fn set_look(&self, look: Option<&CubeLut>) {
+        let data = match look {
+            Some(look) => look.to_rgba8(33),
+            None => create_3d_identity_data(),
+        };
+        self.queue.write_texture(
+            self.tex_look.as_image_copy(),
+            &data,
+            wgpu::TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(33 * 4), rows_per_image: Some(33) },
+            wgpu::Extent3d { width: 33, height: 33, depth_or_array_layers: 33 },
+        );
+    }
+    ///////////////////////////////////////////////////////////////////////////
 
     pub fn generate_image(&self, img_data: &mut [u8], width: u32, height: u32, hist : &mut Vec<u32>) {
 
@@ -369,6 +397,7 @@ impl GpuInterface {
                 wgpu::BindGroupEntry { binding: 3, resource: self.sharpen_params_buffer.as_entire_binding() },
                 wgpu::BindGroupEntry { binding: 4, resource: wgpu::BindingResource::TextureView(&tex_out.create_view(&Default::default())) },
                 wgpu::BindGroupEntry { binding: 5, resource: self.hist_buffer.as_entire_binding() }, 
+                wgpu::BindGroupEntry { binding: 6, resource: wgpu::BindingResource::TextureView(&self.tex_look.create_view(&Default::default())) },
             ],
         });
 
@@ -465,6 +494,33 @@ impl GpuInterface {
         }
     }    
 
+    /// A textúra méretkorlátnál nagyobb kép feldolgozása darabokban, átfedéssel
+    pub fn generate_image_tiled(&self, img: &mut image::RgbaImage, hist: &mut Vec<u32>) {
+        let (width, height) = img.dimensions();
+        let max_side = self.device.limits().max_texture_dimension_2d;
+        if width <= max_side && height <= max_side {
+            self.generate_image(img.as_mut(), width, height, hist);
+            return;
+        }
+        let step = max_side - 2 * TILE_MARGIN;
+        let source = img.clone(); // az átfedő sávokat az eredeti képből vesszük
+        let mut tile_hist = vec![0u32; 1024];
+        for y0 in (0..height).step_by(step as usize) {
+            for x0 in (0..width).step_by(step as usize) {
+                let x1 = x0.saturating_sub(TILE_MARGIN);
+                let y1 = y0.saturating_sub(TILE_MARGIN);
+                let x2 = (x0 + step + TILE_MARGIN).min(width);
+                let y2 = (y0 + step + TILE_MARGIN).min(height);
+                let mut tile = image::imageops::crop_imm(&source, x1, y1, x2 - x1, y2 - y1).to_image();
+                self.generate_image(tile.as_mut(), x2 - x1, y2 - y1, &mut tile_hist);
+                let inner = image::imageops::crop_imm(&tile, x0 - x1, y0 - y1,
+                    (x0 + step).min(width) - x0, (y0 + step).min(height) - y0).to_image();
+                image::imageops::replace(img, &inner, x0 as i64, y0 as i64);
+            }
+        }
+        *hist = rgba_histogram(img); // az átfedések miatt a darabok hisztogramja nem összegezhető
+    }
+
 }
     ///////////////////////////////////////////////////////////////////////////
 
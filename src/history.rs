@@ -0,0 +1,228 @@
+/*
+iview/src/history.rs
+
+Undo / redo of the view edits (ColorSettings snapshots) with a history panel
+
+*/
+
+use std::time::{Duration, Instant};
+
+use crate::bindings::Action;
+use crate::colors::*;
+use crate::ImageViewer;
+use iview::t;
+
+pub const HISTORY_LIMIT: usize = 100;
+/// Ennyi nyugalom után kerül a változás a listába (csúszka húzás közben nem)
+const SETTLE_TIME: Duration = Duration::from_millis(400);
+/// Az ennél közelebbi, azonos fajta változások egy lépésbe olvadnak
+const COALESCE_TIME: Duration = Duration::from_millis(1500);
+
+pub struct HistoryStep {
+    pub label: String,
+    pub settings: ColorSettings,
+}
+
+pub struct History {
+    pub steps: Vec<HistoryStep>,
+    pub current: usize,
+    last_seen: ColorSettings,   // az előző képkocka beállításai
+    last_change: Instant,       // mikor változott utoljára
+    committed_at: Instant,      // mikor került be az utolsó lépés
+}
+
+impl Default for History {
+    fn default() -> Self {
+        let settings = ColorSettings::default();
+        Self {
+            steps: vec![HistoryStep { label: t!("history-open"), settings }],
+            current: 0,
+            last_seen: settings,
+            last_change: Instant::now(),
+            committed_at: Instant::now(),
+        }
+    }
+}
+
+impl History {
+    /// Új kép: a lista az aktuális beállításokkal indul
+    pub fn reset(&mut self, settings: ColorSettings) {
+        *self = History::default();
+        self.steps[0].settings = settings;
+        self.last_seen = settings;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.current + 1 < self.steps.len()
+    }
+
+    /// Új állapot a lista végére; a visszavont lépések elvesznek
+    fn commit(&mut self, settings: ColorSettings) {
+        let label = describe_change(&self.steps[self.current].settings, &settings);
+        self.steps.truncate(self.current + 1);
+        let merge = self.current > 0
+            && self.steps[self.current].label == label
+            && self.committed_at.elapsed() < COALESCE_TIME;
+        if merge {
+            if self.steps[self.current - 1].settings == settings {
+                // visszajutottunk az előzőhöz, pl. kétszer invertálva
+                self.steps.pop();
+                self.current -= 1;
+            } else {
+                self.steps[self.current].settings = settings;
+            }
+        } else {
+            self.steps.push(HistoryStep { label, settings });
+            if self.steps.len() > HISTORY_LIMIT {
+                self.steps.remove(0);
+            }
+            self.current = self.steps.len() - 1;
+        }
+        self.last_seen = settings;
+        self.committed_at = Instant::now();
+    }
+}
+
+/// Rövid leírás arról, mi változott két állapot között
+fn describe_change(from: &ColorSettings, to: &ColorSettings) -> String {
+    if *to == ColorSettings::default() {
+        return t!("history-reset");
+    }
+    let mut parts = Vec::new();
+    if from.orientation != to.orientation { parts.push(t!("history-orientation")); }
+    if from.rotation != to.rotation { parts.push(t!("history-straighten")); }
+    if from.crop != to.crop { parts.push(t!("history-crop")); }
+    if (from.show_r, from.show_g, from.show_b) != (to.show_r, to.show_g, to.show_b) { parts.push(t!("history-channels")); }
+    if from.invert != to.invert { parts.push(t!("history-invert")); }
+    if from.gamma != to.gamma { parts.push(t!("history-gamma")); }
+    if from.contrast != to.contrast { parts.push(t!("history-contrast")); }
+    if from.brightness != to.brightness { parts.push(t!("history-brightness")); }
+    if from.oklab != to.oklab { parts.push(t!("history-color-algorithm")); }
+    if from.hue_shift != to.hue_shift { parts.push(t!("history-hue")); }
+    if from.saturation != to.saturation { parts.push(t!("history-saturation")); }
+    if from.sharpen_amount != to.sharpen_amount || from.sharpen_radius != to.sharpen_radius { parts.push(t!("history-sharpen")); }
+    if from.use_transparency != to.use_transparency || from.rough_transparency != to.rough_transparency
+        || from.transparent_color != to.transparent_color || from.transparency_tolerance != to.transparency_tolerance {
+        parts.push(t!("history-transparency"));
+    }
+    if from.use_look != to.use_look || from.look_intensity != to.look_intensity { parts.push(t!("history-look")); }
+    match parts.len() {
+        0 => t!("history-edit"),
+        1..=3 => parts.join(", "),
+        _ => t!("history-many", first = parts[..2].join(", "), count = parts.len()),
+    }
+}
+
+impl ImageViewer {
+
+    /// Képkockánként: a lezárult változás új lépés lesz (a húzás egyben)
+    pub fn track_history(&mut self, ctx: &egui::Context) {
+        let settings = self.color_settings;
+        let history = &mut self.history;
+        if settings != history.last_seen {
+            history.last_seen = settings;
+            history.last_change = Instant::now();
+        }
+        if settings == history.steps[history.current].settings {
+            return;
+        }
+        let dragging = ctx.input(|i| i.pointer.any_down());
+        if dragging || history.last_change.elapsed() < SETTLE_TIME {
+            ctx.request_repaint_after(SETTLE_TIME);
+            return;
+        }
+        history.commit(settings);
+    }
+
+    /// A még függő változás azonnal lépés lesz (pl. gyors Ctrl+Z előtt)
+    fn flush_history(&mut self) {
+        if self.color_settings != self.history.steps[self.history.current].settings {
+            self.history.commit(self.color_settings);
+        }
+    }
+
+    /// Ugrás a lista tetszőleges állapotára
+    pub fn jump_to_history(&mut self, ctx: &egui::Context, index: usize) {
+        self.flush_history();
+        if index >= self.history.steps.len() || index == self.history.current {
+            return;
+        }
+        let settings = self.history.steps[index].settings;
+        self.history.current = index;
+        self.history.last_seen = settings;
+        let rotated = settings.orientation.swaps_axes() != self.color_settings.orientation.swaps_axes();
+        self.color_settings = settings;
+        self.crop_orientation = settings.orientation; // a visszaállított kivágás maradjon
+        self.review(ctx, true, rotated);
+    }
+
+    pub fn undo(&mut self, ctx: &egui::Context) {
+        self.flush_history();
+        if self.history.can_undo() {
+            self.jump_to_history(ctx, self.history.current - 1);
+        }
+    }
+
+    pub fn redo(&mut self, ctx: &egui::Context) {
+        self.flush_history();
+        if self.history.can_redo() {
+            self.jump_to_history(ctx, self.history.current + 1);
+        }
+    }
+
+    pub fn draw_history_panel(&mut self, ctx: &egui::Context) {
+        if !self.show_history {
+            return;
+        }
+        let mut open = true;
+        let mut jump = None;
+        let mut undo = false;
+        let mut redo = false;
+        egui::Window::new(t!("history-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(200.0)
+            .pivot(egui::Align2::RIGHT_TOP)
+            .default_pos(ctx.viewport_rect().right_top() + egui::vec2(-10.0, 40.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(self.history.can_undo(), egui::Button::new(t!("history-undo"))).on_hover_text(self.bindings.text(ctx, Action::Undo)).clicked() {
+                        undo = true;
+                    }
+                    if ui.add_enabled(self.history.can_redo(), egui::Button::new(t!("history-redo"))).on_hover_text(self.bindings.text(ctx, Action::Redo)).clicked() {
+                        redo = true;
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    for (index, step) in self.history.steps.iter().enumerate() {
+                        let text = if index > self.history.current {
+                            egui::RichText::new(&step.label).weak() // visszavont lépés
+                        } else {
+                            egui::RichText::new(&step.label)
+                        };
+                        if ui.selectable_label(index == self.history.current, text).clicked() {
+                            jump = Some(index);
+                        }
+                    }
+                });
+            });
+        if undo {
+            self.undo(ctx);
+        }
+        if redo {
+            self.redo(ctx);
+        }
+        if let Some(index) = jump {
+            self.jump_to_history(ctx, index);
+        }
+        if !open {
+            self.show_history = false;
+        }
+    }
+}
@@ -0,0 +1,155 @@
+/*
+iview/src/i18n.rs
+
+Message catalogs: every user-facing text goes through t!("key"),
+the English and Hungarian catalogs are Fluent-style .ftl files built into the binary
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum Language {
+    English,
+    Hungarian,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Hungarian];
+
+    /// A nyelv neve a saját nyelvén, a menühöz
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Hungarian => "Magyar",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::Hungarian => include_str!("../locales/hu.ftl"),
+        }
+    }
+
+    /// A rendszer nyelve a környezeti változók szerint, különben angol
+    pub fn system() -> Self {
+        for var in ["LC_ALL", "LC_MESSAGES", "LANG", "LANGUAGE"] {
+            if let Ok(value) = std::env::var(var) {
+                if value.is_empty() {
+                    continue;
+                }
+                return if value.to_lowercase().starts_with("hu") { Language::Hungarian } else { Language::English };
+            }
+        }
+        Language::English
+    }
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::system()
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(u8::MAX); // MAX: még nincs beállítva
+static CATALOGS: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        0 => Language::English,
+        1 => Language::Hungarian,
+        _ => {
+            let language = Language::system();
+            set_language(language);
+            language
+        }
+    }
+}
+
+/// Fluent részhalmaz: `# megjegyzés`, `kulcs = szöveg { $valtozo }`, behúzott folytatósorok
+/// (a közös behúzás levágva, az üres sorok megmaradnak)
+fn parse_catalog(source: &str) -> HashMap<String, String> {
+    fn finish(catalog: &mut HashMap<String, String>, key: String, first: String, lines: Vec<&str>) {
+        let indent = lines.iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let mut value = first;
+        for line in lines.iter().map(|l| l.get(indent..).unwrap_or("").trim_end()) {
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(line);
+        }
+        catalog.insert(key, value.trim_end().to_string());
+    }
+    let mut catalog = HashMap::new();
+    let mut current: Option<(String, String, Vec<&str>)> = None;
+    for line in source.lines() {
+        if line.starts_with(' ') || (line.trim().is_empty() && current.is_some()) {
+            if let Some((_, _, lines)) = &mut current {
+                lines.push(line);
+            }
+            continue;
+        }
+        if let Some((key, first, lines)) = current.take() {
+            finish(&mut catalog, key, first, lines);
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            current = Some((key.trim().to_string(), value.trim().to_string(), Vec::new()));
+        }
+    }
+    if let Some((key, first, lines)) = current {
+        finish(&mut catalog, key, first, lines);
+    }
+    catalog
+}
+
+fn catalogs() -> &'static Vec<HashMap<String, String>> {
+    CATALOGS.get_or_init(|| Language::ALL.iter().map(|l| parse_catalog(l.source())).collect())
+}
+
+/// A kulcs szövege a beállított nyelven; ha hiányzik, angolul, végül maga a kulcs
+pub fn tr(key: &str) -> String {
+    let catalogs = catalogs();
+    catalogs[language() as usize].get(key)
+        .or_else(|| catalogs[Language::English as usize].get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Mint `tr`, a `{ $nev }` helyekre a megadott értékekkel
+pub fn tr_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = tr(key);
+    for (name, value) in args {
+        let value = value.to_string();
+        for placeholder in [format!("{{ ${} }}", name), format!("{{${}}}", name)] {
+            text = text.replace(&placeholder, &value);
+        }
+    }
+    text
+}
+
+/// `t!("menu-open")` vagy `t!("error-save", file = path.display(), error = e)`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::tr_args($key, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
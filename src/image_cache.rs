@@ -0,0 +1,222 @@
+/*
+iview/src/image_cache.rs
+
+Background prefetch of the neighbour images and a memory-bounded LRU cache
+
+*/
+
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::time::SystemTime;
+
+use crate::codec::*;
+use crate::file_handlers::SortDir;
+
+pub const DEFAULT_CACHE_MB: usize = 1024;
+
+struct Job {
+    path: PathBuf,
+    format: SaveFormat,
+    generation: u64,
+}
+
+struct CacheEntry {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    bytes: usize,
+    image: LoadedImage,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: Vec<CacheEntry>, // LRU sorrend: elöl a legutóbb használt
+    used: usize,
+    budget: usize,
+    wanted: Vec<PathBuf>,     // az aktuális kép szomszédai, ezeket töltjük elő
+    loading: Option<PathBuf>, // a háttérszál éppen ezt dekódolja
+    generation: u64,          // törléskor nő, a régi eredményeket eldobjuk
+}
+
+struct Shared {
+    state: Mutex<CacheState>,
+    done: Condvar,
+}
+
+pub struct ImageCache {
+    shared: Arc<Shared>,
+    sender: Option<mpsc::Sender<Job>>,
+    folder: Option<PathBuf>,
+    sort: SortDir,
+}
+
+/// A cache olvasása más szálról (a háttérben futó betöltés használja)
+#[derive(Clone)]
+pub struct CacheReader {
+    shared: Arc<Shared>,
+}
+
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn image_bytes(image: &LoadedImage) -> usize {
+    let mut bytes = image.image.as_bytes().len();
+    if let Some(anim) = &image.animation {
+        bytes += anim.anim_frames.iter().map(|f| f.as_bytes().len()).sum::<usize>();
+    }
+    bytes
+}
+
+impl CacheState {
+    fn position(&self, path: &Path) -> Option<usize> {
+        self.entries.iter().position(|e| e.path == path)
+    }
+
+    fn remove(&mut self, pos: usize) -> CacheEntry {
+        let entry = self.entries.remove(pos);
+        self.used -= entry.bytes;
+        entry
+    }
+
+    fn insert(&mut self, path: PathBuf, modified: Option<SystemTime>, image: LoadedImage) {
+        let bytes = image_bytes(&image);
+        if bytes > self.budget {
+            return;
+        }
+        if let Some(pos) = self.position(&path) {
+            self.remove(pos);
+        }
+        while self.used + bytes > self.budget {
+            let last = self.entries.len() - 1;
+            self.remove(last);
+        }
+        self.used += bytes;
+        self.entries.insert(0, CacheEntry { path, modified, bytes, image });
+    }
+
+    fn is_cached(&self, path: &Path, modified: Option<SystemTime>) -> bool {
+        self.entries.iter().any(|e| e.path == path && e.modified == modified)
+    }
+}
+
+impl CacheReader {
+    /// A kép a cache-ből, ha megvan és azóta nem változott a fájl.
+    /// Ha a háttérszál éppen ezt dekódolja, megvárjuk.
+    pub fn get(&self, path: &Path) -> Option<LoadedImage> {
+        let modified = file_modified(path);
+        let mut state = self.shared.state.lock().unwrap();
+        while state.loading.as_deref() == Some(path) {
+            state = self.shared.done.wait(state).unwrap();
+        }
+        let pos = state.position(path)?;
+        let entry = state.remove(pos);
+        if entry.modified != modified {
+            return None;
+        }
+        let image = entry.image.clone();
+        state.used += entry.bytes;
+        state.entries.insert(0, entry);
+        Some(image)
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_MB)
+    }
+}
+
+impl ImageCache {
+    pub fn new(budget_mb: usize) -> Self {
+        let state = CacheState {
+            budget: budget_mb * 1024 * 1024,
+            ..Default::default()
+        };
+        Self {
+            shared: Arc::new(Shared { state: Mutex::new(state), done: Condvar::new() }),
+            sender: None,
+            folder: None,
+            sort: SortDir::Name,
+        }
+    }
+
+    pub fn set_budget(&mut self, budget_mb: usize) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.budget = budget_mb * 1024 * 1024;
+        while state.used > state.budget {
+            let last = state.entries.len() - 1;
+            state.remove(last);
+        }
+    }
+
+    /// Mindent eldob; a folyamatban lévő dekódolás eredménye sem kerül be
+    pub fn clear(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.entries.clear();
+        state.used = 0;
+        state.wanted.clear();
+        state.generation += 1;
+    }
+
+    /// Könyvtár- vagy rendezésváltáskor üríti a cache-t
+    pub fn set_list(&mut self, folder: &Option<PathBuf>, sort: SortDir) {
+        if self.folder != *folder || self.sort != sort {
+            self.folder = folder.clone();
+            self.sort = sort;
+            self.clear();
+        }
+    }
+
+    /// A kép a cache-ből, ha megvan és azóta nem változott a fájl
+    pub fn get(&self, path: &Path) -> Option<LoadedImage> {
+        self.reader().get(path)
+    }
+
+    pub fn reader(&self) -> CacheReader {
+        CacheReader { shared: self.shared.clone() }
+    }
+
+    /// A megadott képek dekódolása a háttérben (a korábbi kérések érvényüket vesztik)
+    pub fn prefetch(&mut self, paths: Vec<(PathBuf, SaveFormat)>) {
+        let generation = {
+            let mut state = self.shared.state.lock().unwrap();
+            state.wanted = paths.iter().map(|(p, _)| p.clone()).collect();
+            state.generation
+        };
+        for (path, format) in paths {
+            let job = Job { path, format, generation };
+            let sender = self.sender.get_or_insert_with(|| {
+                let (sender, receiver) = mpsc::channel();
+                let shared = self.shared.clone();
+                std::thread::spawn(move || worker(shared, receiver));
+                sender
+            });
+            let _ = sender.send(job);
+        }
+    }
+}
+
+fn worker(shared: Arc<Shared>, receiver: mpsc::Receiver<Job>) {
+    while let Ok(job) = receiver.recv() {
+        let modified = file_modified(&job.path);
+        {
+            let mut state = shared.state.lock().unwrap();
+            if state.generation != job.generation
+                || !state.wanted.contains(&job.path)
+                || state.is_cached(&job.path, modified)
+            {
+                continue;
+            }
+            state.loading = Some(job.path.clone());
+        }
+        let result = std::panic::catch_unwind(|| load_image_as(&job.path, job.format));
+        let mut state = shared.state.lock().unwrap();
+        state.loading = None;
+        if let Ok(Ok(image)) = result {
+            if state.generation == job.generation {
+                state.insert(job.path, modified, image);
+            }
+        }
+        shared.done.notify_all();
+    }
+}
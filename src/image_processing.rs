@@ -4,8 +4,12 @@ use std::path::PathBuf;
 use std::env;
 use crate::ImageViewer;
 use crate::colors::*;
+use crate::geometry::rotate_fine;
+use crate::pf32::Pf32;
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::cell::RefCell;
 
 // Segédfüggvény a vágólapon lévő kép kimentéséhez egy ideiglenes fájlba
 pub fn save_clipboard_image() -> Option<PathBuf> {
@@ -85,19 +89,6 @@ impl BackgroundStyle {
 }
 
 
-#[derive(Clone, Debug)]
-pub struct Resolution {
-    pub xres: f32,
-    pub yres: f32,
-    pub dpi: bool,
-}
-
-pub struct AnimatedImage {
-    //pub anim_frames: Vec<egui::TextureHandle>, // GPU textúrák // old
-    pub anim_frames: Vec<image::DynamicImage>,
-    pub delays: Vec<std::time::Duration>, // Időzítések
-}
-
 /*pub fn color_image_to_dynamic(color_image: egui::ColorImage) -> image::DynamicImage {
     let size = color_image.size;
     // Flatten Color32 (RGBA) pixels into a Vec<u8>
@@ -107,7 +98,7 @@ pub struct AnimatedImage {
 
     // Create an RgbaImage buffer
     let buffer = image::RgbaImage::from_raw(size[0] as u32, size[1] as u32, pixels)
-        .expect("Failed to create image buffer");
+        .unwrap_or_else(|| panic!("{}", t!("error-image-buffer")));
 
     // Wrap in DynamicImage
     image::DynamicImage::ImageRgba8(buffer)
@@ -175,8 +166,147 @@ pub fn draw_custom_background(ui: &mut egui::Ui, bg_style: &BackgroundStyle) {
     }
 }
 
+// Egy feltöltött darab legnagyobb oldala; nagyításkor csak a látható darabok kerülnek a GPU-ra
+pub const TILE_SIDE: u32 = 1024;
+// Az áttekintő kép legnagyobb oldala (a GPU korlátnál kisebb is lehet); ekkora képet egyben töltünk fel
+pub const MAX_OVERVIEW_SIDE: u32 = 4096;
+
+/// A kép egy darabja GPU textúraként; a helye és mérete a mip szint pixeleiben
+pub struct ImageTile {
+    pub texture: egui::TextureHandle,
+    pub level: usize, // mip szint: a 2^level-ed méretű képből
+    pub pos: Pf32,
+    pub size: Pf32,
+}
+
+#[derive(Default)]
+struct TileCache {
+    levels: Vec<Arc<image::RgbaImage>>, // 0: a teljes kép, utána rendre feleződő, igény szerint készül
+    tiles: Vec<ImageTile>,
+}
+
+impl TileCache {
+    fn level(&mut self, level: usize) -> Arc<image::RgbaImage> {
+        while self.levels.len() <= level {
+            let Some(last) = self.levels.last() else {
+                return Arc::new(image::RgbaImage::new(1, 1));
+            };
+            let (w, h) = ((last.width() / 2).max(1), (last.height() / 2).max(1));
+            let half = image::imageops::thumbnail(last.as_ref(), w, h);
+            self.levels.push(Arc::new(half));
+        }
+        self.levels[level].clone()
+    }
+}
+
+/// Egy kép a GPU-n: egy kicsinyített áttekintő kép, nagyításkor pedig csak a látható darabok
+/// a nagyításhoz illő mip szinten. A darabok rajzoláskor töltődnek fel, a kilógók felszabadulnak.
+#[derive(Default)]
+pub struct TileSet {
+    name: String,
+    overview: Option<egui::TextureHandle>,
+    full: bool, // a kép egy textúrába fért, az áttekintő kép a teljes felbontású kép
+    cache: RefCell<TileCache>,
+}
+
+impl TileSet {
+    pub fn new(ctx: &egui::Context, image: Arc<image::RgbaImage>, name: &str) -> TileSet {
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return TileSet::default();
+        }
+        let max_side = (ctx.input(|i| i.max_texture_side) as u32).min(MAX_OVERVIEW_SIDE);
+        let full = width <= max_side && height <= max_side;
+        let color_image = if full {
+            egui::ColorImage::from_rgba_unmultiplied([width as usize, height as usize], image.as_raw())
+        } else {
+            let scale = max_side as f32 / width.max(height) as f32;
+            let (w, h) = (((width as f32 * scale) as u32).max(1), ((height as f32 * scale) as u32).max(1));
+            let thumb = image::imageops::thumbnail(image.as_ref(), w, h);
+            egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], thumb.as_raw())
+        };
+        TileSet {
+            name: name.to_string(),
+            overview: Some(ctx.load_texture(format!("{}_overview", name), color_image, Default::default())),
+            full,
+            cache: RefCell::new(TileCache { levels: vec![image], tiles: Vec::new() }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overview.is_none()
+    }
+
+    /// Kirajzolás a rect-be, a painter vágásával. Kicsinyítve az áttekintő kép is elég részletes,
+    /// különben a látható darabok kerülnek ki (és fel, ha még nincsenek a GPU-n).
+    pub fn paint(&self, painter: &egui::Painter, rect: egui::Rect, magnify: f32) {
+        let Some(overview) = &self.overview else {
+            return;
+        };
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        if self.full || rect.width() <= overview.size()[0] as f32 {
+            self.cache.borrow_mut().tiles.clear();
+            painter.image(overview.id(), rect, uv, egui::Color32::WHITE);
+            return;
+        }
+        let visible = painter.clip_rect().intersect(rect);
+        if visible.width() <= 0.0 || visible.height() <= 0.0 {
+            return;
+        }
+        // a szint legfeljebb kétszeres kicsinyítéssel kerül ki, nagyításkor a teljes kép
+        let level = if magnify < 1.0 { (1.0 / magnify).log2().floor() as usize } else { 0 };
+        let mut cache = self.cache.borrow_mut();
+        let source = cache.level(level);
+        let (width, height) = source.dimensions();
+        // egy szint pixel ennyi képernyő pixel
+        let scale = egui::vec2(rect.width() / width as f32, rect.height() / height as f32);
+        let min = (visible.min - rect.min) / scale;
+        let max = (visible.max - rect.min) / scale;
+        let first = ((min.x.max(0.0) as u32) / TILE_SIDE, (min.y.max(0.0) as u32) / TILE_SIDE);
+        let last = ((max.x.ceil() as u32).min(width).div_ceil(TILE_SIDE), (max.y.ceil() as u32).min(height).div_ceil(TILE_SIDE));
+
+        // a látható sáv körül egy darabnyi tartalék marad görgetéshez, a többi felszabadul
+        cache.tiles.retain(|tile| {
+            let (x, y) = (tile.pos.x as u32 / TILE_SIDE, tile.pos.y as u32 / TILE_SIDE);
+            tile.level == level && x + 1 >= first.0 && x <= last.0 && y + 1 >= first.1 && y <= last.1
+        });
+        for y in first.1..last.1 {
+            for x in first.0..last.0 {
+                let pos: Pf32 = (x * TILE_SIDE, y * TILE_SIDE).into();
+                if cache.tiles.iter().any(|tile| tile.pos == pos) {
+                    continue;
+                }
+                let (w, h) = (TILE_SIDE.min(width - x * TILE_SIDE), TILE_SIDE.min(height - y * TILE_SIDE));
+                let tile = image::imageops::crop_imm(source.as_ref(), x * TILE_SIDE, y * TILE_SIDE, w, h).to_image();
+                let color_image = egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], tile.as_raw());
+                let name = format!("{}_{}_{}_{}", self.name, level, x, y);
+                cache.tiles.push(ImageTile {
+                    texture: painter.ctx().load_texture(name, color_image, Default::default()),
+                    level,
+                    pos,
+                    size: (w, h).into(),
+                });
+            }
+        }
+        for tile in &cache.tiles {
+            let (min, max): (egui::Vec2, egui::Vec2) = (tile.pos.into(), (tile.pos + tile.size).into());
+            let tile_rect = egui::Rect::from_min_max(rect.min + min * scale, rect.min + max * scale);
+            if tile_rect.intersects(visible) {
+                painter.image(tile.texture.id(), tile_rect, uv, egui::Color32::WHITE);
+            }
+        }
+    }
+}
+
 impl ImageViewer {
 
+    /// A színbeállítások az EXIF állással kiegészítve, ahogy a nézet és a "Save View" használja
+    pub fn view_settings(&self) -> ColorSettings {
+        let mut settings = self.color_settings;
+        settings.orientation = self.exif_orientation.then(settings.orientation);
+        settings
+    }
+
     pub fn review(&mut self, ctx: &egui::Context, coloring: bool, new_rotate: bool) {
         
         let default_settings = ColorSettings::default();
@@ -185,7 +315,7 @@ impl ImageViewer {
             }
             else {
                 let lut_ref = self.lut.get_or_insert_with(Lut4ColorSettings::default);
-                lut_ref.update_lut( if self.show_original_only { &default_settings} else { &self.color_settings} );
+                lut_ref.update_lut( if self.show_original_only { &default_settings} else { &self.color_settings}, self.look.as_ref() );
             }
         } else {
             self.lut = None;
@@ -194,30 +324,9 @@ impl ImageViewer {
 
         let img : & mut image::DynamicImage = &mut Default::default();
         
-        if let Some(resized_image) = self.resized_image.clone() {
-            *img = resized_image;
-        }
-        else {
-            if let Some(image) = self.original_image.clone() {
-                let w_orig = image.width();
-                let h_orig = image.height();
-                self.original_image_size = (w_orig,h_orig).into();
-                let max_side = ctx.input(|i| i.max_texture_side) as u32;
-                if w_orig > max_side || h_orig > max_side  {
-                    let magn = (w_orig as f32 / max_side as f32 ).max( h_orig as f32 / max_side as f32 ).ceil();
-                    let w_new = (w_orig as f32/magn).ceil();
-                    let h_new = (h_orig as f32/magn).ceil();
-                     *img = image.thumbnail(w_new as u32, h_new as u32);
-                    //*img = image.resize( w_new, h_new, image::imageops::FilterType::Triangle, );
-                    self.resize =  w_orig as f32 / img.width() as f32;
-                    //println!("resize:{}",self.resize);
-                    self.resized_image = Some(img.clone());
-                }
-                else {
-                    self.resize = 1.0;
-                    *img = image;
-                }
-            }
+        if let Some(image) = self.original_image.clone() {
+            self.original_image_size = (image.width(),image.height()).into();
+            *img = image;
         }
         let mark : & mut image::DynamicImage = &mut Default::default();
         if let Some(mark_img) = self.check_mark_img.clone() {
@@ -226,15 +335,28 @@ impl ImageViewer {
 
         self.modified = !self.show_original_only &&
                 (self.color_settings.is_setted() || self.color_settings.is_blured());
-        match self.color_settings.orientation {
-            Orientation::Rotate90   => { *img = img.rotate90() ; *mark = mark.rotate90() ; self.modified = true; }, 
-            Orientation::Rotate180  => { *img = img.rotate180(); *mark = mark.rotate180(); self.modified = true; },
-            Orientation::Rotate270  => { *img = img.rotate270(); *mark = mark.rotate270(); self.modified = true; },
-            Orientation::Rotate0F   => { *img = img.fliph(); *mark = mark.fliph(); self.modified = true; },
-            Orientation::Rotate180F => { *img = img.flipv(); *mark = mark.flipv(); self.modified = true; },
-            Orientation::Rotate90F  => { *img = img.fliph().rotate90(); *mark = mark.fliph().rotate90(); self.modified = true; },
-            Orientation::Rotate270F => { *img = img.flipv().rotate90(); *mark = mark.flipv().rotate90(); self.modified = true; },
-            _ => {}
+        // a kép saját (EXIF) állása, utána a felhasználó forgatása; csak ez utóbbi módosítás
+        *img = self.view_settings().orientation.apply(std::mem::take(img));
+        if self.color_settings.orientation != Orientation::Rotate0 {
+            *mark = self.color_settings.orientation.apply(std::mem::take(mark));
+            self.modified = true;
+        }
+        if self.color_settings.rotation.abs() >= 0.001 {
+            // a finomforgatás CPU-n drága: csak új képnél, képkockánál, állásnál vagy szögnél számoljuk újra
+            let key = (self.current_frame, self.view_settings().orientation, self.color_settings.rotation);
+            match &self.rotated_base {
+                Some((rotated_for, rotated)) if *rotated_for == key => *img = rotated.clone(),
+                _ => {
+                    *img = image::DynamicImage::ImageRgba8(rotate_fine(&img.to_rgba8(), self.color_settings.rotation));
+                    self.rotated_base = Some((key, img.clone()));
+                }
+            }
+            *mark = image::DynamicImage::ImageRgba8(rotate_fine(&mark.to_rgba8(), self.color_settings.rotation));
+            self.modified = true;
+        }
+        if self.color_settings.orientation != self.crop_orientation {
+            self.color_settings.crop = None; // a kivágás a korábbi állás szerint volt
+            self.crop_orientation = self.color_settings.orientation;
         }
         if new_rotate && img.width() != img.height() {
             self.want_magnify = -1.0;
@@ -243,6 +365,15 @@ impl ImageViewer {
         let mut rgba_image = img.to_rgba8();
         let (width, height) = rgba_image.dimensions();
         self.image_size = (width, height).into();
+
+        // előtte/utána nézethez a korrekció nélküli (de elforgatott) kép is kell; osztáskor nem számolunk újra
+        let corrected = !self.show_original_only &&
+                (self.color_settings.is_setted() || self.color_settings.is_blured());
+        if self.split_view && corrected {
+            self.before_tiles = TileSet::new(ctx, Arc::new(rgba_image.clone()), "elotte");
+        } else {
+            self.before_tiles = TileSet::default();
+        }
         
         if let Some(interface) = &self.gpu_interface {
             interface.change_colorcorrection(
@@ -254,7 +385,7 @@ impl ImageViewer {
         if self.modified {
             if self.gpu_interface.is_some() {
                 //self.hist = vec![0u32; 1024];
-                self.gpu_interface.as_ref().unwrap().generate_image(rgba_image.as_mut(), width, height, & mut self.hist);
+                self.gpu_interface.as_ref().unwrap().generate_image_tiled(&mut rgba_image, & mut self.hist);
                 //println!("a");
             }
             else if let Some(lut) = &self.lut {
@@ -269,22 +400,25 @@ impl ImageViewer {
             //println!("c");
         }
 
-        self.rgba_image = Some(rgba_image.clone());
-        
-        
-        let pixel_data = rgba_image.into_raw();
-        let color_image = egui::ColorImage::from_rgba_unmultiplied(
-            [width as usize, height as usize],
-            &pixel_data,
-        );
+        // A GPU-ra csak az áttekintő kép kerül, nagyításkor a látható darabok rajzoláskor töltődnek fel
+        let rgba_image = Arc::new(rgba_image);
+        self.tiles = TileSet::new(ctx, rgba_image.clone(), "kep");
 
-        self.texture = Some(ctx.load_texture("kep", color_image, Default::default()));
+        self.rgba_image = Some(rgba_image);
         
         let size = [mark.width() as _, mark.height() as _];
         self.check_mark_texture = Some(ctx.load_texture(
             "check-mark",
             egui::ColorImage::from_rgba_unmultiplied(size, &mark.to_rgba8()),
             Default::default() ));
+
+        self.review_compare(ctx);
+    }
+
+    /// Előtte/utána osztott nézet be/ki
+    pub fn toggle_split_view(&mut self, ctx: &egui::Context) {
+        self.split_view = !self.split_view;
+        self.review(ctx, true, false);
     }
 
     pub fn pick_color(&self, pixel_x : u32,pixel_y: u32) -> Option<egui::Color32> {
@@ -298,14 +432,7 @@ impl ImageViewer {
     }
 
     pub fn calculate_histogram_only(&mut self, img: &image::RgbaImage) {
-        self.hist = vec![0u32; 1024];
-        img.pixels().for_each(|p| {
-            self.hist[p[0] as usize ] += 1;
-            self.hist[p[1] as usize + 256] += 1;
-            self.hist[p[2] as usize + 512] += 1;
-            let gray = (p[0] as f32 * 0.299 + p[1] as f32 * 0.587 + p[2] as f32 * 0.114) as usize;
-            self.hist[gray + 768] += 1;
-        });
+        self.hist = rgba_histogram(img);
     }
 
 
@@ -318,8 +445,16 @@ impl ImageViewer {
         } else {
             (self.actual_index + self.list_of_images.len() - 1) % self.list_of_images.len()
         };
-        self.actual_index = uj_index;
-        self.open_image(ctx, &self.list_of_images[uj_index].path(), false);
+        self.open_list_index(ctx, uj_index);
+    }
+
+    /// A lista adott elemének megnyitása (lapozás, bélyegkép kattintás)
+    pub fn open_list_index(&mut self, ctx: &egui::Context, index: usize) {
+        if index >= self.list_of_images.len() {
+            return;
+        }
+        self.actual_index = index;
+        self.open_image(ctx, &self.list_of_images[index].path(), false);
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,1058 @@
+/*
+iview/src/jpeg_lossless.rs
+
+Lossless JPEG transforms, jpegtran style: the DCT coefficients are decoded
+(baseline, extended or progressive Huffman JPEG), rotated, flipped and cropped
+on the block grid, then Huffman coded again with optimized tables. The pixels
+are never decoded, so nothing is recompressed.
+
+*/
+
+use crate::codec::read_exif;
+use crate::colors::{CropRect, Orientation};
+use crate::t;
+
+/// Cikcakk sorrend: a k. együttható helye a 8x8-as blokkban (sor * 8 + oszlop)
+const ZIGZAG: [usize; 64] = [
+     0,  1,  8, 16,  9,  2,  3, 10,
+    17, 24, 32, 25, 18, 11,  4,  5,
+    12, 19, 26, 33, 40, 48, 41, 34,
+    27, 20, 13,  6,  7, 14, 21, 28,
+    35, 42, 49, 56, 57, 50, 43, 36,
+    29, 22, 15, 23, 30, 37, 44, 51,
+    58, 59, 52, 45, 38, 31, 39, 46,
+    53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+type Block = [i16; 64];
+
+#[derive(Clone)]
+struct Component {
+    id: u8,
+    h: usize,
+    v: usize,
+    tq: usize,
+    blocks_w: usize, // a lefoglalt blokkok (MCU-ra kerekítve)
+    blocks_h: usize,
+    coefs: Vec<Block>,
+}
+
+impl Component {
+    fn block(&self, x: usize, y: usize) -> Option<&Block> {
+        if x < self.blocks_w && y < self.blocks_h { self.coefs.get(y * self.blocks_w + x) } else { None }
+    }
+}
+
+struct Frame {
+    width: usize,
+    height: usize,
+    precision: u8,
+    progressive: bool,
+    components: Vec<Component>,
+    hmax: usize,
+    vmax: usize,
+}
+
+impl Frame {
+    fn mcus(&self) -> (usize, usize) {
+        (self.width.div_ceil(8 * self.hmax), self.height.div_ceil(8 * self.vmax))
+    }
+
+    /// A komponens valódi blokkjai (a nem átlapolt scan ennyit kódol)
+    fn component_blocks(&self, c: &Component) -> (usize, usize) {
+        ((self.width * c.h).div_ceil(self.hmax).div_ceil(8), (self.height * c.v).div_ceil(self.vmax).div_ceil(8))
+    }
+}
+
+#[derive(Clone)]
+struct QuantTable {
+    precision: u8,      // 0: 8 bit, 1: 16 bit
+    values: [u16; 64],  // természetes sorrendben
+}
+
+impl Default for QuantTable {
+    fn default() -> Self {
+        QuantTable { precision: 0, values: [1; 64] }
+    }
+}
+
+/// Huffman tábla dekódoláshoz (kanonikus kódok, bitenként)
+#[derive(Clone, Default)]
+struct Decoder {
+    maxcode: [i32; 18],
+    valptr: [i32; 17],
+    mincode: [i32; 17],
+    values: Vec<u8>,
+}
+
+impl Decoder {
+    fn new(counts: &[u8; 16], values: Vec<u8>) -> Self {
+        let mut table = Decoder { values, ..Default::default() };
+        let mut code = 0i32;
+        let mut k = 0i32;
+        for l in 1..=16 {
+            let n = counts[l - 1] as i32;
+            table.valptr[l] = k;
+            table.mincode[l] = code;
+            code += n;
+            k += n;
+            table.maxcode[l] = if n > 0 { code - 1 } else { -1 };
+            code <<= 1;
+        }
+        table.maxcode[17] = i32::MAX;
+        table
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    acc: u32,
+    bits: u32,
+    marker: bool, // markerhez értünk, innen nullák jönnek
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader { data, pos: 0, acc: 0, bits: 0, marker: false }
+    }
+
+    fn fill(&mut self) {
+        while self.bits <= 24 {
+            let mut byte = 0u32;
+            if !self.marker && self.pos < self.data.len() {
+                let b = self.data[self.pos];
+                if b == 0xFF {
+                    if self.data.get(self.pos + 1) == Some(&0) {
+                        self.pos += 2;
+                        byte = 0xFF;
+                    } else {
+                        self.marker = true;
+                    }
+                } else {
+                    self.pos += 1;
+                    byte = b as u32;
+                }
+            }
+            self.acc |= byte << (24 - self.bits);
+            self.bits += 8;
+        }
+    }
+
+    fn bit(&mut self) -> u32 {
+        if self.bits == 0 {
+            self.fill();
+        }
+        let b = self.acc >> 31;
+        self.acc <<= 1;
+        self.bits -= 1;
+        b
+    }
+
+    fn receive(&mut self, n: u32) -> i32 {
+        let mut v = 0i32;
+        for _ in 0..n {
+            v = (v << 1) | self.bit() as i32;
+        }
+        v
+    }
+
+    fn receive_extend(&mut self, n: u32) -> i32 {
+        if n == 0 {
+            return 0;
+        }
+        let v = self.receive(n);
+        if v < (1 << (n - 1)) { v - (1 << n) + 1 } else { v }
+    }
+
+    fn decode(&mut self, table: &Decoder) -> Result<u8, String> {
+        let mut code = self.bit() as i32;
+        let mut l = 1;
+        while code > table.maxcode[l] {
+            code = (code << 1) | self.bit() as i32;
+            l += 1;
+            if l > 16 {
+                return Err(t!("jpeg-bad-huffman"));
+            }
+        }
+        let index = (table.valptr[l] + code - table.mincode[l]) as usize;
+        table.values.get(index).copied().ok_or_else(|| t!("jpeg-bad-huffman"))
+    }
+
+    /// Újraindítás: a bitek eldobása és az RSTn marker átlépése
+    fn restart(&mut self) {
+        self.acc = 0;
+        self.bits = 0;
+        self.marker = false;
+        while self.pos + 1 < self.data.len() && !(self.data[self.pos] == 0xFF && (0xD0..=0xD7).contains(&self.data[self.pos + 1])) {
+            self.pos += 1;
+        }
+        self.pos = (self.pos + 2).min(self.data.len());
+    }
+}
+
+/// Egy scan paraméterei
+struct Scan {
+    components: Vec<(usize, usize, usize)>, // (komponens index, DC tábla, AC tábla)
+    ss: usize,
+    se: usize,
+    ah: u32,
+    al: u32,
+}
+
+/// A beolvasott JPEG: a keret, a kvantálók és a megtartandó szegmensek (APPn, COM)
+struct Jpeg {
+    frame: Frame,
+    quant: [QuantTable; 4],
+    segments: Vec<(u8, Vec<u8>)>,
+}
+
+fn be16(data: &[u8], pos: usize) -> Result<usize, String> {
+    match data.get(pos..pos + 2) {
+        Some(b) => Ok(((b[0] as usize) << 8) | b[1] as usize),
+        None => Err(t!("jpeg-truncated")),
+    }
+}
+
+/// A scan entrópia kódolt adatainak vége (a következő nem RST marker)
+fn scan_end(data: &[u8], start: usize) -> usize {
+    let mut pos = start;
+    while pos + 1 < data.len() {
+        if data[pos] == 0xFF {
+            let next = data[pos + 1];
+            if next == 0 || (0xD0..=0xD7).contains(&next) || next == 0xFF {
+                pos += if next == 0xFF { 1 } else { 2 };
+                continue;
+            }
+            return pos;
+        }
+        pos += 1;
+    }
+    data.len()
+}
+
+fn parse(data: &[u8]) -> Result<Jpeg, String> {
+    if data.len() < 4 || data[0] != 0xFF || data[1] != 0xD8 {
+        return Err(t!("jpeg-not-jpeg"));
+    }
+    let mut quant: [QuantTable; 4] = Default::default();
+    let mut dc_tables: [Decoder; 4] = Default::default();
+    let mut ac_tables: [Decoder; 4] = Default::default();
+    let mut segments = Vec::new();
+    let mut frame: Option<Frame> = None;
+    let mut restart_interval = 0usize;
+    let mut pos = 2;
+    loop {
+        // a markerek előtt tetszőleges számú 0xFF állhat
+        while pos < data.len() && data[pos] == 0xFF && data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        if pos + 1 >= data.len() || data[pos] != 0xFF {
+            return Err(t!("jpeg-truncated"));
+        }
+        let marker = data[pos + 1];
+        pos += 2;
+        if marker == 0xD9 {
+            break;
+        }
+        if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+            continue;
+        }
+        let length = be16(data, pos)?;
+        if length < 2 || pos + length > data.len() {
+            return Err(t!("jpeg-truncated"));
+        }
+        let body = &data[pos + 2..pos + length];
+        pos += length;
+        match marker {
+            0xC0..=0xC2 => {
+                if body.len() < 6 {
+                    return Err(t!("jpeg-truncated"));
+                }
+                let precision = body[0];
+                let height = be16(body, 1)?;
+                let width = be16(body, 3)?;
+                let count = body[5] as usize;
+                if width == 0 || height == 0 || count == 0 || count > 4 || body.len() < 6 + count * 3 {
+                    return Err(t!("jpeg-unsupported"));
+                }
+                let mut components = Vec::new();
+                for i in 0..count {
+                    let c = &body[6 + i * 3..9 + i * 3];
+                    let (h, v) = ((c[1] >> 4) as usize, (c[1] & 15) as usize);
+                    if !(1..=4).contains(&h) || !(1..=4).contains(&v) || c[2] > 3 {
+                        return Err(t!("jpeg-unsupported"));
+                    }
+                    components.push(Component { id: c[0], h, v, tq: c[2] as usize, blocks_w: 0, blocks_h: 0, coefs: Vec::new() });
+                }
+                let hmax = components.iter().map(|c| c.h).max().unwrap_or(1);
+                let vmax = components.iter().map(|c| c.v).max().unwrap_or(1);
+                let mut f = Frame { width, height, precision, progressive: marker == 0xC2, components, hmax, vmax };
+                let (mx, my) = f.mcus();
+                for c in &mut f.components {
+                    c.blocks_w = mx * c.h;
+                    c.blocks_h = my * c.v;
+                    c.coefs = vec![[0; 64]; c.blocks_w * c.blocks_h];
+                }
+                frame = Some(f);
+            }
+            0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => return Err(t!("jpeg-unsupported")),
+            0xC4 => {
+                let mut p = 0;
+                while p + 17 <= body.len() {
+                    let (class, id) = (body[p] >> 4, (body[p] & 15) as usize);
+                    let mut counts = [0u8; 16];
+                    counts.copy_from_slice(&body[p + 1..p + 17]);
+                    let total: usize = counts.iter().map(|&n| n as usize).sum();
+                    if id > 3 || p + 17 + total > body.len() {
+                        return Err(t!("jpeg-bad-huffman"));
+                    }
+                    let table = Decoder::new(&counts, body[p + 17..p + 17 + total].to_vec());
+                    if class == 0 { dc_tables[id] = table; } else { ac_tables[id] = table; }
+                    p += 17 + total;
+                }
+            }
+            0xDB => {
+                let mut p = 0;
+                while p < body.len() {
+                    let (precision, id) = (body[p] >> 4, (body[p] & 15) as usize);
+                    let size = if precision == 0 { 64 } else { 128 };
+                    if id > 3 || p + 1 + size > body.len() {
+                        return Err(t!("jpeg-truncated"));
+                    }
+                    let mut table = QuantTable { precision, values: [0; 64] };
+                    for k in 0..64 {
+                        table.values[ZIGZAG[k]] = if precision == 0 {
+                            body[p + 1 + k] as u16
+                        } else {
+                            ((body[p + 1 + 2 * k] as u16) << 8) | body[p + 2 + 2 * k] as u16
+                        };
+                    }
+                    quant[id] = table;
+                    p += 1 + size;
+                }
+            }
+            0xDD => restart_interval = be16(body, 0)?,
+            0xDA => {
+                let f = frame.as_mut().ok_or_else(|| t!("jpeg-truncated"))?;
+                let count = *body.first().ok_or_else(|| t!("jpeg-truncated"))? as usize;
+                if count == 0 || body.len() < 4 + count * 2 {
+                    return Err(t!("jpeg-truncated"));
+                }
+                let mut components = Vec::new();
+                for i in 0..count {
+                    let id = body[1 + i * 2];
+                    let tables = body[2 + i * 2];
+                    let index = f.components.iter().position(|c| c.id == id).ok_or_else(|| t!("jpeg-unsupported"))?;
+                    components.push((index, (tables >> 4) as usize & 3, (tables & 15) as usize & 3));
+                }
+                let p = 1 + count * 2;
+                let scan = Scan {
+                    components,
+                    ss: body[p] as usize,
+                    se: (body[p + 1] as usize).min(63),
+                    ah: (body[p + 2] >> 4) as u32,
+                    al: (body[p + 2] & 15) as u32,
+                };
+                let end = scan_end(data, pos);
+                decode_scan(f, &scan, &dc_tables, &ac_tables, restart_interval, &data[pos..end])?;
+                pos = end;
+            }
+            0xE0..=0xEF | 0xFE => segments.push((marker, body.to_vec())),
+            _ => {} // DNL, DHP, EXP és a többi: nincs rá szükség
+        }
+    }
+    let frame = frame.ok_or_else(|| t!("jpeg-truncated"))?;
+    Ok(Jpeg { frame, quant, segments })
+}
+
+fn decode_scan(frame: &mut Frame, scan: &Scan, dc_tables: &[Decoder; 4], ac_tables: &[Decoder; 4],
+               restart_interval: usize, data: &[u8]) -> Result<(), String> {
+    let mut reader = BitReader::new(data);
+    let mut preds = vec![0i32; frame.components.len()];
+    let mut eobrun = 0u32;
+    // a blokkok sorrendje: átlapolt scanben MCU-nként, egy komponensnél a komponens saját rácsán
+    let single = scan.components.len() == 1;
+    let (mx, my) = frame.mcus();
+    let (bw, bh) = frame.component_blocks(&frame.components[scan.components[0].0]);
+    let total = if single { bw * bh } else { mx * my };
+    for unit in 0..total {
+        if restart_interval > 0 && unit > 0 && unit % restart_interval == 0 {
+            reader.restart();
+            preds.iter_mut().for_each(|p| *p = 0);
+            eobrun = 0;
+        }
+        let blocks: Vec<(usize, usize, usize)> = if single {
+            vec![(0, unit % bw, unit / bw)]
+        } else {
+            let (ux, uy) = (unit % mx, unit / mx);
+            let mut list = Vec::new();
+            for (n, &(index, _, _)) in scan.components.iter().enumerate() {
+                let c = &frame.components[index];
+                for by in 0..c.v {
+                    for bx in 0..c.h {
+                        list.push((n, ux * c.h + bx, uy * c.v + by));
+                    }
+                }
+            }
+            list
+        };
+        for (n, x, y) in blocks {
+            let (index, dc, ac) = scan.components[n];
+            let c = &mut frame.components[index];
+            let offset = y * c.blocks_w + x;
+            let block = c.coefs.get_mut(offset).ok_or_else(|| t!("jpeg-truncated"))?;
+            if !frame.progressive {
+                decode_baseline(&mut reader, block, &mut preds[index], &dc_tables[dc], &ac_tables[ac])?;
+            } else if scan.ss == 0 {
+                if scan.ah == 0 {
+                    let s = dc_category(&mut reader, &dc_tables[dc])?;
+                    preds[index] += reader.receive_extend(s);
+                    block[0] = (preds[index] << scan.al) as i16;
+                } else if reader.bit() == 1 {
+                    block[0] |= 1 << scan.al;
+                }
+            } else if scan.ah == 0 {
+                decode_ac_first(&mut reader, block, scan, &ac_tables[ac], &mut eobrun)?;
+            } else {
+                decode_ac_refine(&mut reader, block, scan, &ac_tables[ac], &mut eobrun)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn dc_category(reader: &mut BitReader, dc: &Decoder) -> Result<u32, String> {
+    match reader.decode(dc)? {
+        s @ 0..=16 => Ok(s as u32),
+        _ => Err(t!("jpeg-bad-huffman")),
+    }
+}
+
+fn decode_baseline(reader: &mut BitReader, block: &mut Block, pred: &mut i32, dc: &Decoder, ac: &Decoder) -> Result<(), String> {
+    let s = dc_category(reader, dc)?;
+    *pred += reader.receive_extend(s);
+    block[0] = *pred as i16;
+    let mut k = 1;
+    while k < 64 {
+        let rs = reader.decode(ac)?;
+        let (r, s) = ((rs >> 4) as usize, (rs & 15) as u32);
+        if s == 0 {
+            if r == 15 {
+                k += 16;
+                continue;
+            }
+            break;
+        }
+        k += r;
+        if k > 63 {
+            return Err(t!("jpeg-bad-huffman"));
+        }
+        block[ZIGZAG[k]] = reader.receive_extend(s) as i16;
+        k += 1;
+    }
+    Ok(())
+}
+
+fn decode_ac_first(reader: &mut BitReader, block: &mut Block, scan: &Scan, ac: &Decoder, eobrun: &mut u32) -> Result<(), String> {
+    if *eobrun > 0 {
+        *eobrun -= 1;
+        return Ok(());
+    }
+    let mut k = scan.ss;
+    while k <= scan.se {
+        let rs = reader.decode(ac)?;
+        let (r, s) = ((rs >> 4) as u32, (rs & 15) as u32);
+        if s == 0 {
+            if r < 15 {
+                *eobrun = (1 << r) - 1;
+                if r > 0 {
+                    *eobrun += reader.receive(r) as u32;
+                }
+                break;
+            }
+            k += 16;
+            continue;
+        }
+        k += r as usize;
+        if k > 63 {
+            return Err(t!("jpeg-bad-huffman"));
+        }
+        block[ZIGZAG[k]] = (reader.receive_extend(s) * (1 << scan.al)) as i16;
+        k += 1;
+    }
+    Ok(())
+}
+
+/// Progresszív AC finomítás (a libjpeg decode_mcu_AC_refine szerint)
+fn decode_ac_refine(reader: &mut BitReader, block: &mut Block, scan: &Scan, ac: &Decoder, eobrun: &mut u32) -> Result<(), String> {
+    let p1 = 1i16 << scan.al;
+    let m1 = -1i16 << scan.al;
+    let mut k = scan.ss;
+    if *eobrun == 0 {
+        while k <= scan.se {
+            let rs = reader.decode(ac)?;
+            let mut r = (rs >> 4) as i32;
+            let s = rs & 15;
+            let mut value = 0i16;
+            if s != 0 {
+                value = if reader.bit() == 1 { p1 } else { m1 };
+            } else if r != 15 {
+                *eobrun = 1 << r;
+                if r > 0 {
+                    *eobrun += reader.receive(r as u32) as u32;
+                }
+                break;
+            }
+            // a már nem nulla együtthatók javító bitjei, és r darab nulla átlépése
+            while k <= scan.se {
+                let coef = &mut block[ZIGZAG[k]];
+                if *coef != 0 {
+                    if reader.bit() == 1 && (*coef & p1) == 0 {
+                        *coef += if *coef >= 0 { p1 } else { m1 };
+                    }
+                } else {
+                    r -= 1;
+                    if r < 0 {
+                        break;
+                    }
+                }
+                k += 1;
+            }
+            if value != 0 && k <= scan.se {
+                block[ZIGZAG[k]] = value;
+            }
+            k += 1;
+        }
+    }
+    if *eobrun > 0 {
+        while k <= scan.se {
+            let coef = &mut block[ZIGZAG[k]];
+            if *coef != 0 && reader.bit() == 1 && (*coef & p1) == 0 {
+                *coef += if *coef >= 0 { p1 } else { m1 };
+            }
+            k += 1;
+        }
+        *eobrun -= 1;
+    }
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Átalakítás
+
+/// Az orientáció a forrás tengelyein: (tengelycsere, x megfordul, y megfordul)
+fn axes(orientation: Orientation) -> (bool, bool, bool) {
+    match orientation {
+        Orientation::Rotate0    => (false, false, false),
+        Orientation::Rotate0F   => (false, true, false),
+        Orientation::Rotate180F => (false, false, true),
+        Orientation::Rotate180  => (false, true, true),
+        Orientation::Rotate270F => (true, false, false), // transzponálás
+        Orientation::Rotate90   => (true, false, true),
+        Orientation::Rotate270  => (true, true, false),
+        Orientation::Rotate90F  => (true, true, true),   // transzverz
+    }
+}
+
+/// Veszteségmentesen elérhető kivágás pixelben (x, y, szélesség, magasság) az elforgatott képen,
+/// MCU határra igazítva; a megfordított tengely töredék MCU-ja elvész (mint jpegtran -trim)
+fn source_region(frame: &Frame, orientation: Orientation, crop: Option<CropRect>) -> Result<(usize, usize, usize, usize), String> {
+    let (swap, rev_x, rev_y) = axes(orientation);
+    let (w, h) = (frame.width, frame.height);
+    let (mcu_w, mcu_h) = if frame.components.len() == 1 { (8, 8) } else { (8 * frame.hmax, 8 * frame.vmax) };
+    // a megforduló tengelyen csak egész MCU-k maradhatnak
+    let trim_w = if rev_x { w / mcu_w * mcu_w } else { w };
+    let trim_h = if rev_y { h / mcu_h * mcu_h } else { h };
+    if trim_w == 0 || trim_h == 0 {
+        return Err(t!("jpeg-too-small"));
+    }
+    // a kivágás az elforgatott teljes képen adott, a forrás koordinátáira visszük
+    let (dest_w, dest_h) = if swap { (h, w) } else { (w, h) };
+    let (cx, cy, cw, ch) = match crop {
+        Some(crop) if !crop.is_full() => {
+            let (x, y, cw, ch) = crop.pixels(dest_w as u32, dest_h as u32);
+            (x as usize, y as usize, cw as usize, ch as usize)
+        }
+        _ => (0, 0, dest_w, dest_h),
+    };
+    let (ax, aw, bx, bw) = if swap { (cy, ch, cx, cw) } else { (cx, cw, cy, ch) };
+    let (mut x0, mut x1) = if rev_x { (w - (ax + aw), w - ax) } else { (ax, ax + aw) };
+    let (mut y0, mut y1) = if rev_y { (h - (bx + bw), h - bx) } else { (bx, bx + bw) };
+    x1 = x1.min(trim_w);
+    y1 = y1.min(trim_h);
+    x0 = x0 / mcu_w * mcu_w;
+    y0 = y0 / mcu_h * mcu_h;
+    if rev_x {
+        x1 = x1.div_ceil(mcu_w) * mcu_w;
+    }
+    if rev_y {
+        y1 = y1.div_ceil(mcu_h) * mcu_h;
+    }
+    if x1 <= x0 || y1 <= y0 {
+        return Err(t!("jpeg-too-small"));
+    }
+    Ok((x0, y0, x1 - x0, y1 - y0))
+}
+
+/// A blokk együtthatói az orientáció szerint
+fn transform_block(block: &Block, swap: bool, flip_u: bool, flip_v: bool) -> Block {
+    let mut out = [0i16; 64];
+    for v in 0..8 {
+        for u in 0..8 {
+            let value = if swap { block[u * 8 + v] } else { block[v * 8 + u] };
+            let negate = (flip_u && u % 2 == 1) != (flip_v && v % 2 == 1);
+            out[v * 8 + u] = if negate { -value } else { value };
+        }
+    }
+    out
+}
+
+fn transform(jpeg: &mut Jpeg, orientation: Orientation, crop: Option<CropRect>) -> Result<(), String> {
+    let (swap, rev_x, rev_y) = axes(orientation);
+    let (flip_u, flip_v) = if swap { (rev_y, rev_x) } else { (rev_x, rev_y) };
+    let (x0, y0, w, h) = source_region(&jpeg.frame, orientation, crop)?;
+    let source = &jpeg.frame;
+    let single = source.components.len() == 1;
+    let mut frame = Frame {
+        width: if swap { h } else { w },
+        height: if swap { w } else { h },
+        precision: source.precision,
+        progressive: false,
+        components: Vec::new(),
+        hmax: if single { 1 } else if swap { source.vmax } else { source.hmax },
+        vmax: if single { 1 } else if swap { source.hmax } else { source.vmax },
+    };
+    for c in &source.components {
+        let (ch, cv) = if single { (1, 1) } else if swap { (c.v, c.h) } else { (c.h, c.v) };
+        frame.components.push(Component { id: c.id, h: ch, v: cv, tq: c.tq, blocks_w: 0, blocks_h: 0, coefs: Vec::new() });
+    }
+    let (mx, my) = frame.mcus();
+    for (n, c) in source.components.iter().enumerate() {
+        // a forrás kivágás ebben a komponensben, blokkokban
+        let (sh, sv) = if single { (1, 1) } else { (c.h, c.v) };
+        let (shmax, svmax) = if single { (1, 1) } else { (source.hmax, source.vmax) };
+        let offset_x = x0 * sh / shmax / 8;
+        let offset_y = y0 * sv / svmax / 8;
+        let bw = (w * sh).div_ceil(shmax).div_ceil(8);
+        let bh = (h * sv).div_ceil(svmax).div_ceil(8);
+        let dest = &mut frame.components[n];
+        dest.blocks_w = mx * dest.h;
+        dest.blocks_h = my * dest.v;
+        dest.coefs = vec![[0; 64]; dest.blocks_w * dest.blocks_h];
+        let (dw, dh) = if swap { (bh, bw) } else { (bw, bh) };
+        for dy in 0..dh.min(dest.blocks_h) {
+            for dx in 0..dw.min(dest.blocks_w) {
+                let (a, b) = if swap { (dy, dx) } else { (dx, dy) };
+                let sx = if rev_x { bw - 1 - a } else { a };
+                let sy = if rev_y { bh - 1 - b } else { b };
+                if let Some(block) = c.block(offset_x + sx, offset_y + sy) {
+                    dest.coefs[dy * dest.blocks_w + dx] = transform_block(block, swap, flip_u, flip_v);
+                }
+            }
+        }
+    }
+    if swap {
+        for table in &mut jpeg.quant {
+            let old = table.values;
+            for v in 0..8 {
+                for u in 0..8 {
+                    table.values[v * 8 + u] = old[u * 8 + v];
+                }
+            }
+        }
+    }
+    jpeg.frame = frame;
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Kódolás: baseline, egy scan, optimalizált Huffman táblák
+
+/// Huffman tábla: a hosszak szerinti darabszám és a szimbólumok, plusz a kódok
+struct Encoder {
+    counts: [u8; 16],
+    values: Vec<u8>,
+    codes: [(u16, u8); 256], // (kód, hossz)
+}
+
+impl Encoder {
+    /// Optimális tábla a gyakoriságokból, legfeljebb 16 bites kódokkal (libjpeg jpeg_gen_optimal_table)
+    fn optimal(freq: &[u64; 256]) -> Self {
+        let mut freq: Vec<u64> = freq.iter().copied().chain(std::iter::once(1)).collect(); // 256: foglalt, így nincs csupa 1 kód
+        if freq[..256].iter().all(|&f| f == 0) {
+            freq[0] = 1;
+        }
+        let mut codesize = [0usize; 257];
+        let mut others = [-1i32; 257];
+        loop {
+            let mut c1 = -1i32;
+            let mut v = u64::MAX;
+            for (i, &f) in freq.iter().enumerate() {
+                if f > 0 && f <= v {
+                    v = f;
+                    c1 = i as i32;
+                }
+            }
+            let mut c2 = -1i32;
+            v = u64::MAX;
+            for (i, &f) in freq.iter().enumerate() {
+                if f > 0 && f <= v && i as i32 != c1 {
+                    v = f;
+                    c2 = i as i32;
+                }
+            }
+            if c2 < 0 {
+                break;
+            }
+            let (mut a, mut b) = (c1 as usize, c2 as usize);
+            freq[a] += freq[b];
+            freq[b] = 0;
+            codesize[a] += 1;
+            while others[a] >= 0 {
+                a = others[a] as usize;
+                codesize[a] += 1;
+            }
+            others[a] = c2;
+            codesize[b] += 1;
+            while others[b] >= 0 {
+                b = others[b] as usize;
+                codesize[b] += 1;
+            }
+        }
+        let mut bits = [0i32; 33];
+        for &size in codesize.iter().filter(|&&s| s > 0) {
+            bits[size.min(32)] += 1;
+        }
+        // 16 bitnél hosszabb kódok nem lehetnek
+        for i in (17..=32).rev() {
+            while bits[i] > 0 {
+                let mut j = i - 2;
+                while bits[j] == 0 {
+                    j -= 1;
+                }
+                bits[i] -= 2;
+                bits[i - 1] += 1;
+                bits[j + 1] += 2;
+                bits[j] -= 1;
+            }
+        }
+        // a foglalt kód eltávolítása
+        let mut i = 16;
+        while i > 0 && bits[i] == 0 {
+            i -= 1;
+        }
+        bits[i] -= 1;
+        let mut values = Vec::new();
+        for size in 1..=32 {
+            for (symbol, &s) in codesize.iter().enumerate().take(256) {
+                if s == size {
+                    values.push(symbol as u8);
+                }
+            }
+        }
+        let mut counts = [0u8; 16];
+        for (l, count) in counts.iter_mut().enumerate() {
+            *count = bits[l + 1] as u8;
+        }
+        values.truncate(counts.iter().map(|&n| n as usize).sum());
+        let mut codes = [(0u16, 0u8); 256];
+        let mut code = 0u16;
+        let mut k = 0;
+        for (l, &count) in counts.iter().enumerate() {
+            for _ in 0..count {
+                codes[values[k] as usize] = (code, l as u8 + 1);
+                code = code.wrapping_add(1);
+                k += 1;
+            }
+            code <<= 1;
+        }
+        Encoder { counts, values, codes }
+    }
+}
+
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn put(&mut self, code: u32, size: u32) {
+        if size == 0 {
+            return;
+        }
+        self.acc = (self.acc << size) | (code & ((1 << size) - 1));
+        self.bits += size;
+        while self.bits >= 8 {
+            let byte = (self.acc >> (self.bits - 8)) as u8;
+            self.out.push(byte);
+            if byte == 0xFF {
+                self.out.push(0);
+            }
+            self.bits -= 8;
+        }
+        self.acc &= (1 << self.bits) - 1;
+    }
+
+    fn flush(&mut self) {
+        if self.bits > 0 {
+            let pad = 8 - self.bits;
+            self.put((1 << pad) - 1, pad);
+        }
+    }
+}
+
+/// Az érték kategóriája (bitszáma) és a kiírandó bitjei
+fn category(value: i32) -> (u32, u32) {
+    let size = 32 - value.unsigned_abs().leading_zeros();
+    let bits = if value < 0 { (value - 1) as u32 } else { value as u32 };
+    (size, bits & ((1u32 << size) - 1))
+}
+
+/// Egy blokk kódolása; `emit` kapja a (tábla, szimbólum, extra bitek, bitszám) négyeseket
+fn encode_block(block: &Block, pred: &mut i32, mut emit: impl FnMut(bool, u8, u32, u32)) {
+    let (size, bits) = category(block[0] as i32 - *pred);
+    *pred = block[0] as i32;
+    emit(false, size as u8, bits, size);
+    let mut run = 0;
+    for k in 1..64 {
+        let value = block[ZIGZAG[k]] as i32;
+        if value == 0 {
+            run += 1;
+            continue;
+        }
+        while run > 15 {
+            emit(true, 0xF0, 0, 0);
+            run -= 16;
+        }
+        let (size, bits) = category(value);
+        emit(true, ((run << 4) | size) as u8, bits, size);
+        run = 0;
+    }
+    if run > 0 {
+        emit(true, 0x00, 0, 0);
+    }
+}
+
+/// A blokkok kódolási sorrendje: (komponens, blokk x, blokk y)
+fn block_order(frame: &Frame) -> Vec<(usize, usize, usize)> {
+    let mut order = Vec::new();
+    if frame.components.len() == 1 {
+        let (bw, bh) = frame.component_blocks(&frame.components[0]);
+        for y in 0..bh {
+            for x in 0..bw {
+                order.push((0, x, y));
+            }
+        }
+        return order;
+    }
+    let (mx, my) = frame.mcus();
+    for uy in 0..my {
+        for ux in 0..mx {
+            for (n, c) in frame.components.iter().enumerate() {
+                for by in 0..c.v {
+                    for bx in 0..c.h {
+                        order.push((n, ux * c.h + bx, uy * c.v + by));
+                    }
+                }
+            }
+        }
+    }
+    order
+}
+
+fn segment(out: &mut Vec<u8>, marker: u8, body: &[u8]) {
+    out.extend_from_slice(&[0xFF, marker]);
+    out.extend_from_slice(&((body.len() + 2) as u16).to_be_bytes());
+    out.extend_from_slice(body);
+}
+
+fn encode(jpeg: &Jpeg) -> Vec<u8> {
+    let frame = &jpeg.frame;
+    let order = block_order(frame);
+    // a fényesség a 0. táblát, a színek az 1.-et használják
+    let table_of = |n: usize| if n == 0 { 0 } else { 1 };
+    let tables = if frame.components.len() == 1 { 1 } else { 2 };
+    let mut freq = [[[0u64; 256]; 2]; 2]; // [tábla][DC/AC]
+    let mut preds = vec![0i32; frame.components.len()];
+    for &(n, x, y) in &order {
+        let c = &frame.components[n];
+        encode_block(&c.coefs[y * c.blocks_w + x], &mut preds[n], |ac, symbol, _, _| {
+            freq[table_of(n)][ac as usize][symbol as usize] += 1;
+        });
+    }
+    let encoders: Vec<[Encoder; 2]> = (0..tables)
+        .map(|t| [Encoder::optimal(&freq[t][0]), Encoder::optimal(&freq[t][1])])
+        .collect();
+
+    let mut out = vec![0xFF, 0xD8];
+    for (marker, body) in &jpeg.segments {
+        segment(&mut out, *marker, body);
+    }
+    let mut extended = frame.precision != 8;
+    for tq in 0..4 {
+        if !frame.components.iter().any(|c| c.tq == tq) {
+            continue;
+        }
+        let table = &jpeg.quant[tq];
+        let mut body = vec![(table.precision << 4) | tq as u8];
+        for &pos in &ZIGZAG {
+            let value = table.values[pos];
+            if table.precision == 0 {
+                body.push(value as u8);
+            } else {
+                body.extend_from_slice(&value.to_be_bytes());
+                extended = true;
+            }
+        }
+        segment(&mut out, 0xDB, &body);
+    }
+    let mut sof = vec![frame.precision];
+    sof.extend_from_slice(&(frame.height as u16).to_be_bytes());
+    sof.extend_from_slice(&(frame.width as u16).to_be_bytes());
+    sof.push(frame.components.len() as u8);
+    for c in &frame.components {
+        sof.extend_from_slice(&[c.id, ((c.h << 4) | c.v) as u8, c.tq as u8]);
+    }
+    segment(&mut out, if extended { 0xC1 } else { 0xC0 }, &sof);
+    let mut dht = Vec::new();
+    for (t, pair) in encoders.iter().enumerate() {
+        for (class, encoder) in pair.iter().enumerate() {
+            dht.push(((class as u8) << 4) | t as u8);
+            dht.extend_from_slice(&encoder.counts);
+            dht.extend_from_slice(&encoder.values);
+        }
+    }
+    segment(&mut out, 0xC4, &dht);
+    let mut sos = vec![frame.components.len() as u8];
+    for (n, c) in frame.components.iter().enumerate() {
+        let t = table_of(n) as u8;
+        sos.extend_from_slice(&[c.id, (t << 4) | t]);
+    }
+    sos.extend_from_slice(&[0, 63, 0]);
+    segment(&mut out, 0xDA, &sos);
+
+    let mut writer = BitWriter { out, acc: 0, bits: 0 };
+    preds.iter_mut().for_each(|p| *p = 0);
+    for &(n, x, y) in &order {
+        let c = &frame.components[n];
+        let pair = &encoders[table_of(n)];
+        encode_block(&c.coefs[y * c.blocks_w + x], &mut preds[n], |ac, symbol, bits, size| {
+            let (code, length) = pair[ac as usize].codes[symbol as usize];
+            writer.put(code as u32, length as u32);
+            writer.put(bits, size);
+        });
+    }
+    writer.flush();
+    let mut out = writer.out;
+    out.extend_from_slice(&[0xFF, 0xD9]);
+    out
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// A JPEG forgatása / tükrözése / kivágása újratömörítés nélkül.
+/// `crop` az elforgatott képen értendő, MCU határra bővül. A többi szegmens (EXIF is) változatlan marad.
+/// Visszaadja az új fájlt és a méretét.
+pub fn transform_jpeg(data: &[u8], orientation: Orientation, crop: Option<CropRect>) -> Result<(Vec<u8>, (u32, u32)), String> {
+    let mut jpeg = parse(data)?;
+    transform(&mut jpeg, orientation, crop)?;
+    let size = (jpeg.frame.width as u32, jpeg.frame.height as u32);
+    Ok((encode(&jpeg), size))
+}
+
+/// Az EXIF Orientation tag értéke a JPEG-ben (1, ha nincs)
+pub fn jpeg_exif_orientation(data: &[u8]) -> Orientation {
+    exif_range(data)
+        .and_then(|range| read_exif(&data[range]).ok())
+        .map(|(_, _, orientation)| Orientation::from_exif(orientation as u16))
+        .unwrap_or_default()
+}
+
+/// Az "Exif\0\0" kezdetű APP1 szegmens tartalmának helye
+fn exif_range(data: &[u8]) -> Option<std::ops::Range<usize>> {
+    let mut pos = 2;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        if marker == 0xDA || marker == 0xD9 {
+            break;
+        }
+        let length = be16(data, pos + 2).ok()?;
+        let range = pos + 4..pos + 2 + length;
+        if marker == 0xE1 && data.get(range.clone())?.starts_with(b"Exif\0\0") {
+            return Some(range);
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+/// Veszteségmentes átalakítás, az EXIF-ben az Orientation 1, a méretek és a bélyegkép az új képhez igazítva
+pub fn lossless_jpeg(data: &[u8], orientation: Orientation, crop: Option<CropRect>) -> Result<Vec<u8>, String> {
+    let (output, (width, height)) = transform_jpeg(data, orientation, crop)?;
+    let Some(range) = exif_range(&output) else {
+        return Ok(output);
+    };
+    let Ok((mut exif, _, _)) = read_exif(&output[range]) else {
+        return Ok(output);
+    };
+    exif.patch_orientation(1)?;
+    exif.patch_dimensions(width, height)?;
+    if exif.thumbnail_bytes().is_some() {
+        let img = image::load_from_memory_with_format(&output, image::ImageFormat::Jpeg)
+            .map_err(|e| t!("error-thumbnail", error = e))?;
+        let thumbnail = exif.generate_fitted_thumbnail(&img.to_rgba8());
+        exif.patch_thumbnail(&thumbnail)?;
+    }
+    replace_jpeg_exif(&output, &exif.raw_exif)
+}
+
+/// Az EXIF APP1 szegmens cseréje (vagy beszúrása az SOI és a JFIF után); a többi bájt változatlan
+pub fn replace_jpeg_exif(data: &[u8], exif: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < 4 || data[0..2] != [0xFF, 0xD8] {
+        return Err(t!("jpeg-not-jpeg"));
+    }
+    if exif.len() > crate::exif_writer::MAX_EXIF_SIZE {
+        return Err(t!("exif-too-large", size = exif.len()));
+    }
+    let (start, end) = match exif_range(data) {
+        Some(range) => (range.start - 4, range.end),
+        None if data[2..4] == [0xFF, 0xE0] => {
+            let end = (4 + be16(data, 4)?).min(data.len());
+            (end, end)
+        }
+        None => (2, 2),
+    };
+    let mut output = Vec::with_capacity(data.len() + exif.len() + 4);
+    output.extend_from_slice(&data[..start]);
+    output.extend_from_slice(&[0xFF, 0xE1]);
+    output.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+    output.extend_from_slice(exif);
+    output.extend_from_slice(&data[end..]);
+    Ok(output)
+}
+
+/// Új EXIF blokk írása a JPEG fájlba, a képadat újratömörítése nélkül
+pub fn write_jpeg_exif_file(path: &std::path::Path, exif: &[u8]) -> Result<(), String> {
+    let data = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let output = replace_jpeg_exif(&data, exif)?;
+    write_replacing(path, &output)
+}
+
+/// Ideiglenes fájlba ír, aztán átnevez, így hiba esetén az eredeti megmarad
+fn write_replacing(target: &std::path::Path, data: &[u8]) -> Result<(), String> {
+    let temp = target.with_extension("iview-tmp");
+    std::fs::write(&temp, data).map_err(|e| format!("{}: {}", temp.display(), e))?;
+    std::fs::rename(&temp, target).map_err(|e| format!("{}: {}", target.display(), e))
+}
+
+/// A fájl kiegyenesítése a saját EXIF állása szerint, utána `orientation` és `crop` az egyenes képen;
+/// `target` nélkül helyben (előbb ideiglenes fájlba ír, aztán átnevez)
+pub fn lossless_jpeg_file(path: &std::path::Path, target: Option<&std::path::Path>, orientation: Orientation,
+                          crop: Option<CropRect>) -> Result<(), String> {
+    let data = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let orientation = jpeg_exif_orientation(&data).then(orientation);
+    let output = lossless_jpeg(&data, orientation, crop)?;
+    write_replacing(target.unwrap_or(path), &output)
+}
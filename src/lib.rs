@@ -0,0 +1,27 @@
+/*
+iview/src/lib.rs
+
+IView képkezelő könyvtár egui nélkül:
+ - codec: beolvasás (kép + felbontás + EXIF + animáció), kódolás SaveFormat szerint
+ - colors: ColorSettings, Oklab / HSV színkorrekció, 3D LUT
+ - exif_display: az EXIF értékek olvasható alakban, IFD-nként csoportosítva, JSON export
+ - exif_my: ExifBlock értelmezés és módosítás
+ - exif_writer: az EXIF IFD-k típusos alakban, szerkesztés és újraírás bármelyik bájtsorrendben
+ - geometry: finom forgatás (kiegyenesítés) a legnagyobb belső téglalapra vágva
+ - i18n: üzenetkatalógusok (angol, magyar), t!("kulcs") makró
+ - jpeg_lossless: JPEG forgatás, tükrözés, kivágás újratömörítés nélkül (DCT együtthatókon)
+ - metrics: két kép eltérése (PSNR, SSIM, hibák), különbség kép
+ - thumbnails: bélyegképek, freedesktop szerinti lemez cache-sel
+
+*/
+
+pub mod codec;
+pub mod colors;
+pub mod exif_display;
+pub mod exif_my;
+pub mod exif_writer;
+pub mod geometry;
+pub mod i18n;
+pub mod jpeg_lossless;
+pub mod metrics;
+pub mod thumbnails;
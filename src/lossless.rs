@@ -0,0 +1,195 @@
+/*
+iview/src/lossless.rs
+
+Lossless JPEG actions: the view's orientation (EXIF and user rotation) and
+crop written back into the JPEG without recompression, in place or to a new
+file, and a folder-wide auto-rotate by the EXIF Orientation tag
+
+*/
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use crate::codec::SaveFormat;
+use crate::colors::*;
+use crate::sidecar::*;
+use crate::ImageViewer;
+use iview::jpeg_lossless::*;
+use iview::t;
+
+fn is_jpeg(path: &Path) -> bool {
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    SaveFormat::from_extension(ext) == Some(SaveFormat::Jpeg)
+}
+
+/// A háttérszálon futó mappa kiegyenesítés állása (az ablak ezt mutatja)
+pub struct AutoRotateJob {
+    receiver: mpsc::Receiver<Result<bool, String>>, // fájlonként: el kellett-e forgatni
+    total: usize,
+    done: usize,
+    rotated: usize,
+    errors: Vec<String>,
+}
+
+impl ImageViewer {
+
+    /// Csak JPEG-nél, kiegyenesítés nélkül, állóképnél lehet
+    pub fn can_lossless_jpeg(&self) -> bool {
+        self.image_format == SaveFormat::Jpeg
+            && self.displayed_image.is_some()
+            && self.anim_data.is_none()
+            && self.color_settings.rotation.abs() < 0.001
+    }
+
+    /// Az állás és a kivágás beírása a fájlba; a többi korrekció megmarad a nézetben
+    pub fn lossless_jpeg_in_place(&mut self, ctx: &egui::Context) {
+        if !self.can_lossless_jpeg() {
+            return;
+        }
+        let Some((image, _, _)) = self.displayed_image.clone() else {
+            return;
+        };
+        // a fájl saját EXIF állása után a felhasználó forgatása (a fájl közben kiegyenesedhetett)
+        let orientation = self.color_settings.orientation;
+        let crop = self.color_settings.crop;
+        // az állás és a kivágás a fájlba kerül, a többi korrekció az új tartalom hash-éhez
+        let mut surviving = self.color_settings;
+        surviving.orientation = Orientation::Rotate0;
+        surviving.crop = None;
+        println!("{}", t!("saving", file = image.display()));
+        let mut written = false;
+        let result = rewrite_keeping_sidecar(&image, self.sidecar_mode, Some(surviving), || {
+            lossless_jpeg_file(&image, None, orientation, crop)?;
+            written = true;
+            Ok(())
+        });
+        match result {
+            Ok(path) => self.sidecar = path,
+            Err(e) if !written => {
+                self.show_error(t!("error-lossless", error = e));
+                return;
+            }
+            Err(e) => {
+                // a kép már átíródott, csak a sidecar nem
+                self.sidecar = None;
+                self.show_error(t!("sidecar-save-error", error = e));
+            }
+        }
+        self.color_settings = surviving;
+        self.crop_orientation = Orientation::Rotate0;
+        self.load_image(ctx, true);
+        // a többi korrekció akkor is marad, ha megnyitáskor egyébként alapra állnának
+        if let Some(pending) = &mut self.pending_load {
+            pending.keep_corrections = true;
+        }
+    }
+
+    /// Ugyanez új fájlba; a látható kép nem változik
+    pub fn lossless_jpeg_save_as(&mut self) {
+        if !self.can_lossless_jpeg() {
+            return;
+        }
+        let Some((image, _, _)) = self.displayed_image.clone() else {
+            return;
+        };
+        let stem = image.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let mut dialog = rfd::FileDialog::new()
+            .set_title(t!("title-lossless-save"))
+            .add_filter("Jpeg", &["jpg", "jpeg"])
+            .set_file_name(format!("{}_lossless.jpg", stem));
+        if let Some(parent) = image.parent() {
+            dialog = dialog.set_directory(parent);
+        }
+        let Some(target) = dialog.save_file() else {
+            return;
+        };
+        println!("{}", t!("saving", file = target.display()));
+        match lossless_jpeg_file(&image, Some(&target), self.color_settings.orientation, self.color_settings.crop) {
+            Ok(()) => self.add_to_recent(&target),
+            Err(e) => self.show_error(t!("error-lossless", error = e)),
+        }
+    }
+
+    /// A mappa minden JPEG-je az EXIF állás szerint kiegyenesítve, háttérszálon; az állás ablakban látszik
+    pub fn auto_rotate_folder(&mut self, ctx: &egui::Context) {
+        if self.auto_rotate.as_ref().is_some_and(|job| job.done < job.total) {
+            return; // még fut az előző
+        }
+        let files: Vec<PathBuf> = self.list_of_images.iter()
+            .map(|entry| entry.path())
+            .filter(|path| is_jpeg(path))
+            .collect();
+        let (sender, receiver) = mpsc::channel();
+        self.auto_rotate = Some(AutoRotateJob { receiver, total: files.len(), done: 0, rotated: 0, errors: Vec::new() });
+        let mode = self.sidecar_mode;
+        let repaint = ctx.clone();
+        std::thread::spawn(move || {
+            for path in &files {
+                let result = auto_rotate_file(path, mode)
+                    .map_err(|e| t!("error-lossless-file", file = path.display(), error = e));
+                if sender.send(result).is_err() {
+                    return; // az ablakot bezárták
+                }
+                repaint.request_repaint();
+            }
+        });
+    }
+
+    /// A mappa kiegyenesítés állása, a végén az eredmény és a hibák
+    pub fn auto_rotate_window(&mut self, ctx: &egui::Context) {
+        let Some(job) = &mut self.auto_rotate else {
+            return;
+        };
+        while let Ok(result) = job.receiver.try_recv() {
+            job.done += 1;
+            match result {
+                Ok(true) => job.rotated += 1,
+                Ok(false) => {}
+                Err(e) => job.errors.push(e),
+            }
+        }
+        let finished = job.done >= job.total;
+        let mut close = false;
+        egui::Window::new(t!("title-auto-rotate"))
+            .collapsible(false)
+            .resizable(false)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.viewport_rect().center())
+            .show(ctx, |ui| {
+                if finished {
+                    ui.label(t!("lossless-rotated-count", count = job.rotated, total = job.total));
+                } else {
+                    ui.add(egui::ProgressBar::new(job.done as f32 / job.total as f32)
+                        .desired_width(300.0)
+                        .text(t!("lossless-rotating", done = job.done, total = job.total)));
+                }
+                if !job.errors.is_empty() {
+                    ui.add_space(6.0);
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for error in &job.errors {
+                            ui.label(egui::RichText::new(error.as_str()).color(egui::Color32::RED));
+                        }
+                    });
+                }
+                ui.add_space(6.0);
+                let button = if finished { t!("error-ok") } else { t!("save-cancel") };
+                if ui.button(button).clicked() {
+                    close = true;
+                }
+            });
+        if close {
+            self.auto_rotate = None; // futás közben a szál a következő fájlnál megáll
+        }
+    }
+}
+
+/// Igaz, ha át kellett írni; a kép sidecarja az új tartalomhoz kerül
+fn auto_rotate_file(path: &Path, mode: SidecarMode) -> Result<bool, String> {
+    let data = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if jpeg_exif_orientation(&data) == Orientation::Rotate0 {
+        return Ok(false);
+    }
+    // a felhasználói forgatás az egyenes képhez képest van megadva, így érvényes marad
+    rewrite_keeping_sidecar(path, mode, None, || lossless_jpeg_file(path, None, Orientation::Rotate0, None))?;
+    Ok(true)
+}
@@ -10,7 +10,6 @@ Created by Ferenc Takács in 2026
 
 //mod exif;
 mod gpu_colors;
-mod colors;
 mod file_handlers;
 mod ui_elements;
 mod ui_shortcuts;
@@ -18,11 +17,36 @@ mod ui_menu;
 mod ui_draw;
 mod ui_dialogs;
 mod image_processing;
-mod exif_my;
+mod image_cache;
+mod ui_browser;
+mod compare;
+mod slideshow;
+mod crop;
+mod resize;
+mod history;
+mod presets;
+mod sidecar;
+mod lossless;
+mod exif_editor;
+mod bindings;
 mod pf32;
+mod cli;
+use iview::{codec, colors, exif_my, geometry, metrics, thumbnails};
+use iview::t;
 use colors::*;
 use crate::image_processing::*;
+use crate::image_cache::*;
+use crate::ui_browser::*;
+use crate::compare::*;
+use crate::slideshow::*;
+use crate::crop::*;
+use crate::resize::*;
+use crate::history::*;
+use crate::presets::*;
+use crate::sidecar::*;
+use crate::bindings::*;
 use crate::file_handlers::*;
+use crate::lossless::AutoRotateJob;
 use crate::exif_my::*;
 use crate::pf32::Pf32;
 use eframe::egui;
@@ -33,20 +57,32 @@ use pollster;
 
 fn main() -> eframe::Result<()> {
     
+    load_language(); // az indulási üzenetek már a beállított nyelven
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "convert" {
+        // ablak nélküli kötegelt konvertálás
+        #[cfg(windows)]
+        cli::attach_console();
+        std::process::exit(cli::run_convert(&args[2..]));
+    }
+    // --slideshow: a megadott kép (vagy könyvtár) mappájának vetítése
+    let slideshow = args.iter().any(|a| a == "--slideshow");
+    let args: Vec<String> = args.into_iter().filter(|a| a != "--slideshow").collect();
+
     let has_wgpu = pollster::block_on(check_wgpu_support());
     
     let renderer = if has_wgpu {
-        println!("WGPU támogatott, Shader mód bekapcsolva.");
+        println!("{}", t!("start-wgpu"));
         eframe::Renderer::Wgpu
     } else {
-        println!("WGPU nem elérhető. Váltás GLOW (OpenGL) módra - CPU fallback.");
+        println!("{}", t!("start-glow"));
         eframe::Renderer::Glow
     };
     
-    let args: Vec<String> = env::args().collect();
     let (start_image, clipboard) = if args.len() > 1 {
-        // Ha van argumentum, azt útvonalként kezeljük
-        (Some(PathBuf::from(&args[1])), false)
+        // Ha van argumentum, azt útvonalként kezeljük; könyvtárnál az első képe
+        let path = PathBuf::from(&args[1]);
+        (if path.is_dir() { first_image_in(&path) } else { Some(path) }, false)
     } else {
         // 2. Ha nincs, megnézzük a vágólapot (Ctrl+C-vel másolt kép)
         (save_clipboard_image(), true)
@@ -69,10 +105,12 @@ fn main() -> eframe::Result<()> {
             let mut app = ImageViewer::default();
             
             let image_data = include_bytes!("assets/check-mark.png");
-            app.check_mark_img = Some(image::load_from_memory(image_data).expect("Failed to load icon"));
+            app.check_mark_img = Some(image::load_from_memory(image_data).unwrap_or_else(|e| panic!("{}: {}", t!("start-icon-error"), e)));
 
             app.load_settings();
             
+            app.apply_window_mode(&cc.egui_ctx);
+
             app.has_gpu = has_wgpu;
             if !has_wgpu { app.use_gpu = false; }
             
@@ -82,6 +120,9 @@ fn main() -> eframe::Result<()> {
                     app.make_image_list()
                 }
                 app.open_image(&cc.egui_ctx, &path, !clipboard);
+                if slideshow {
+                    app.start_slideshow();
+                }
             } else {
                 app.open_image_dialog(&cc.egui_ctx, &None);
             }
@@ -104,11 +145,11 @@ async fn check_wgpu_support() -> bool {
         let info = a.get_info();
         let name = info.name.to_lowercase();
         if name.contains("mesa") || name.contains("svga3d") || name.contains("llvmpipe") {
-            println!("Virtualizált GPU detektálva ({}), biztonsági okokból Glow módra váltunk.", info.name);
+            println!("{}", t!("start-virtual-gpu", name = info.name));
             return false; 
         }
         let limits = a.limits();
-        println!("GPU találva: {}, Max Texture: {}", a.get_info().name, limits.max_texture_dimension_2d);
+        println!("{}", t!("start-gpu-found", name = a.get_info().name, size = limits.max_texture_dimension_2d));
         true
     } else {
         false
@@ -124,24 +165,31 @@ struct ImageViewer {
     pub image_folder: Option<PathBuf>,     // a képek könyvtára
     pub list_of_images: Vec<fs::DirEntry>, // kép nevek listája a könyvtárban
     pub actual_index: usize,               // a kép indexe a listában
+    pub image_cache: ImageCache,           // előtöltött szomszéd képek
+    pub pending_load: Option<PendingLoad>, // háttérben töltődő kép
+    pub auto_rotate: Option<AutoRotateJob>, // a mappa kiegyenesítése (ablak)
+    pub displayed_image: Option<(PathBuf, SaveFormat, usize)>, // a látható kép útvonala, formátuma, indexe
     pub magnify: f32,
     pub change_magnify: f32,
     pub want_magnify: f32,
     pub mouse_zoom: bool,
-    pub resize: f32,
-    pub texture: Option<egui::TextureHandle>,
+    pub tiles: TileSet,                 // a megjelenített kép a GPU-n
     pub original_image: Option<image::DynamicImage>,
-    pub resized_image: Option<image::DynamicImage>,
-    pub rgba_image: Option<image::ImageBuffer<image::Rgba<u8>, Vec<u8>>>,
-    pub image_size: Pf32, // beolvasott, és átméretezett kép mérete pixelben
+    pub rgba_image: Option<std::sync::Arc<image::RgbaImage>>,
+    pub rotated_base: Option<((usize, Orientation, f32), image::DynamicImage)>, // finomforgatott kép (képkocka, állás, szög)
+    pub image_size: Pf32, // a megjelenített (elforgatott) kép mérete pixelben
     pub inner_size: Pf32,
     pub original_image_size: Pf32,  // beolvasott kép mérete pixelben
     pub center: bool,           // igaz, ha középe tesszük az ablakot, egyébként a bal felső sarokba
     pub set_pos: bool,
+    pub window_mode: WindowMode,
+    pub view_fit: ViewFit,
+    pub fullscreen: bool,       // keret nélküli teljes képernyő, a menü a felső szélen jelenik meg
     pub aktualis_offset: Pf32,    // megjelenítés kezdőpozíció a nagyított képen
     pub sort: SortDir,
     pub color_settings: ColorSettings,
     pub lut: Option<Lut4ColorSettings>,
+    pub look: Option<CubeLut>,
     pub refit_reopen: bool,
     pub fit_open: bool,
     pub same_correction_open: bool,
@@ -151,6 +199,9 @@ struct ImageViewer {
     pub resolution: Option<Resolution>,
     pub recent_file_modified: bool,
     pub show_exif_details: bool,
+    pub exif_search: String, // szűrő az EXIF részletekhez
+    pub exif_editor: Option<exif_editor::ExifEditor>, // nyitott EXIF szerkesztő
+    pub exif_error: Option<String>, // miért nem olvasható a kép EXIF blokkja
     pub is_animated: bool,    // Ez a fájl animálható-e?
     pub anim_playing: bool,   // Fut-e most az animáció?
     pub anim_loop: bool,      // Ismétlődjön-e (default: true)?
@@ -181,6 +232,33 @@ struct ImageViewer {
     pub hist_texture: Option<egui::TextureHandle>,
     pub check_mark_img: Option<image::DynamicImage>,
     pub check_mark_texture: Option<egui::TextureHandle>,
+    pub show_grid: bool,                // bélyegkép rács a kép helyett
+    pub show_filmstrip: bool,           // bélyegkép sáv a kép alatt
+    pub filmstrip_index: Option<usize>, // ehhez a képhez görgettük utoljára a sávot
+    pub thumbnails: ThumbnailStore,
+    pub compare: Option<Compare>,       // összehasonlítás egy második képpel
+    pub split_view: bool,               // előtte/utána osztott nézet
+    pub split_vertical: bool,           // függőleges elválasztó (bal: előtte, jobb: utána)
+    pub split_pos: f32,                 // az elválasztó helye a látható terület arányában
+    pub before_tiles: TileSet,          // a korrekció nélküli kép
+    pub slideshow: Option<Slideshow>,   // futó diavetítés
+    pub slideshow_settings: SlideshowSettings,
+    pub crop_tool: bool,                // a kivágó eszköz aktív
+    pub horizon_tool: bool,             // vízszint rajzolása a kiegyenesítéshez
+    pub crop_options: CropOptions,
+    pub crop_orientation: Orientation,  // ebben az állásban adták meg a kivágást
+    pub exif_orientation: Orientation,  // a kép EXIF szerinti állása, a felhasználói forgatás előtt
+    pub output_size: Option<OutputSize>, // mentéskori pontos méret a nagyítás helyett
+    pub resize_dialog: Option<ResizeDialog>,
+    pub history: History,               // visszavonható szerkesztések
+    pub show_history: bool,
+    pub presets: Vec<ColorPreset>,      // elnevezett színkorrekciók
+    pub preset_name: Option<String>,    // a mentendő preset neve (nyitott ablak)
+    pub error_message: Option<String>,  // felugró ablakban látszó hiba(k)
+    pub sidecar_mode: SidecarMode,      // hova kerül a képenkénti szerkesztés
+    pub sidecar: Option<PathBuf>,       // a látható kép sidecarja, ha van
+    pub bindings: KeyBindings,          // billentyű és egér hozzárendelések
+    pub bindings_dialog: Option<BindingsDialog>,
 }
 
 
@@ -195,24 +273,31 @@ impl Default for ImageViewer {
             image_folder: None,
             list_of_images: Vec::new(),
             actual_index: 0,
+            image_cache: ImageCache::default(),
+            pending_load: None,
+            auto_rotate: None,
+            displayed_image: None,
             magnify: 1.0,
             change_magnify: 0.0,
             want_magnify: 0.0,
             mouse_zoom: false,
-            resize: 1.0,
-            texture: None,
+            tiles: TileSet::default(),
             original_image: None,
-            resized_image: None,
             rgba_image: None,
+            rotated_base: None,
             image_size: (800.0, 600.0).into(),
             inner_size: (800.0, 600.0).into(),
             original_image_size: (800.0, 600.0).into(),
             center: false,
             set_pos: true,
+            window_mode: WindowMode::FollowImage,
+            view_fit: ViewFit::Fit,
+            fullscreen: false,
             aktualis_offset: (0.0, 0.0).into(),
             sort: SortDir::Name,
             color_settings: ColorSettings::default(),
             lut: None,
+            look: None,
             refit_reopen: false,
             fit_open: true,
             same_correction_open: false,
@@ -222,6 +307,9 @@ impl Default for ImageViewer {
             resolution: None,
             recent_file_modified: false,
             show_exif_details: false,
+            exif_search: String::new(),
+            exif_editor: None,
+            exif_error: None,
             is_animated: false,  // Ez a fájl animálható-e?
             anim_playing: false, // Fut-e most az animáció?
             anim_loop: true,     // Ismétlődjön-e (default: true)?
@@ -252,6 +340,33 @@ impl Default for ImageViewer {
             hist_texture: None,
             check_mark_img: None, 
             check_mark_texture: None,
+            show_grid: false,
+            show_filmstrip: false,
+            filmstrip_index: None,
+            thumbnails: ThumbnailStore::default(),
+            compare: None,
+            split_view: false,
+            split_vertical: true,
+            split_pos: 0.5,
+            before_tiles: TileSet::default(),
+            slideshow: None,
+            slideshow_settings: SlideshowSettings::default(),
+            crop_tool: false,
+            horizon_tool: false,
+            crop_options: CropOptions::default(),
+            crop_orientation: Orientation::Rotate0,
+            exif_orientation: Orientation::Rotate0,
+            output_size: None,
+            resize_dialog: None,
+            history: History::default(),
+            show_history: false,
+            presets: Vec::new(),
+            preset_name: None,
+            error_message: None,
+            sidecar_mode: SidecarMode::Off,
+            sidecar: None,
+            bindings: KeyBindings::default(),
+            bindings_dialog: None,
         }
     }
 }
@@ -269,6 +384,9 @@ pub enum Menu {
     Channels,
     Backgrounds,
     Zoom,
+    Slideshow,
+    Presets,
+    Language,
 }
 
 
@@ -297,6 +415,11 @@ pub struct MenuVariables {
     pub channels_menu_pos:  Pf32,
     pub background_menu_pos: Pf32,
     pub zoom_menu_pos:      Pf32,
+    pub slideshow_menu_pos: Pf32,
+    pub presets_menu_pos: Pf32,
+    pub language_menu_pos: Pf32,
+    pub fullscreen: bool,     // nincs címsor, a főmenü csak a felső szélen
+    pub menu_hovered: bool,   // az egér a főmenü fölött van
     pub last_msg :          String,
 }
 
@@ -327,6 +450,11 @@ impl Default for MenuVariables {
             channels_menu_pos:  (0.0,0.0).into(),
             background_menu_pos: (0.0,0.0).into(),
             zoom_menu_pos:      (0.0,0.0).into(),
+            slideshow_menu_pos: (0.0,0.0).into(),
+            presets_menu_pos: (0.0,0.0).into(),
+            language_menu_pos: (0.0,0.0).into(),
+            fullscreen: false,
+            menu_hovered: false,
             last_msg:           "".into(),
         }
     }
@@ -338,6 +466,10 @@ impl eframe::App for ImageViewer {
 
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
 
+        self.poll_pending_load(ctx);
+
+        self.slideshow_tick(ctx);
+
         self.anim_and_gpu(ctx, frame);
 
         self.handle_shortcuts(ctx);
@@ -346,6 +478,8 @@ impl eframe::App for ImageViewer {
 
         self.dialogs(ctx);
 
+        self.draw_history_panel(ctx);
+
         let dropped_file = ctx.input_mut(|i| {
             if !i.raw.dropped_files.is_empty() {
                 let files = std::mem::take(&mut i.raw.dropped_files);
@@ -359,11 +493,22 @@ impl eframe::App for ImageViewer {
             //println!("Fájl behúzva: {:?}", path);
         }
 
-        self.draw_image_area(ctx);
+        if self.show_grid {
+            self.draw_thumbnail_grid(ctx);
+        } else {
+            if self.show_filmstrip {
+                self.draw_filmstrip(ctx);
+            }
+            self.draw_compare_info(ctx);
+            self.draw_crop_bar(ctx);
+            self.draw_image_area(ctx);
+        }
 
+        self.track_history(ctx);
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.store_sidecar();
         self.save_settings();
     }
 }
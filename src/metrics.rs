@@ -0,0 +1,193 @@
+/*
+iview/src/metrics.rs
+
+Pixel difference of two images: PSNR, SSIM, mean / max error per channel,
+and a visible difference image (amplified or heat map)
+
+*/
+
+use rayon::prelude::*;
+
+// SSIM ablak mérete és lépése a világosság csatornán
+const SSIM_WINDOW: u32 = 8;
+const SSIM_STEP: u32 = 4;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChannelError {
+    pub mean: f64, // átlagos abszolút eltérés
+    pub max: u8,
+    pub psnr: f64, // dB, azonos csatornánál végtelen
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DiffMetrics {
+    pub width: u32, // az összevetett (közös) terület
+    pub height: u32,
+    pub same_size: bool,
+    pub channels: [ChannelError; 4], // R, G, B, A
+    pub psnr: f64,                   // RGB együtt
+    pub ssim: f64,                   // világosságon, 1.0 = azonos
+    pub differing_pixels: u64,
+    pub total_pixels: u64,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DiffStyle {
+    Amplified(u8), // |a-b| * szorzó csatornánként
+    HeatMap,       // a legnagyobb csatorna eltérés színskálán
+}
+
+fn psnr(sum_sq: f64, count: f64) -> f64 {
+    if sum_sq == 0.0 || count == 0.0 {
+        return f64::INFINITY;
+    }
+    let mse = sum_sq / count;
+    10.0 * (255.0 * 255.0 / mse).log10()
+}
+
+fn luma(p: &image::Rgba<u8>) -> f64 {
+    0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64
+}
+
+/// Átlagos SSIM a világosság csatornán, átfedő 8x8-as ablakokkal
+fn ssim(a: &image::RgbaImage, b: &image::RgbaImage, width: u32, height: u32) -> f64 {
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+    if width < SSIM_WINDOW || height < SSIM_WINDOW {
+        return 1.0;
+    }
+    let rows: Vec<u32> = (0..=height - SSIM_WINDOW).step_by(SSIM_STEP as usize).collect();
+    let (sum, count) = rows
+        .par_iter()
+        .map(|&y| {
+            let mut sum = 0.0;
+            let mut count = 0usize;
+            for x in (0..=width - SSIM_WINDOW).step_by(SSIM_STEP as usize) {
+                let (mut sa, mut sb, mut saa, mut sbb, mut sab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+                for wy in y..y + SSIM_WINDOW {
+                    for wx in x..x + SSIM_WINDOW {
+                        let la = luma(a.get_pixel(wx, wy));
+                        let lb = luma(b.get_pixel(wx, wy));
+                        sa += la;
+                        sb += lb;
+                        saa += la * la;
+                        sbb += lb * lb;
+                        sab += la * lb;
+                    }
+                }
+                let n = (SSIM_WINDOW * SSIM_WINDOW) as f64;
+                let (ma, mb) = (sa / n, sb / n);
+                let va = saa / n - ma * ma;
+                let vb = sbb / n - mb * mb;
+                let cov = sab / n - ma * mb;
+                sum += ((2.0 * ma * mb + C1) * (2.0 * cov + C2))
+                    / ((ma * ma + mb * mb + C1) * (va + vb + C2));
+                count += 1;
+            }
+            (sum, count)
+        })
+        .reduce(|| (0.0, 0), |x, y| (x.0 + y.0, x.1 + y.1));
+    if count == 0 { 1.0 } else { sum / count as f64 }
+}
+
+/// A két kép eltérése a közös (bal felső) területen
+pub fn compare_images(a: &image::RgbaImage, b: &image::RgbaImage) -> DiffMetrics {
+    let width = a.width().min(b.width());
+    let height = a.height().min(b.height());
+
+    // soronként: csatornánkénti abszolút és négyzetes összeg, maximum, eltérő pixelek
+    let (abs, sq, max, differing) = (0..height)
+        .into_par_iter()
+        .map(|y| {
+            let mut abs = [0u64; 4];
+            let mut sq = [0u64; 4];
+            let mut max = [0u8; 4];
+            let mut differing = 0u64;
+            for x in 0..width {
+                let pa = a.get_pixel(x, y);
+                let pb = b.get_pixel(x, y);
+                let mut same = true;
+                for c in 0..4 {
+                    let d = pa[c].abs_diff(pb[c]);
+                    abs[c] += d as u64;
+                    sq[c] += (d as u64) * (d as u64);
+                    max[c] = max[c].max(d);
+                    same &= d == 0;
+                }
+                if !same {
+                    differing += 1;
+                }
+            }
+            (abs, sq, max, differing)
+        })
+        .reduce(
+            || ([0u64; 4], [0u64; 4], [0u8; 4], 0u64),
+            |x, y| {
+                let mut r = x;
+                for c in 0..4 {
+                    r.0[c] += y.0[c];
+                    r.1[c] += y.1[c];
+                    r.2[c] = r.2[c].max(y.2[c]);
+                }
+                r.3 += y.3;
+                r
+            },
+        );
+
+    let total = width as u64 * height as u64;
+    let mut channels = [ChannelError::default(); 4];
+    for c in 0..4 {
+        channels[c] = ChannelError {
+            mean: if total == 0 { 0.0 } else { abs[c] as f64 / total as f64 },
+            max: max[c],
+            psnr: psnr(sq[c] as f64, total as f64),
+        };
+    }
+    DiffMetrics {
+        width,
+        height,
+        same_size: a.dimensions() == b.dimensions(),
+        channels,
+        psnr: psnr((sq[0] + sq[1] + sq[2]) as f64, 3.0 * total as f64),
+        ssim: ssim(a, b, width, height),
+        differing_pixels: differing,
+        total_pixels: total,
+    }
+}
+
+/// Hőtérkép: fekete → kék → zöld → sárga → piros
+fn heat_color(d: u8) -> [u8; 3] {
+    let t = d as f32 / 255.0 * 4.0;
+    let (r, g, b) = match t {
+        t if t < 1.0 => (0.0, 0.0, t),
+        t if t < 2.0 => (0.0, t - 1.0, 2.0 - t),
+        t if t < 3.0 => (t - 2.0, 1.0, 0.0),
+        t => (1.0, (4.0 - t).max(0.0), 0.0),
+    };
+    [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8]
+}
+
+/// A különbség láthatóvá téve, a közös terület méretében
+pub fn difference_image(a: &image::RgbaImage, b: &image::RgbaImage, style: DiffStyle) -> image::RgbaImage {
+    let width = a.width().min(b.width());
+    let height = a.height().min(b.height());
+    let mut out = image::RgbaImage::new(width, height);
+    out.par_chunks_mut(width.max(1) as usize * 4).enumerate().for_each(|(y, row)| {
+        for x in 0..width as usize {
+            let pa = a.get_pixel(x as u32, y as u32);
+            let pb = b.get_pixel(x as u32, y as u32);
+            let d = [pa[0].abs_diff(pb[0]), pa[1].abs_diff(pb[1]), pa[2].abs_diff(pb[2]), pa[3].abs_diff(pb[3])];
+            let rgb = match style {
+                DiffStyle::Amplified(factor) => {
+                    let amp = |v: u8| (v as u32 * factor as u32).min(255) as u8;
+                    // az átlátszóság eltérése szürkeként látszik
+                    let alpha = amp(d[3]);
+                    [amp(d[0]).max(alpha), amp(d[1]).max(alpha), amp(d[2]).max(alpha)]
+                }
+                DiffStyle::HeatMap => heat_color(d.iter().copied().max().unwrap_or(0)),
+            };
+            row[x * 4..x * 4 + 4].copy_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+        }
+    });
+    out
+}
@@ -0,0 +1,186 @@
+/*
+iview/src/presets.rs
+
+Named color-correction presets: save the view settings under a name,
+apply them from the menu or with Alt+1..9, import / export as JSON
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::colors::*;
+use crate::file_handlers::*;
+use crate::ImageViewer;
+use iview::t;
+
+/// Alapból Alt+1 .. Alt+9 az első kilenc presetet alkalmazza (átállítható)
+pub const PRESET_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1, egui::Key::Num2, egui::Key::Num3,
+    egui::Key::Num4, egui::Key::Num5, egui::Key::Num6,
+    egui::Key::Num7, egui::Key::Num8, egui::Key::Num9,
+];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ColorPreset {
+    pub name: String,
+    pub settings: ColorSettings,
+}
+
+impl ColorPreset {
+    /// A preset színei a kép saját állásával, kiegyenesítésével és kivágásával
+    pub fn applied_to(&self, current: &ColorSettings) -> ColorSettings {
+        ColorSettings {
+            orientation: current.orientation,
+            rotation: current.rotation,
+            crop: current.crop,
+            ..self.settings
+        }
+    }
+}
+
+pub fn find_preset<'a>(presets: &'a [ColorPreset], name: &str) -> Option<&'a ColorPreset> {
+    presets.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// Azonos nevű presetet felülír, különben a végére kerül
+pub fn insert_preset(presets: &mut Vec<ColorPreset>, preset: ColorPreset) {
+    match presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&preset.name)) {
+        Some(old) => *old = preset,
+        None => presets.push(preset),
+    }
+}
+
+pub fn export_presets(path: &Path, presets: &[ColorPreset]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(presets).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Preset lista, egyetlen preset, vagy egy iview settings.json presetjei
+pub fn import_presets(path: &Path) -> Result<Vec<ColorPreset>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let presets = if value.is_array() {
+        serde_json::from_value::<Vec<ColorPreset>>(value)
+    } else if value.get("color_settings").is_some() {
+        serde_json::from_value::<AppSettings>(value).map(|app| app.presets)
+    } else {
+        serde_json::from_value::<ColorPreset>(value).map(|preset| vec![preset])
+    };
+    presets.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+impl ImageViewer {
+
+    pub fn apply_preset(&mut self, ctx: &egui::Context, index: usize) {
+        let Some(preset) = self.presets.get(index) else {
+            return;
+        };
+        self.color_settings = preset.applied_to(&self.color_settings);
+        if self.look.is_none() {
+            self.color_settings.use_look = false;
+        }
+        self.review(ctx, true, false);
+    }
+
+    pub fn save_preset(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        insert_preset(&mut self.presets, ColorPreset { name: name.to_string(), settings: self.color_settings });
+    }
+
+    pub fn delete_preset(&mut self, index: usize) {
+        if index < self.presets.len() {
+            self.presets.remove(index);
+        }
+    }
+
+    /// Mentéskor a megnevezett preset lép a nézet beállításai helyére; a régit adja vissza
+    pub fn swap_in_preset(&mut self, name: &str) -> Option<(ColorSettings, Option<Lut4ColorSettings>)> {
+        let settings = find_preset(&self.presets, name)?.applied_to(&self.color_settings);
+        let mut lut = Lut4ColorSettings::default();
+        lut.update_lut(&settings, self.look.as_ref());
+        let old_settings = std::mem::replace(&mut self.color_settings, settings);
+        let old_lut = self.lut.replace(lut);
+        Some((old_settings, old_lut))
+    }
+
+    pub fn import_presets_dialog(&mut self) {
+        let dialog = rfd::FileDialog::new()
+            .set_title(t!("preset-import-title"))
+            .add_filter(t!("filter-presets"), &["json"]);
+        if let Some(path) = dialog.pick_file() {
+            match import_presets(&path) {
+                Ok(presets) => {
+                    for preset in presets {
+                        insert_preset(&mut self.presets, preset);
+                    }
+                }
+                Err(e) => self.show_error(t!("error-preset-import", error = e)),
+            }
+        }
+    }
+
+    pub fn export_presets_dialog(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title(t!("preset-export-title"))
+            .add_filter(t!("filter-presets"), &["json"])
+            .set_file_name("iview-presets.json");
+        if let Some(folder) = &self.image_folder {
+            dialog = dialog.set_directory(folder);
+        }
+        if let Some(path) = dialog.save_file() {
+            if let Err(e) = export_presets(&path, &self.presets) {
+                self.show_error(t!("error-preset-export", error = e));
+            }
+        }
+    }
+
+    /// Név bekérése az aktuális beállítások mentéséhez
+    pub fn preset_name_dialog(&mut self, ctx: &egui::Context) {
+        let Some(name) = &mut self.preset_name else {
+            return;
+        };
+        let mut save = false;
+        let mut close = false;
+        egui::Window::new(t!("preset-save-title"))
+            .collapsible(false)
+            .resizable(false)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.viewport_rect().center())
+            .show(ctx, |ui| {
+                let edit = ui.add(egui::TextEdit::singleline(name).hint_text(t!("preset-name")));
+                if edit.lost_focus() {
+                    // Enter ment, Esc elvet
+                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        save = true;
+                    } else if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        close = true;
+                    }
+                } else if !edit.has_focus() && ui.memory(|m| m.focused().is_none()) {
+                    edit.request_focus();
+                }
+                if find_preset(&self.presets, name.trim()).is_some() {
+                    ui.label(t!("preset-replace"));
+                }
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!name.trim().is_empty(), egui::Button::new(t!("save-button"))).clicked() {
+                        save = true;
+                    }
+                    if ui.button(t!("save-cancel")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if save {
+            if let Some(name) = self.preset_name.take() {
+                self.save_preset(&name);
+            }
+        } else if close {
+            self.preset_name = None;
+        }
+    }
+}
@@ -0,0 +1,205 @@
+/*
+iview/src/resize.rs
+
+Resize dialog for export: target size in pixels, percent or print size
+with DPI, aspect lock and resampling filter. The chosen size replaces
+the zoom in Save View, Copy View and animation export.
+
+*/
+
+use crate::codec::{OutputSize, ResizeFilter};
+use crate::ImageViewer;
+use iview::t;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ResizeUnit {
+    Pixels,
+    Percent,
+    Centimeters,
+    Inches,
+}
+
+impl ResizeUnit {
+    fn label(self) -> String {
+        match self {
+            ResizeUnit::Pixels => t!("resize-unit-px"),
+            ResizeUnit::Percent => t!("resize-unit-percent"),
+            ResizeUnit::Centimeters => t!("resize-unit-cm"),
+            ResizeUnit::Inches => t!("resize-unit-inch"),
+        }
+    }
+}
+
+pub struct ResizeDialog {
+    pub unit: ResizeUnit,
+    pub width: u32, // a kimenet pixelben
+    pub height: u32,
+    pub dpi: f32,
+    pub keep_aspect: bool,
+    pub filter: ResizeFilter,
+    base: (u32, u32), // a nézet mérete 1x nagyításnál (forgatás és kivágás után)
+}
+
+impl ResizeDialog {
+    /// Pixelből a választott mértékegységbe; `base` az adott oldal eredeti hossza
+    fn to_unit(&self, px: u32, base: u32) -> f32 {
+        match self.unit {
+            ResizeUnit::Pixels => px as f32,
+            ResizeUnit::Percent => px as f32 * 100.0 / base.max(1) as f32,
+            ResizeUnit::Centimeters => px as f32 / self.dpi * 2.54,
+            ResizeUnit::Inches => px as f32 / self.dpi,
+        }
+    }
+
+    fn to_pixels(&self, value: f32, base: u32) -> u32 {
+        let px = match self.unit {
+            ResizeUnit::Pixels => value,
+            ResizeUnit::Percent => value * base as f32 / 100.0,
+            ResizeUnit::Centimeters => value / 2.54 * self.dpi,
+            ResizeUnit::Inches => value * self.dpi,
+        };
+        (px.round() as u32).clamp(1, 65535)
+    }
+
+    fn is_print(&self) -> bool {
+        self.unit == ResizeUnit::Centimeters || self.unit == ResizeUnit::Inches
+    }
+}
+
+impl ImageViewer {
+
+    /// A mentett nézet mérete 1x nagyításnál: elforgatva, kiegyenesítve, kivágva
+    pub fn view_base_size(&self) -> (u32, u32) {
+        let (w, h) = (self.image_size.x as u32, self.image_size.y as u32);
+        match self.color_settings.crop {
+            Some(crop) => {
+                let (_, _, cw, ch) = crop.pixels(w, h);
+                (cw, ch)
+            }
+            None => (w, h),
+        }
+    }
+
+    pub fn open_resize_dialog(&mut self) {
+        if self.original_image.is_none() {
+            return;
+        }
+        let base = self.view_base_size();
+        let (width, height) = match &self.output_size {
+            Some(size) => size.size_for(base),
+            None => (((base.0 as f32 * self.magnify).round() as u32).max(1), ((base.1 as f32 * self.magnify).round() as u32).max(1)),
+        };
+        // a beolvasott felbontás, pont / cm esetén átszámolva
+        let dpi = self.output_size.and_then(|s| s.dpi)
+            .or(self.resolution.as_ref().filter(|r| r.xres > 0.0).map(|r| if r.dpi { r.xres } else { r.xres * 2.54 }))
+            .unwrap_or(72.0);
+        self.resize_dialog = Some(ResizeDialog {
+            unit: ResizeUnit::Pixels,
+            width,
+            height,
+            dpi,
+            keep_aspect: self.output_size.is_none_or(|s| s.keep_aspect),
+            filter: self.output_size.map(|s| s.filter).unwrap_or(ResizeFilter::Lanczos3),
+            base,
+        });
+    }
+
+    pub fn resize_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &mut self.resize_dialog else {
+            return;
+        };
+        let mut apply = false;
+        let mut use_zoom = false;
+        let mut close = false;
+        egui::Window::new(t!("resize-title"))
+            .collapsible(false)
+            .resizable(false)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.viewport_rect().center())
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for unit in [ResizeUnit::Pixels, ResizeUnit::Percent, ResizeUnit::Centimeters, ResizeUnit::Inches] {
+                        ui.radio_value(&mut dialog.unit, unit, unit.label());
+                    }
+                });
+                ui.add_space(6.0);
+                let decimals = if dialog.unit == ResizeUnit::Pixels { 0 } else { 2 };
+                egui::Grid::new("resize_grid").num_columns(3).show(ui, |ui| {
+                    ui.label(t!("resize-width"));
+                    let mut width = dialog.to_unit(dialog.width, dialog.base.0);
+                    if ui.add(egui::DragValue::new(&mut width).speed(0.5).fixed_decimals(decimals)).changed() {
+                        dialog.width = dialog.to_pixels(width, dialog.base.0);
+                        if dialog.keep_aspect {
+                            dialog.height = ((dialog.width as f32 * dialog.base.1 as f32 / dialog.base.0.max(1) as f32).round() as u32).max(1);
+                        }
+                    }
+                    ui.label(dialog.unit.label());
+                    ui.end_row();
+
+                    ui.label(t!("resize-height"));
+                    let mut height = dialog.to_unit(dialog.height, dialog.base.1);
+                    if ui.add(egui::DragValue::new(&mut height).speed(0.5).fixed_decimals(decimals)).changed() {
+                        dialog.height = dialog.to_pixels(height, dialog.base.1);
+                        if dialog.keep_aspect {
+                            dialog.width = ((dialog.height as f32 * dialog.base.0 as f32 / dialog.base.1.max(1) as f32).round() as u32).max(1);
+                        }
+                    }
+                    ui.label(dialog.unit.label());
+                    ui.end_row();
+
+                    ui.label(t!("resize-resolution"));
+                    let (print_w, print_h) = (dialog.width as f32 / dialog.dpi, dialog.height as f32 / dialog.dpi);
+                    if ui.add(egui::DragValue::new(&mut dialog.dpi).range(1.0..=4800.0).speed(1.0).fixed_decimals(0)).changed()
+                        && dialog.is_print()
+                    {
+                        // nyomtatási méretnél a papíron mért méret marad
+                        dialog.width = ((print_w * dialog.dpi).round() as u32).clamp(1, 65535);
+                        dialog.height = ((print_h * dialog.dpi).round() as u32).clamp(1, 65535);
+                    }
+                    ui.label(t!("resize-dpi"));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut dialog.keep_aspect, t!("resize-keep-aspect"));
+                egui::ComboBox::from_label(t!("resize-filter"))
+                    .selected_text(format!("{:?}", dialog.filter))
+                    .show_ui(ui, |ui| {
+                        for filter in ResizeFilter::ALL {
+                            ui.selectable_value(&mut dialog.filter, filter, format!("{:?}", filter));
+                        }
+                    });
+                ui.separator();
+                ui.label(t!("resize-summary",
+                    from_width = dialog.base.0, from_height = dialog.base.1, width = dialog.width, height = dialog.height,
+                    print_width = format!("{:.2}", dialog.width as f32 / dialog.dpi * 2.54),
+                    print_height = format!("{:.2}", dialog.height as f32 / dialog.dpi * 2.54),
+                    dpi = format!("{:.0}", dialog.dpi)));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("resize-apply")).clicked() {
+                        apply = true;
+                    }
+                    if ui.button(t!("resize-use-zoom")).on_hover_text(t!("resize-use-zoom-hint")).clicked() {
+                        use_zoom = true;
+                    }
+                    if ui.button(t!("save-cancel")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if apply {
+            self.output_size = Some(OutputSize {
+                width: dialog.width,
+                height: dialog.height,
+                filter: dialog.filter,
+                dpi: Some(dialog.dpi),
+                keep_aspect: dialog.keep_aspect,
+            });
+        }
+        if use_zoom {
+            self.output_size = None;
+        }
+        if apply || use_zoom || close {
+            self.resize_dialog = None;
+        }
+    }
+}
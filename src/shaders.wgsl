@@ -157,7 +157,7 @@ struct GpuSharpenSettings {
     transparency_tolerance: f32,
     use_transparency: u32,   // != 0
     rough_transparency: u32,
-    _pad: f32,
+    look_intensity: f32,   // .cube LUT erőssége, 0 = nincs
 }
 
 @group(1) @binding(0) var t_src: texture_2d<f32>;       // Eredeti kép
@@ -166,6 +166,7 @@ struct GpuSharpenSettings {
 @group(1) @binding(3) var<uniform> filt: GpuSharpenSettings;
 @group(1) @binding(4) var t_out: texture_storage_2d<rgba8unorm, write>;
 @group(1) @binding(5) var<storage, read_write> histogram: array<atomic<u32>, 4*256>;
+@group(1) @binding(6) var t_look: texture_3d<f32>;      // Betöltött .cube LUT (33x33x33)
 
 @compute @workgroup_size(16, 16)
 fn apply_effects(@builtin(global_invocation_id) id: vec3<u32>) {
@@ -243,6 +244,11 @@ fn apply_effects(@builtin(global_invocation_id) id: vec3<u32>) {
     let lut_size = 33.0;
     let lut_coords = clamp(processed, vec3(0.0), vec3(1.0)) * ((lut_size - 1.0) / lut_size) + (0.5 / lut_size);
     var corrected_rgb = textureSampleLevel(t_lut, s_linear, lut_coords, 0.0).rgb;
+    if (filt.look_intensity > 0.0) {
+        let look_coords = clamp(corrected_rgb, vec3(0.0), vec3(1.0)) * ((lut_size - 1.0) / lut_size) + (0.5 / lut_size);
+        let looked = textureSampleLevel(t_look, s_linear, look_coords, 0.0).rgb;
+        corrected_rgb = mix(corrected_rgb, looked, filt.look_intensity);
+    }
     
     let h_r = u32(corrected_rgb.r * 255.0);
     atomicAdd(&histogram[h_r], 1u);
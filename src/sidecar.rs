@@ -0,0 +1,206 @@
+/*
+iview/src/sidecar.rs
+
+Non-destructive edit sidecars: the view settings of an edited image are kept
+next to the file (<file>.iview.json) or in a central store keyed by the file
+content, and reapplied when the image is opened again
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::colors::*;
+use crate::file_handlers::*;
+use crate::ImageViewer;
+use iview::t;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum SidecarMode {
+    Off,        // nem ment, nem tölt vissza
+    NextToFile, // <kép>.iview.json a kép mellett
+    Central,    // a beállítások mappájában, a tartalom hash-e szerint
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Sidecar {
+    pub path: PathBuf,        // a kép, amihez készült
+    pub content_hash: String, // a kép ujjlenyomata, ha a fájl megváltozik, nem érvényes
+    pub settings: ColorSettings,
+}
+
+/// Ennyi bájtot hash-elünk a fájl elejéből (a hosszal együtt)
+const HASH_BYTES: u64 = 1 << 20;
+
+/// A fájl ujjlenyomata: a hossz és az első 1 MB FNV-1a hash-e
+pub fn content_hash(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let mut head = Vec::new();
+    file.take(HASH_BYTES).read_to_end(&mut head).ok()?;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in len.to_le_bytes().iter().chain(head.iter()) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    Some(format!("{:016x}", hash))
+}
+
+fn next_to_file(image: &Path) -> PathBuf {
+    let mut name = image.file_name().unwrap_or_default().to_os_string();
+    name.push(".iview.json");
+    image.with_file_name(name)
+}
+
+fn central_store(hash: &str) -> PathBuf {
+    let dir = get_settings_path().parent().map(|p| p.join("sidecars")).unwrap_or_else(|| PathBuf::from("sidecars"));
+    dir.join(format!("{}.json", hash))
+}
+
+/// A megtalált sidecar (helye és beállításai), vagy a hibás sidecar hibája
+pub type SidecarLookup = Result<Option<(PathBuf, ColorSettings)>, String>;
+
+/// A kép mellett, majd a központi tárban keres; csak változatlan fájlhoz ad vissza beállítást.
+/// A hash miatt lassú lehet, ezért a betöltő szálon fut.
+pub fn read_sidecar(image: &Path) -> SidecarLookup {
+    let Some(hash) = content_hash(image) else {
+        return Ok(None);
+    };
+    let mut error = None;
+    for path in [next_to_file(image), central_store(&hash)] {
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match serde_json::from_str::<Sidecar>(&text) {
+            Ok(sidecar) if sidecar.content_hash == hash => return Ok(Some((path, sidecar.settings))),
+            Ok(_) => {} // a kép azóta megváltozott, a régi szerkesztés nem érvényes
+            Err(e) => error = error.or(Some(t!("sidecar-error", path = path.display(), error = e))),
+        }
+    }
+    error.map_or(Ok(None), Err)
+}
+
+pub fn write_sidecar(mode: SidecarMode, image: &Path, settings: &ColorSettings) -> Result<Option<PathBuf>, String> {
+    let hash = content_hash(image).ok_or(t!("sidecar-cannot-read", path = image.display()))?;
+    let path = match mode {
+        SidecarMode::Off => return Ok(None),
+        SidecarMode::NextToFile => next_to_file(image),
+        SidecarMode::Central => central_store(&hash),
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let sidecar = Sidecar { path: image.to_path_buf(), content_hash: hash, settings: *settings };
+    let json = serde_json::to_string_pretty(&sidecar).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Some(path))
+}
+
+/// Mindkét helyről törli a kép sidecarját
+pub fn remove_sidecars(image: &Path) -> Result<(), String> {
+    let mut paths = vec![next_to_file(image)];
+    if let Some(hash) = content_hash(image) {
+        paths.push(central_store(&hash));
+    }
+    for path in paths {
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| t!("sidecar-remove-error", path = path.display(), error = e))?;
+        }
+    }
+    Ok(())
+}
+
+/// A kép fájl átírása (`write`) úgy, hogy a sidecarja az új tartalom hash-éhez kerüljön.
+/// `keep`: az átírás után megmaradó beállítások, None esetén a régi sidecaré; az új sidecar helyét adja vissza
+pub fn rewrite_keeping_sidecar(image: &Path, mode: SidecarMode, keep: Option<ColorSettings>,
+                               write: impl FnOnce() -> Result<(), String>) -> Result<Option<PathBuf>, String> {
+    if mode == SidecarMode::Off {
+        write()?;
+        return Ok(None);
+    }
+    // a hibás sidecar nem akadályozza a kép átírását
+    let sidecar = read_sidecar(image).ok().flatten();
+    write()?;
+    if let Some((old, _)) = &sidecar {
+        let _ = std::fs::remove_file(old);
+    }
+    match keep.or(sidecar.map(|(_, settings)| settings)) {
+        Some(settings) if settings != ColorSettings::default() => write_sidecar(mode, image, &settings),
+        _ => Ok(None),
+    }
+}
+
+impl ImageViewer {
+
+    /// A látható kép szerkesztését elmenti, ha a megnyitás óta változott
+    pub fn store_sidecar(&mut self) {
+        let Some((image, _, _)) = &self.displayed_image else {
+            return;
+        };
+        let opened = self.history.steps[0].settings;
+        if self.sidecar_mode == SidecarMode::Off || self.color_settings == opened {
+            return;
+        }
+        if self.color_settings == ColorSettings::default() {
+            // kézzel visszaállították az eredetire
+            let removed = remove_sidecars(image);
+            self.sidecar = None;
+            if let Err(e) = removed {
+                self.show_error(e);
+            }
+            return;
+        }
+        match write_sidecar(self.sidecar_mode, image, &self.color_settings) {
+            Ok(path) => self.sidecar = path,
+            Err(e) => self.show_error(t!("sidecar-save-error", error = e)),
+        }
+    }
+
+    /// Betöltéskor: a betöltő szálon megkeresett korábbi szerkesztés visszaállítása; igaz, ha volt
+    pub fn load_sidecar(&mut self, found: SidecarLookup) -> bool {
+        self.sidecar = None;
+        if self.sidecar_mode == SidecarMode::Off {
+            return false;
+        }
+        let (path, mut settings) = match found {
+            Ok(Some(found)) => found,
+            Ok(None) => return false,
+            Err(e) => {
+                self.show_error(e);
+                return false;
+            }
+        };
+        if self.look.is_none() {
+            settings.use_look = false;
+        }
+        self.color_settings = settings;
+        self.crop_orientation = settings.orientation;
+        self.sidecar = Some(path);
+        true
+    }
+
+    /// A sidecar törlése és az eredeti kép visszaállítása (visszavonható)
+    pub fn revert_to_original(&mut self, ctx: &egui::Context) {
+        if let Some((image, _, _)) = &self.displayed_image {
+            if let Err(e) = remove_sidecars(image) {
+                self.show_error(e);
+            }
+        }
+        self.sidecar = None;
+        let rot = self.color_settings.orientation.swaps_axes();
+        self.color_settings = ColorSettings::default();
+        self.review(ctx, true, rot);
+    }
+
+    /// A szerkesztett nézet mentése új fájlba, az eredeti mellé
+    pub fn bake_to_new_file(&mut self) {
+        let Some((image, _, _)) = &self.displayed_image else {
+            return;
+        };
+        let stem = image.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let target = image.with_file_name(format!("{}_edited.png", stem));
+        self.save_original = false;
+        self.starting_save_as(&target);
+    }
+}
@@ -0,0 +1,228 @@
+/*
+iview/src/slideshow.rs
+
+Slideshow of the current folder: timer, shuffle, loop and transitions
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::image_processing::*;
+use crate::pf32::Pf32;
+use crate::ImageViewer;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum Transition {
+    None,
+    CrossFade, // az új kép fokozatosan jelenik meg a régi fölött
+    Slide,     // az új kép jobbról betolja a régit
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SlideshowSettings {
+    pub interval: f32, // másodperc
+    pub shuffle: bool,
+    pub repeat: bool,
+    pub transition: Transition,
+    pub transition_time: f32, // másodperc
+}
+
+impl Default for SlideshowSettings {
+    fn default() -> Self {
+        Self {
+            interval: 5.0,
+            shuffle: false,
+            repeat: true,
+            transition: Transition::CrossFade,
+            transition_time: 0.6,
+        }
+    }
+}
+
+/// Az előző kép a váltás idejére
+struct PreviousSlide {
+    tiles: TileSet,
+    size: Pf32,
+    magnify: f32,
+    started: Instant,
+}
+
+pub struct Slideshow {
+    order: Vec<usize>, // a lista indexei a vetítés sorrendjében
+    shown_at: Instant, // mikor jelent meg az aktuális kép
+    previous: Option<PreviousSlide>,
+}
+
+/// Véletlen sorrend (xorshift, az idő a mag); az első elem nem `avoid`
+fn shuffled(len: usize, avoid: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    let mut seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0x9E37_79B9_7F4A_7C15)
+        | 1;
+    for i in (1..len).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        order.swap(i, (seed % (i as u64 + 1)) as usize);
+    }
+    if len > 1 && order[0] == avoid {
+        order.swap(0, len - 1);
+    }
+    order
+}
+
+/// Véletlen sorrend, amely a `first` képpel kezdődik
+fn shuffled_from(len: usize, first: usize) -> Vec<usize> {
+    let mut order = shuffled(len, usize::MAX);
+    if let Some(pos) = order.iter().position(|&i| i == first) {
+        order.swap(0, pos);
+    }
+    order
+}
+
+impl ImageViewer {
+
+    fn slideshow_order(&self) -> Vec<usize> {
+        let len = self.list_of_images.len();
+        if self.slideshow_settings.shuffle {
+            // az aktuális képpel kezdünk
+            shuffled_from(len, self.actual_index)
+        } else {
+            (0..len).map(|k| (self.actual_index + k) % len).collect()
+        }
+    }
+
+    pub fn start_slideshow(&mut self) {
+        if self.list_of_images.is_empty() {
+            return;
+        }
+        self.slideshow = Some(Slideshow {
+            order: self.slideshow_order(),
+            shown_at: Instant::now(),
+            previous: None,
+        });
+        self.show_grid = false;
+        self.want_magnify = -1.0;
+    }
+
+    pub fn stop_slideshow(&mut self) {
+        self.slideshow = None;
+    }
+
+    pub fn toggle_slideshow(&mut self) {
+        if self.slideshow.is_some() {
+            self.stop_slideshow();
+        } else {
+            self.start_slideshow();
+        }
+    }
+
+    /// A következő kép indexe, ha van (előtöltéshez)
+    pub fn slideshow_next(&self) -> Option<usize> {
+        let show = self.slideshow.as_ref()?;
+        let pos = show.order.iter().position(|&i| i == self.actual_index)?;
+        match show.order.get(pos + 1) {
+            Some(&next) => Some(next),
+            None if self.slideshow_settings.repeat && !self.slideshow_settings.shuffle => show.order.first().copied(),
+            None => None,
+        }
+    }
+
+    /// Minden képkockában: ha letelt az idő (animációnál legalább egy teljes kör), jön a következő kép
+    pub fn slideshow_tick(&mut self, ctx: &egui::Context) {
+        let Some(show) = &mut self.slideshow else {
+            return;
+        };
+        match show.previous.as_ref().map(|p| p.started.elapsed().as_secs_f32()) {
+            Some(t) if t >= self.slideshow_settings.transition_time => show.previous = None,
+            Some(_) => ctx.request_repaint(), // a váltás animációja
+            None => {}
+        }
+        if self.pending_load.is_some() {
+            return;
+        }
+        let mut wait = Duration::from_secs_f32(self.slideshow_settings.interval.max(0.5));
+        if let Some(anim) = &self.anim_data {
+            wait = wait.max(anim.delays.iter().sum());
+        }
+        let elapsed = show.shown_at.elapsed();
+        if elapsed < wait {
+            ctx.request_repaint_after(wait - elapsed);
+            return;
+        }
+
+        let len = self.list_of_images.len();
+        if show.order.len() != len {
+            // közben más könyvtárra váltottak; a keverés bekapcsolva marad
+            show.order = if self.slideshow_settings.shuffle {
+                shuffled_from(len, self.actual_index)
+            } else {
+                (0..len).collect()
+            };
+        }
+        let pos = show.order.iter().position(|&i| i == self.actual_index).unwrap_or(0);
+        let next = match show.order.get(pos + 1).copied() {
+            Some(next) => next,
+            None if self.slideshow_settings.repeat && len > 0 => {
+                if self.slideshow_settings.shuffle {
+                    show.order = shuffled(len, self.actual_index);
+                }
+                show.order[0]
+            }
+            None => {
+                self.slideshow = None;
+                return;
+            }
+        };
+        // ha nem sikerül betölteni, innen számolva lépünk tovább
+        show.shown_at = Instant::now();
+        self.open_list_index(ctx, next);
+    }
+
+    /// A betöltött új kép megjelenése előtt: az előző kép megmarad a váltás animációhoz
+    pub fn slideshow_image_loaded(&mut self) {
+        let Some(show) = &mut self.slideshow else {
+            return;
+        };
+        show.shown_at = Instant::now();
+        self.want_magnify = -1.0;
+        if self.slideshow_settings.transition != Transition::None && !self.tiles.is_empty() {
+            show.previous = Some(PreviousSlide {
+                tiles: std::mem::take(&mut self.tiles),
+                size: self.image_size,
+                magnify: self.magnify,
+                started: Instant::now(),
+            });
+        }
+    }
+
+    /// Váltás közben az előző és az új kép együtt; igaz, ha rajzolt
+    pub fn draw_transition(&self, ui: &egui::Ui, image_rect: egui::Rect,
+                           tiles: &TileSet) -> bool {
+        let Some(previous) = self.slideshow.as_ref().and_then(|s| s.previous.as_ref()) else {
+            return false;
+        };
+        let t = (previous.started.elapsed().as_secs_f32() / self.slideshow_settings.transition_time.max(0.01)).clamp(0.0, 1.0);
+        let previous_rect = egui::Rect::from_min_size(image_rect.min, (previous.size * previous.magnify).floor().into());
+        match self.slideshow_settings.transition {
+            Transition::CrossFade => {
+                previous.tiles.paint(ui.painter(), previous_rect, previous.magnify);
+                let mut painter = ui.painter().clone();
+                painter.set_opacity(t);
+                tiles.paint(&painter, image_rect, self.magnify);
+            }
+            Transition::Slide => {
+                let width = ui.clip_rect().width();
+                let shift = egui::vec2(-width * t, 0.0);
+                previous.tiles.paint(ui.painter(), previous_rect.translate(shift), previous.magnify);
+                tiles.paint(ui.painter(), image_rect.translate(shift + egui::vec2(width, 0.0)), self.magnify);
+            }
+            Transition::None => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,142 @@
+/*
+iview/src/thumbnails.rs
+
+Thumbnails for the folder browser, cached on disk following the freedesktop thumbnail spec
+https://specifications.freedesktop.org/thumbnail-spec/latest/
+
+*/
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::codec::*;
+use crate::colors::Orientation;
+use crate::t;
+
+pub const THUMB_SIZE: u32 = 128; // a spec "normal" mérete
+
+/// $XDG_CACHE_HOME/thumbnails/normal (Windows-on AppData/Local/thumbnails/normal)
+pub fn thumbnail_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|d| d.cache_dir().join("thumbnails").join("normal"))
+}
+
+/// Abszolút file:// URI, a nem biztonságos karakterek %XX alakban
+pub fn file_uri(path: &Path) -> String {
+    let abs = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut s = abs.to_string_lossy().replace('\\', "/");
+    if let Some(rest) = s.strip_prefix("//?/") { // Windows canonicalize előtag
+        s = rest.to_string();
+    }
+    if !s.starts_with('/') {
+        s.insert(0, '/');
+    }
+    let mut uri = String::from("file://");
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+            | b'-' | b'_' | b'.' | b'~' | b'!' | b'*' | b'\'' | b'(' | b')'
+            | b'/' | b':' | b'@' | b'&' | b'=' | b'+' | b'$' | b',' => uri.push(b as char),
+            _ => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
+
+/// A cache fájl neve: az URI MD5 összege hexában
+pub fn thumbnail_path(uri: &str) -> Option<PathBuf> {
+    thumbnail_dir().map(|d| d.join(format!("{:x}.png", md5::compute(uri.as_bytes()))))
+}
+
+fn file_mtime(path: &Path) -> Result<u64, String> {
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(modified.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0))
+}
+
+fn read_cached(thumb_path: &Path, uri: &str, mtime: u64) -> Option<image::RgbaImage> {
+    let file = std::fs::File::open(thumb_path).ok()?;
+    let reader = png::Decoder::new(std::io::BufReader::new(file)).read_info().ok()?;
+    let texts = &reader.info().uncompressed_latin1_text;
+    let valid_uri = texts.iter().any(|t| t.keyword == "Thumb::URI" && t.text == uri);
+    let valid_mtime = texts.iter().any(|t| t.keyword == "Thumb::MTime" && t.text == mtime.to_string());
+    if !valid_uri || !valid_mtime {
+        return None; // a fájl azóta változott
+    }
+    drop(reader);
+    image::open(thumb_path).ok().map(|img| img.to_rgba8())
+}
+
+fn write_cached(thumb_path: &Path, uri: &str, mtime: u64, img: &image::RgbaImage) -> Result<(), String> {
+    let dir = thumb_path.parent().ok_or("bad thumbnail path".to_string())?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700));
+    }
+    // a spec szerint ideiglenes fájlba írunk, majd átnevezzük
+    let tmp = thumb_path.with_extension(format!("png.iview-{}", std::process::id()));
+    {
+        let file = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), img.width(), img.height());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.add_text_chunk("Thumb::URI".to_string(), uri.to_string()).map_err(|e| e.to_string())?;
+        encoder.add_text_chunk("Thumb::MTime".to_string(), mtime.to_string()).map_err(|e| e.to_string())?;
+        encoder.add_text_chunk("Software".to_string(), "IView".to_string()).map_err(|e| e.to_string())?;
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(img.as_raw()).map_err(|e| e.to_string())?;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600));
+    }
+    std::fs::rename(&tmp, thumb_path).map_err(|e| e.to_string())
+}
+
+/// JPEG esetén az EXIF-be ágyazott thumbnail, a teljes kép dekódolása nélkül
+fn exif_thumbnail(path: &Path) -> Option<image::RgbaImage> {
+    let buffer = std::fs::read(path).ok()?;
+    let jpeg = img_parts::jpeg::Jpeg::from_bytes(buffer.into()).ok()?;
+    let raw = jpeg.segments().iter()
+        .find(|s| s.marker() == 0xE1 && s.contents().starts_with(b"Exif"))?
+        .contents().to_vec();
+    let (exif, _, orientation) = read_exif(&raw).ok()?;
+    let thumb = image::load_from_memory(exif.thumbnail_bytes()?).ok()?;
+    let thumb = Orientation::from_exif(orientation as u16).apply(thumb);
+    Some(thumb.thumbnail(THUMB_SIZE, THUMB_SIZE).to_rgba8())
+}
+
+/// Bélyegkép a lemez cache-ből, az EXIF-ből, vagy a kép dekódolásával;
+/// mellette a cache írásának hibája, ha volt (a bélyegkép ettől még használható)
+pub fn load_thumbnail(path: &Path) -> Result<(image::RgbaImage, Option<String>), String> {
+    let mtime = file_mtime(path)?;
+    let uri = file_uri(path);
+    let cache_file = thumbnail_path(&uri);
+    if let Some(cache_file) = &cache_file {
+        if let Some(img) = read_cached(cache_file, &uri, mtime) {
+            return Ok((img, None));
+        }
+    }
+    let is_jpeg = path.extension().and_then(|s| s.to_str())
+        .and_then(SaveFormat::from_extension) == Some(SaveFormat::Jpeg);
+    let thumb = match is_jpeg.then(|| exif_thumbnail(path)).flatten() {
+        Some(thumb) => thumb,
+        None => {
+            let loaded = load_image_file(path)?;
+            loaded.orientation().apply(loaded.image).thumbnail(THUMB_SIZE, THUMB_SIZE).to_rgba8()
+        }
+    };
+    let mut cache_error = None;
+    if let Some(cache_file) = &cache_file {
+        // a thumbnail könyvtárban lévő képekről nem készítünk újabbat
+        if !cache_file.parent().is_some_and(|dir| path.starts_with(dir)) {
+            cache_error = write_cached(cache_file, &uri, mtime, &thumb)
+                .err()
+                .map(|e| t!("thumbnail-cache-error", error = e));
+        }
+    }
+    Ok((thumb, cache_error))
+}
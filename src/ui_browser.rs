@@ -0,0 +1,174 @@
+/*
+iview/src/ui_browser.rs
+
+Thumbnail grid and filmstrip of the current folder
+
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use rayon::prelude::*;
+
+use crate::thumbnails::*;
+use crate::ImageViewer;
+use iview::t;
+
+pub const FILMSTRIP_HEIGHT: f32 = 96.0;
+const GRID_CELL: f32 = THUMB_SIZE as f32 + 12.0;
+
+/// A mappa bélyegképei textúraként; a hiányzókat háttérszálon, párhuzamosan készíti
+#[derive(Default)]
+pub struct ThumbnailStore {
+    pub textures: HashMap<PathBuf, egui::TextureHandle>,
+    receiver: Option<mpsc::Receiver<(PathBuf, image::RgbaImage)>>,
+    cancel: Arc<AtomicBool>,
+    key: (Option<PathBuf>, usize), // mappa és fájlszám, amihez a generálás fut
+}
+
+impl ThumbnailStore {
+    pub fn ensure(&mut self, ctx: &egui::Context, folder: &Option<PathBuf>, paths: &[PathBuf]) {
+        let key = (folder.clone(), paths.len());
+        if key != self.key {
+            self.key = key;
+            self.start(ctx, paths);
+        }
+        if let Some(receiver) = &self.receiver {
+            for (path, img) in receiver.try_iter().take(64) {
+                let size = [img.width() as usize, img.height() as usize];
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(size, img.as_raw());
+                let texture = ctx.load_texture(format!("thumb_{}", path.display()), color_image, Default::default());
+                self.textures.insert(path, texture);
+            }
+        }
+    }
+
+    fn start(&mut self, ctx: &egui::Context, paths: &[PathBuf]) {
+        self.cancel.store(true, Ordering::Relaxed); // az előző mappa generálása leáll
+        let wanted: HashSet<&PathBuf> = paths.iter().collect();
+        self.textures.retain(|p, _| wanted.contains(p));
+
+        let todo: Vec<PathBuf> = paths.iter().filter(|p| !self.textures.contains_key(*p)).cloned().collect();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.cancel = cancel.clone();
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(receiver);
+        let repaint = ctx.clone();
+        std::thread::spawn(move || {
+            todo.par_iter().for_each_with(sender, |sender, path| {
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
+                match load_thumbnail(path) {
+                    Ok((img, cache_error)) => {
+                        if let Some(e) = cache_error {
+                            println!("{}", e);
+                        }
+                        if sender.send((path.clone(), img)).is_ok() {
+                            repaint.request_repaint();
+                        }
+                    }
+                    Err(e) => println!("{}", t!("thumbnail-error", error = e)),
+                }
+            });
+        });
+    }
+}
+
+impl ImageViewer {
+
+    fn update_thumbnails(&mut self, ctx: &egui::Context) -> Vec<PathBuf> {
+        let paths: Vec<PathBuf> = self.list_of_images.iter().map(|e| e.path()).collect();
+        self.thumbnails.ensure(ctx, &self.image_folder, &paths);
+        paths
+    }
+
+    /// Egy bélyegkép cella; az aktuális kép kiemelve
+    fn thumbnail_cell(&self, ui: &mut egui::Ui, path: &PathBuf, index: usize, side: f32) -> egui::Response {
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(side, side), egui::Sense::click());
+        if index == self.actual_index {
+            ui.painter().rect_filled(rect, 4.0, ui.visuals().selection.bg_fill);
+        } else if response.hovered() {
+            ui.painter().rect_filled(rect, 4.0, ui.visuals().widgets.hovered.bg_fill);
+        }
+        if let Some(texture) = self.thumbnails.textures.get(path) {
+            let size = texture.size_vec2();
+            let scale = (side - 8.0) / size.x.max(size.y);
+            let img_rect = egui::Rect::from_center_size(rect.center(), size * scale);
+            egui::Image::from_texture(texture).paint_at(ui, img_rect);
+        } else {
+            ui.put(rect.shrink(side * 0.35), egui::Spinner::new());
+        }
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        response.on_hover_text(name)
+    }
+
+    pub fn draw_thumbnail_grid(&mut self, ctx: &egui::Context) {
+        let paths = self.update_thumbnails(ctx);
+        let mut clicked = None;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if paths.is_empty() {
+                ui.centered_and_justified(|ui| ui.label(t!("browser-empty")));
+                return;
+            }
+            let columns = ((ui.available_width() / GRID_CELL).floor() as usize).max(1);
+            let rows = paths.len().div_ceil(columns);
+            egui::ScrollArea::vertical()
+                .id_salt("thumbnail_grid")
+                .auto_shrink([false; 2])
+                .show_rows(ui, GRID_CELL, rows, |ui, row_range| {
+                    for row in row_range {
+                        ui.horizontal(|ui| {
+                            for (index, path) in paths.iter().enumerate().skip(row * columns).take(columns) {
+                                if self.thumbnail_cell(ui, path, index, GRID_CELL).clicked() {
+                                    clicked = Some(index);
+                                }
+                            }
+                        });
+                    }
+                });
+        });
+        if let Some(index) = clicked {
+            self.show_grid = false;
+            self.open_list_index(ctx, index);
+        }
+    }
+
+    pub fn draw_filmstrip(&mut self, ctx: &egui::Context) {
+        let paths = self.update_thumbnails(ctx);
+        let side = FILMSTRIP_HEIGHT - 8.0;
+        let mut clicked = None;
+        egui::TopBottomPanel::bottom("filmstrip")
+            .exact_height(FILMSTRIP_HEIGHT)
+            .show(ctx, |ui| {
+                let cell = side + ui.spacing().item_spacing.x;
+                let mut scroll = egui::ScrollArea::horizontal()
+                    .id_salt("filmstrip")
+                    .auto_shrink([false; 2]);
+                // az aktuális kép kerüljön középre, ha lapoztunk
+                if self.filmstrip_index != Some(self.actual_index) {
+                    self.filmstrip_index = Some(self.actual_index);
+                    let offset = self.actual_index as f32 * cell - (ui.available_width() - cell) * 0.5;
+                    scroll = scroll.horizontal_scroll_offset(offset.max(0.0));
+                }
+                scroll.show_viewport(ui, |ui, viewport| {
+                    // csak a látható cellákat rajzoljuk, a többi helyét kihagyjuk
+                    ui.set_width(cell * paths.len() as f32);
+                    let first = ((viewport.min.x / cell).floor().max(0.0) as usize).min(paths.len());
+                    let last = ((viewport.max.x / cell).ceil().max(0.0) as usize).min(paths.len());
+                    ui.horizontal(|ui| {
+                        ui.add_space(first as f32 * cell);
+                        for (index, path) in paths.iter().enumerate().take(last).skip(first) {
+                            if self.thumbnail_cell(ui, path, index, side).clicked() {
+                                clicked = Some(index);
+                            }
+                        }
+                    });
+                });
+            });
+        if let Some(index) = clicked {
+            self.open_list_index(ctx, index);
+        }
+    }
+}
@@ -1,7 +1,12 @@
 use crate::colors::*;
 use crate::file_handlers::*;
+use crate::exif_editor::ExifEditorAction;
+use crate::sidecar::SidecarMode;
 //use crate::image_processing::*;
 use crate::ImageViewer;
+use iview::exif_display::group_title;
+use iview::exif_writer::Ifd;
+use iview::t;
 
 
 impl ImageViewer {
@@ -36,7 +41,7 @@ impl ImageViewer {
                                     ui.visuals().text_color().linear_multiply(0.5)
                                 };
                                 ui.spacing_mut().item_spacing.x = 1.0; // Szoros illeszkedés a gombok között
-                                let header_btn = egui::Button::new(egui::RichText::new("iView 🔍 About").color(text_color).strong()).fill(header_color)
+                                let header_btn = egui::Button::new(egui::RichText::new(t!("about-title")).color(text_color).strong()).fill(header_color)
                                     .min_size(egui::vec2(ui.available_width() - 30.0, 28.0));
                                 let title_bar_response = ui.add(header_btn);
                                 if title_bar_response.is_pointer_button_down_on() {
@@ -57,20 +62,20 @@ impl ImageViewer {
                             
                             ui.add_space(10.0);
                             ui.heading(egui::RichText::new("iView 2026").size(30.0).strong());
-                            ui.label("The high-speed Rust image viewer");
-                            ui.label("Version: 0.9.1");
+                            ui.label(t!("about-tagline"));
+                            ui.label(t!("about-version", version = "0.9.1"));
                             ui.separator();
 
                             ui.add_space(10.0);
-                            ui.label(egui::RichText::new("Developed by:").strong());
+                            ui.label(egui::RichText::new(t!("about-developed")).strong());
                             ui.label("Ferenc Takács");
 
                             ui.add_space(10.0);
-                            ui.label(egui::RichText::new("AI Contributor:").strong());
+                            ui.label(egui::RichText::new(t!("about-ai")).strong());
                             ui.label("Google Gemini (Pro)");
 
                             ui.add_space(20.0);
-                            ui.label(egui::RichText::new("Technologies used:").strong());
+                            ui.label(egui::RichText::new(t!("about-technologies")).strong());
                         });
 
                         ui.add_space(10.0);
@@ -80,13 +85,13 @@ impl ImageViewer {
                                 ui.group(|ui| {
                                     ui.set_width(320.0);
                                     ui.vertical_centered(|ui| {
-                                        ui.label("• egui & eframe (0.30) - Graphical interface");
-                                        ui.label("• image (0.25) - Image decoding and animation");
-                                        ui.label("• tiff (0.9) - Precision metadata management");
-                                        ui.label("• png (0.17) - Chunk level analysis");
-                                        ui.label("• kamadak-exif - EXIF database");
-                                        ui.label("• rfd - Native file dialogs");
-                                        ui.label("• serde - Configuration backup");
+                                        ui.label(t!("about-tech-egui"));
+                                        ui.label(t!("about-tech-image"));
+                                        ui.label(t!("about-tech-tiff"));
+                                        ui.label(t!("about-tech-png"));
+                                        ui.label(t!("about-tech-exif"));
+                                        ui.label(t!("about-tech-rfd"));
+                                        ui.label(t!("about-tech-serde"));
                                         ui.label("• webp-animation (0.9)");
                                     });
                                 });
@@ -95,17 +100,17 @@ impl ImageViewer {
                         if self.gpu_interface.is_some() {
                             ui.vertical_centered(|ui| {
                                 ui.add_space(20.0);
-                                ui.label(egui::RichText::new("GPU interface used.").strong());
+                                ui.label(egui::RichText::new(t!("about-gpu")).strong());
                             });
                         }
                         ui.add_space(20.0);
                         ui.vertical_centered(|ui| {
-                            if ui.button("Cancel").clicked() {
+                            if ui.button(t!("button-cancel")).clicked() {
                                 self.show_about_window = false;
                             }
                             ui.add_space(10.0);
                             ui.label(
-                                egui::RichText::new("Made in Rust, for speed.")
+                                egui::RichText::new(t!("about-made-in-rust"))
                                     .italics()
                                     .size(10.0),
                             );
@@ -119,11 +124,18 @@ impl ImageViewer {
             );
         }
 
+        self.resize_dialog(ctx);
+
+        self.preset_name_dialog(ctx);
+
+        self.bindings_dialog(ctx);
+
+        let view_base = self.view_base_size(); // a pontos kimeneti méret ehhez igazodik
         if let Some(save_data) = &mut self.save_dialog {
             let mut need_save = false;
             let mut cancel_save = false;
             // modal(true) blokkolja az alatta lévő felületet
-            egui::Window::new("iView ⚙ Save Settings")
+            egui::Window::new(t!("save-title"))
                 .collapsible(false)
                 .resizable(false)
                 .pivot(egui::Align2::CENTER_CENTER) // Középre tesszük
@@ -134,38 +146,52 @@ impl ImageViewer {
                         SaveFormat::Jpeg => {
                             ui.add(
                                 egui::Slider::new(&mut save_data.quality, 1..=100)
-                                    .text("Quality (JPEG)"),
+                                    .text(t!("save-quality-jpeg")),
                             );
                         }
                         SaveFormat::J2k | SaveFormat::Jp2 => {
                             ui.add(
                                 egui::Slider::new(&mut save_data.quality, 0..=100)
-                                    .text("Quality(1-100) (0:lossless)"),
+                                    .text(t!("save-quality-avif")),
                             );
                         }
                         SaveFormat::Webp => {
-                            ui.checkbox(&mut save_data.lossless, "Lossless Compression");
+                            ui.checkbox(&mut save_data.lossless, t!("save-lossless"));
                             if !save_data.lossless {
                                 ui.add(
                                     egui::Slider::new(&mut save_data.quality, 1..=100)
-                                        .text("Quality (WebP)"),
+                                        .text(t!("save-quality-webp")),
                                 );
                             }
                         }
                         SaveFormat::Jxl => {
                             ui.add(
                                 egui::Slider::new(&mut save_data.quality, 0..=15)
-                                    .text("Quality (0 is lossless)"),
+                                    .text(t!("save-quality-lossless")),
                             );
                         }
                         _ => {}
                     }
+                    if let (false, Some(size)) = (self.save_original, &self.output_size) {
+                        let (width, height) = size.size_for(view_base);
+                        ui.label(t!("save-output-size", width = width, height = height, filter = format!("{:?}", size.filter)));
+                    }
+                    if !self.save_original && !self.presets.is_empty() {
+                        egui::ComboBox::from_label(t!("save-preset"))
+                            .selected_text(save_data.preset.clone().unwrap_or_else(|| t!("save-current-view")))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut save_data.preset, None, t!("save-current-view"));
+                                for preset in &self.presets {
+                                    ui.selectable_value(&mut save_data.preset, Some(preset.name.clone()), preset.name.as_str());
+                                }
+                            });
+                    }
                     if save_data.can_include_exif {
                         if let Some(exif) = self.exif.clone() {
                             if save_data.saveformat != SaveFormat::Bmp && save_data.saveformat != SaveFormat::Png {
                                 ui.separator();
                             }
-                            let txt = format!("📝 Include EXIF metadata (+ {} bytes) ",exif.raw_exif_length);
+                            let txt = t!("save-include-exif", bytes = exif.raw_exif_length);
                             ui.checkbox(&mut save_data.include_exif, txt);
                         }
                     }
@@ -173,17 +199,17 @@ impl ImageViewer {
                     
                     if save_data.is_animation {
                         ui.horizontal(|ui| {
-                            ui.label(egui::RichText::new("Animation detected:").strong());
-                            ui.radio_value(&mut save_data.save_all_frames, false, "Current Frame Only");
-                            ui.radio_value(&mut save_data.save_all_frames, true, "Full Animation");
+                            ui.label(egui::RichText::new(t!("save-animation")).strong());
+                            ui.radio_value(&mut save_data.save_all_frames, false, t!("save-current-frame"));
+                            ui.radio_value(&mut save_data.save_all_frames, true, t!("save-full-animation"));
                         });
                     }
 
                     ui.horizontal(|ui| {
-                        if ui.button("💾 Save").clicked() {
+                        if ui.button(t!("save-button")).clicked() {
                             need_save = true;
                         }
-                        if ui.button("❌ Cancel").clicked() {
+                        if ui.button(t!("save-cancel")).clicked() {
                             cancel_save = true;
                         }
                     });
@@ -191,12 +217,19 @@ impl ImageViewer {
             if cancel_save {
                 self.save_dialog = None;
             } else if need_save {
-                self.completing_save(); // Ez belül állítja None-ra a save_dialog-ot
+                // Ez belül állítja None-ra a save_dialog-ot
+                if let Err(e) = self.completing_save() {
+                    println!("{}", e);
+                }
             }
         }
 
         if self.show_info {
-            egui::Window::new("iView 🔍 Image Info")
+            let mut revert = false;
+            let mut bake = false;
+            let mut exif_action = ExifEditorAction::None;
+            let mut toggle_editor = false;
+            egui::Window::new(t!("info-title"))
                 .open(&mut self.show_info) // Bezáró gomb (X) kezelése
                 .show(ctx, |ui| {
                     self.show_info_focus = ctx.input(|i| i.viewport().focused == Some(true));
@@ -205,20 +238,17 @@ impl ImageViewer {
                         .spacing([40.0, 4.0]) // Oszlopok közötti távolság
                         .striped(true) // Sávos festés a jobb olvashatóságért
                         .show(ui, |ui| {
-                            ui.label("Name of file:");
+                            ui.label(t!("info-file-name"));
                             ui.label(self.image_name.clone());
                             ui.end_row();
 
-                            ui.label("Size of image:");
-                            ui.label(format!(
-                                "{} x {} pixel",
-                                self.image_size.x * self.resize, self.image_size.y * self.resize
-                            ));
+                            ui.label(t!("info-image-size"));
+                            ui.label(t!("info-pixels", width = self.image_size.x, height = self.image_size.y));
                             ui.end_row();
 
                             // Fájlméret és dátum lekérése
                             if let Some(meta) = &self.file_meta {
-                                ui.label("Size of file:");
+                                ui.label(t!("info-file-size"));
                                 let mut s = format!("{}", meta.len()).to_string();
                                 let l = s.len();
                                 if l > 3 {
@@ -242,10 +272,10 @@ impl ImageViewer {
                                         s[l - 9..].to_string()
                                     );
                                 }
-                                ui.label(format!("{} Byte", s));
+                                ui.label(t!("info-bytes", size = s));
                                 ui.end_row();
                                 if let Ok(time) = meta.created() {
-                                    ui.label("Time of file:");
+                                    ui.label(t!("info-file-time"));
                                     let ts = time_format::from_system_time(time).unwrap();
                                     let c = time_format::components_utc(ts).unwrap();
                                     ui.label(format!(
@@ -263,7 +293,7 @@ impl ImageViewer {
                                 let dpi = resol.dpi;
                                 let x_val = x_res.to_string();
                                 let y_val = y_res.to_string();
-                                ui.label("Resolution:");
+                                ui.label(t!("info-resolution"));
                                 let unit_str = if dpi { "dpi" } else { "dpcm" };
                                 if x_val == y_val {
                                     ui.label(format!("{} {}", x_val, unit_str));
@@ -276,13 +306,13 @@ impl ImageViewer {
                             if let Some(exif) = &self.exif {
                                 if let Some(f) = exif.get_field("DateTimeOriginal".into())
                                 {
-                                    ui.label("Created:");
+                                    ui.label(t!("info-created"));
                                     ui.label(f/*.display_value().to_string()*/);
                                     ui.end_row();
                                 }
                                 if let Some(f) = exif.get_field("Model".into())
                                 {
-                                    ui.label("Machine:");
+                                    ui.label(t!("info-machine"));
                                     ui.label(f/*.display_value().to_string()*/);
                                     ui.end_row();
                                 }
@@ -302,57 +332,141 @@ impl ImageViewer {
                                     if lor_.contains('W') {
                                         lo_ = -lo_;
                                     }
-                                    ui.label("GeoLocation:");
+                                    ui.label(t!("info-geolocation"));
                                     let koord_szoveg = format!("{:.6}, {:.6}", la_, lo_);
                                     ui.label(&koord_szoveg);
                                     ui.end_row();
 
-                                    ui.label("Map:");
+                                    ui.label(t!("info-map"));
                                     let map_url = format!(
                                         "https://www.google.com/maps/place/{:.6},{:.6}",
                                         la_, lo_
                                     );
-                                    if ui.link("Open in browser 🌍").clicked() {
+                                    if ui.link(t!("info-open-browser")).clicked() {
                                         if let Err(e) = webbrowser::open(&map_url) {
-                                            eprintln!("Can not open the Browser: {}", e);
+                                            eprintln!("{}", t!("error-browser", error = e));
                                         }
                                     }
                                     ui.end_row();
                                 }
                             }
+                            if let Some(error) = &self.exif_error {
+                                ui.label(t!("info-exif-error"));
+                                ui.colored_label(egui::Color32::RED, error);
+                                ui.end_row();
+                            }
                         });
+                        // képenként megőrzött szerkesztés
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            match &self.sidecar {
+                                Some(path) => {
+                                    ui.label(t!("info-edited")).on_hover_text(path.display().to_string());
+                                }
+                                None => {
+                                    ui.label(t!("info-no-edit"));
+                                }
+                            }
+                            egui::ComboBox::from_id_salt("sidecar_mode")
+                                .selected_text(match self.sidecar_mode {
+                                    SidecarMode::Off => t!("sidecar-off"),
+                                    SidecarMode::NextToFile => t!("sidecar-next-to-file"),
+                                    SidecarMode::Central => t!("sidecar-central"),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut self.sidecar_mode, SidecarMode::Off, t!("sidecar-off"));
+                                    ui.selectable_value(&mut self.sidecar_mode, SidecarMode::NextToFile, t!("sidecar-next-to-file"));
+                                    ui.selectable_value(&mut self.sidecar_mode, SidecarMode::Central, t!("sidecar-central"));
+                                });
+                        });
+                        if self.sidecar.is_some() || self.color_settings != ColorSettings::default() {
+                            ui.horizontal(|ui| {
+                                if ui.button(t!("info-revert")).clicked() {
+                                    revert = true;
+                                }
+                                if ui.button(t!("info-bake")).clicked() {
+                                    bake = true;
+                                }
+                            });
+                        }
                         if self.exif.is_some() {
                             ui.separator();
-                            if ui.button("Detail EXIF data").clicked() {
+                            if ui.button(t!("info-exif-detail")).clicked() {
                                 self.show_exif_details = !self.show_exif_details;
                             }
 
                             if self.show_exif_details {
-                                egui::ScrollArea::vertical()
-                                    .max_height(300.0) // Korlátozzuk a magasságot, hogy ne nyúljon túl
-                                    .show(ui, |ui| {
-                                        ui.group(|ui| {
-                                            if let Some(exif) = &self.exif {
-                                                for (name, val, _off) in exif.fields() {
-                                                    ui.horizontal(|ui| {
-                                                        // Tag neve (pl. "Make", "DateTime")
-                                                        ui.label(egui::RichText::new(format!("{}:", name.to_string())).strong());
-                                                        if let Some(v) = val.get("val") {
-                                                            ui.label(v.to_string());
-                                                        }
-                                                        else { ui.label(val.to_string()); }
+                                let mut copy_json = false;
+                                ui.horizontal(|ui| {
+                                    ui.add(egui::TextEdit::singleline(&mut self.exif_search)
+                                        .hint_text(t!("exif-search-hint"))
+                                        .desired_width(200.0));
+                                    if ui.button(t!("exif-copy-json")).on_hover_text(t!("exif-copy-json-hint")).clicked() {
+                                        copy_json = true;
+                                    }
+                                });
+                                if let Some(exif) = &self.exif {
+                                    if copy_json {
+                                        ctx.copy_text(exif.grouped_json(&self.exif_search));
+                                    }
+                                    egui::ScrollArea::vertical()
+                                        .max_height(300.0) // Korlátozzuk a magasságot, hogy ne nyúljon túl
+                                        .show(ui, |ui| {
+                                            // IFD-nként, a bélyegkép adatai alapból összecsukva
+                                            for (ifd, rows) in exif.grouped_fields(&self.exif_search) {
+                                                egui::CollapsingHeader::new(format!("{} ({})", group_title(ifd), rows.len()))
+                                                    .id_salt(("exif_group", ifd.name()))
+                                                    .default_open(ifd != Ifd::Ifd1)
+                                                    .show(ui, |ui| {
+                                                        egui::Grid::new(("exif_group_grid", ifd.name()))
+                                                            .num_columns(2)
+                                                            .striped(true)
+                                                            .show(ui, |ui| {
+                                                                for (entry, text) in rows {
+                                                                    // Tag neve (pl. "Make", "DateTime"); az értékre mutatva a nyers JSON
+                                                                    ui.label(egui::RichText::new(format!("{}:", entry.name)).strong());
+                                                                    ui.label(text).on_hover_text(entry.value.to_string());
+                                                                    ui.end_row();
+                                                                }
+                                                            });
                                                     });
-                                                }
                                             }
                                         });
-                                    });
+                                }
+                            }
+                        }
+                        if self.displayed_image.is_some() && self.anim_data.is_none() {
+                            ui.separator();
+                            if ui.button(t!("info-exif-edit")).clicked() {
+                                toggle_editor = true;
+                            }
+                            if let Some(editor) = &mut self.exif_editor {
+                                let can_write = self.image_format == SaveFormat::Jpeg;
+                                exif_action = editor.ui(ui, self.original_image.as_ref(), can_write);
                             }
                         }
                 });
+            if toggle_editor {
+                self.toggle_exif_editor();
+            }
+            match exif_action {
+                ExifEditorAction::Apply => self.apply_exif_editor(ctx, false),
+                ExifEditorAction::WriteFile => self.apply_exif_editor(ctx, true),
+                ExifEditorAction::Close => self.exif_editor = None,
+                ExifEditorAction::None => {}
+            }
+            if revert {
+                self.revert_to_original(ctx);
+            }
+            if bake {
+                self.bake_to_new_file();
+            }
         }
 
         if self.color_correction_dialog && !self.menvar.hided {
             let mut changed = false;
+            let mut load_look = false;
+            let mut clear_look = false;
             ctx.show_viewport_immediate(
                 egui::ViewportId::from_hash_of("colorcorrection_viewport"),
                 egui::ViewportBuilder::default()
@@ -386,7 +500,7 @@ impl ImageViewer {
                         ui.visuals().text_color().linear_multiply(0.5)
                     };
                     ui.spacing_mut().item_spacing.x = 1.0; // Szoros illeszkedés a gombok között
-                    let header_btn = egui::Button::new(egui::RichText::new("iView 🔍 Color Correction").color(text_color).strong()).fill(header_color)
+                    let header_btn = egui::Button::new(egui::RichText::new(t!("color-title")).color(text_color).strong()).fill(header_color)
                         .min_size(egui::vec2(ui.available_width() - 30.0, 28.0));
                     let title_bar_response = ui.add(header_btn);
                     if title_bar_response.is_pointer_button_down_on() {
@@ -411,35 +525,35 @@ impl ImageViewer {
 
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::left_to_right(egui::Align::TOP), |ui| {
-                        ui.label("Orientation:");
+                        ui.label(t!("color-orientation"));
                         ui.style_mut().spacing.item_spacing.x = 4.0;
 
-                        if ui.button(" ⟲ ").on_hover_text("Rotate Left (90°)").clicked() {
+                        if ui.button(" ⟲ ").on_hover_text(t!("color-rotate-left")).clicked() {
                             self.color_settings.orientation.rotate_left();
                             changed = true;
                         }
-                        if ui.button(" ⟳ ").on_hover_text("Rotate Right (90°)").clicked() {
+                        if ui.button(" ⟳ ").on_hover_text(t!("color-rotate-right")).clicked() {
                             self.color_settings.orientation.rotate_right();
                             changed = true;
                         }
-                        if ui.button(" ↔ ").on_hover_text("Flip Horizontal").clicked() {
+                        if ui.button(" ↔ ").on_hover_text(t!("color-flip-h")).clicked() {
                             self.color_settings.orientation.flip_h();
                             changed = true;
                         }
-                        if ui.button(" ↕ ").on_hover_text("Flip Vertical").clicked() {
+                        if ui.button(" ↕ ").on_hover_text(t!("color-flip-v")).clicked() {
                             self.color_settings.orientation.flip_v();
                             changed = true;
                         }
 
                         if let Some(check_icon) = &self.check_mark_texture {                            
-                            if ui.add(egui::Button::image(egui::Image::new(check_icon))).on_hover_text("Stand Up 🔝").clicked() {
+                            if ui.add(egui::Button::image(egui::Image::new(check_icon))).on_hover_text(t!("color-stand-up")).clicked() {
                                 self.color_settings.orientation = Orientation::Rotate0;
                                 changed = true;
                             }
                         }
 
                         ui.add_space(10.0);
-                        ui.label("Channels:");
+                        ui.label(t!("color-channels"));
                         ui.style_mut().spacing.item_spacing.x = 2.0;
 
                         ui.style_mut().spacing.item_spacing.x = 2.0; // Szorosabb gombok
@@ -469,7 +583,7 @@ impl ImageViewer {
 
                 let gam = ui.add(egui::Slider::new(
                     &mut self.color_settings.gamma, 0.1..=3.0)
-                    .text("Gamma"));
+                    .text(t!("color-gamma")));
                 if self.gpu_interface.is_none() {
                     if gam.drag_stopped() || (gam.changed() && !ui.input(|i| i.pointer.any_down())) {
                         changed = true;
@@ -482,7 +596,7 @@ impl ImageViewer {
                 }
                 let con = ui.add(egui::Slider::new(
                     &mut self.color_settings.contrast, -1.0..=1.0)
-                    .text("Contrast"));
+                    .text(t!("color-contrast")));
                 if self.gpu_interface.is_none() {
                     if con.drag_stopped() || (con.changed() && !ui.input(|i| i.pointer.any_down())) {
                         changed = true;
@@ -498,17 +612,17 @@ impl ImageViewer {
                 //ui.label(egui::RichText::new("Hsv/Oklab Color Shift").strong());
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("Color Correction Algorithm: ").strong());
-                        if ui.radio_value(&mut self.color_settings.oklab, true, "Oklab (Natural)").clicked() {
+                        ui.label(egui::RichText::new(t!("color-algorithm")).strong());
+                        if ui.radio_value(&mut self.color_settings.oklab, true, t!("color-oklab")).clicked() {
                             changed = true;
                         }
-                        if ui.radio_value(&mut self.color_settings.oklab, false, "HSV (Classic)").clicked() {
+                        if ui.radio_value(&mut self.color_settings.oklab, false, t!("color-hsv")).clicked() {
                             changed = true;
                         }
                     });
                     let hue = ui.add(egui::Slider::new(
                         &mut self.color_settings.hue_shift, -180.0..=180.0)
-                        .text("Hue Shift"));
+                        .text(t!("color-hue")));
                     if self.gpu_interface.is_none() {
                         if hue.drag_stopped() || (hue.changed() && !ui.input(|i| i.pointer.any_down())) {
                             changed = true;
@@ -521,7 +635,7 @@ impl ImageViewer {
                     }
                     let sat = ui.add(egui::Slider::new(
                         &mut self.color_settings.saturation, -1.0..=1.0)
-                        .text("Saturation"));
+                        .text(t!("color-saturation")));
                     if self.gpu_interface.is_none() {
                         if sat.drag_stopped() || (sat.changed() && !ui.input(|i| i.pointer.any_down())) {
                             changed = true;
@@ -534,7 +648,7 @@ impl ImageViewer {
                     }
                     let bri = ui.add(egui::Slider::new(
                         &mut self.color_settings.brightness, -1.0..=1.0)
-                        .text("Brightness"));
+                        .text(t!("color-brightness")));
                     if self.gpu_interface.is_none() {
                         if bri.drag_stopped() || (bri.changed() && !ui.input(|i| i.pointer.any_down())) {
                             changed = true;
@@ -549,11 +663,11 @@ impl ImageViewer {
                 });
 
                 // --- Élesítés / Blur (GPU előkészítés) ---
-                ui.label(egui::RichText::new("Sharpen (Amount > 0) & Blur (Amount < 0)").strong());
+                ui.label(egui::RichText::new(t!("color-sharpen")).strong());
                 ui.horizontal(|ui| {
                     let res = ui.add(egui::Slider::new(
                         &mut self.color_settings.sharpen_amount, -1.0..=9.0)
-                        .text("Amount"));
+                        .text(t!("color-amount")));
                     if self.gpu_interface.is_none() {
                         if res.drag_stopped() || (res.changed() && !ui.input(|i| i.pointer.any_down())) {
                             changed = true;
@@ -564,7 +678,7 @@ impl ImageViewer {
                             changed = true;
                         }
                     }
-                    if ui.button("⟲").on_hover_text("Reset Amount").clicked() {
+                    if ui.button("⟲").on_hover_text(t!("color-reset-amount")).clicked() {
                         self.color_settings.sharpen_amount = 0.0;
                         changed = true;
                     }
@@ -573,7 +687,7 @@ impl ImageViewer {
                 ui.horizontal(|ui| {
                     let res = ui.add(egui::Slider::new(
                         &mut self.color_settings.sharpen_radius, 0.2..=7.0)
-                        .text("Radius"));
+                        .text(t!("color-radius")));
                     if self.gpu_interface.is_none() {
                         if res.drag_stopped() || (res.changed() && !ui.input(|i| i.pointer.any_down())) {
                             changed = true;
@@ -584,7 +698,7 @@ impl ImageViewer {
                             changed = true;
                         }
                     }
-                    if ui.button("⟲").on_hover_text("Reset Radius").clicked() {
+                    if ui.button("⟲").on_hover_text(t!("color-reset-radius")).clicked() {
                         self.color_settings.sharpen_radius = 0.2;
                         changed = true;
                     }
@@ -592,13 +706,13 @@ impl ImageViewer {
 
 
                 ui.group(|ui| {
-                    if ui.checkbox(&mut self.color_settings.use_transparency, "Use transparency color").changed() {
+                    if ui.checkbox(&mut self.color_settings.use_transparency, t!("color-use-transparency")).changed() {
                         changed = true;
                     };
                     ui.horizontal(|ui| {
                         let res = ui.add(egui::Slider::new(
                             &mut self.color_settings.transparency_tolerance, 0.0..=1.0)
-                            .text("Tolerance"));
+                            .text(t!("color-tolerance")));
                         if self.gpu_interface.is_none() {
                             if res.drag_stopped() || (res.changed() && !ui.input(|i| i.pointer.any_down())) {
                                 changed = true;
@@ -611,28 +725,28 @@ impl ImageViewer {
                         }
                     });
                     ui.horizontal(|ui| {
-                        ui.label("Transparent Color ");
+                        ui.label(t!("color-transparent"));
                         let color = self.color_settings.transparent_color;
                         
                         let (rect, _) = ui.allocate_exact_size(egui::vec2(26.0, 16.0), egui::Sense::hover());
                         let col = egui::Color32::from_rgba_unmultiplied(color[0], color[1], color[2], 255);
                         ui.painter().rect_filled(rect, 2.0, col);
                         
-                        ui.label("Red:");
+                        ui.label(t!("color-red"));
                         let mut r_txt = format!("{}",color[0]);
                         let r_res = ui.add(egui::TextEdit::singleline(&mut r_txt).desired_width(30.0));
                         if r_res.changed() {
                             self.color_settings.transparent_color[0] = r_txt.parse::<u8>().unwrap_or(color[0]);
                             changed=true;
                         }
-                        ui.label("Green:");
+                        ui.label(t!("color-green"));
                         let mut g_txt = format!("{}",color[1]);
                         let g_res = ui.add(egui::TextEdit::singleline(&mut g_txt).desired_width(30.0));
                         if g_res.changed() {
                             self.color_settings.transparent_color[1] = g_txt.parse::<u8>().unwrap_or(color[1]);
                             changed=true;
                         }
-                        ui.label("Blue:");
+                        ui.label(t!("color-blue"));
                         let mut b_txt = format!("{}",color[2]);
                         let b_res = ui.add(egui::TextEdit::singleline(&mut b_txt).desired_width(30.0));
                         if b_res.changed() {
@@ -642,6 +756,47 @@ impl ImageViewer {
                     });
                 });
 
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("3D LUT (.cube): ").strong());
+                        if let Some(look) = &self.look {
+                            if ui.checkbox(&mut self.color_settings.use_look, look.title.as_str())
+                                .on_hover_text(look.path.display().to_string()).changed() {
+                                changed = true;
+                            }
+                        }
+                        else {
+                            ui.label(t!("color-lut-none"));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let res = ui.add_enabled(self.look.is_some(), egui::Slider::new(
+                            &mut self.color_settings.look_intensity, 0.0..=1.0)
+                            .text(t!("color-intensity")));
+                        if self.gpu_interface.is_none() {
+                            if res.drag_stopped() || (res.changed() && !ui.input(|i| i.pointer.any_down())) {
+                                changed = true;
+                            }
+                        }
+                        else {
+                            if res.changed() {
+                                changed = true;
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("color-lut-load")).clicked() {
+                            load_look = true;
+                        }
+                        if ui.add_enabled(self.look.is_some(), egui::Button::new(t!("color-lut-remove"))).clicked() {
+                            clear_look = true;
+                        }
+                        if ui.button(t!("color-lut-export")).on_hover_text(t!("color-lut-export-hint")).clicked() {
+                            self.export_look_dialog();
+                        }
+                    });
+                });
+
                 if self.hist.len() == 1024 {
                 let max_val = if self.show_rgb_histogram {
                     self.hist[0..768].iter().cloned().max().unwrap_or(1) as f32
@@ -651,9 +806,9 @@ impl ImageViewer {
                 if max_val >= 1.0 {// Üres hisztogram védelem
                 ui.group(|ui| {
                     ui.horizontal( |ui| {
-                        ui.label("Histogram           ");
-                        ui.checkbox(&mut self.show_rgb_histogram, "RGB Mode");
-                        ui.checkbox(&mut self.use_log_scale, "Logaritmic Scale");
+                        ui.label(format!("{}           ", t!("color-histogram")));
+                        ui.checkbox(&mut self.show_rgb_histogram, t!("color-rgb-mode"));
+                        ui.checkbox(&mut self.use_log_scale, t!("color-log-scale"));
                     });
                     egui::Frame::group(ui.style()).fill(egui::Color32::from_rgb(200, 200, 200)).show(ui, |ui| {
                         // 1. Lefoglalunk egy fix területet (pl. 120px magas)
@@ -724,11 +879,11 @@ impl ImageViewer {
                                 #[allow(deprecated)]
                                 egui::show_tooltip(ui.ctx(), ui.layer_id(), egui::Id::new("hist_tooltip"), |ui: &mut egui::Ui| {
                                     ui.set_width(65.0);
-                                    ui.label(format!("Level: {}", bin));
+                                    ui.label(t!("color-level", level = bin));
                                     ui.colored_label(egui::Color32::RED,   format!("R:{}", self.hist[bin]));
                                     ui.colored_label(egui::Color32::GREEN, format!("G:{}", self.hist[256 + bin]));
                                     ui.colored_label(egui::Color32::BLUE,  format!("B:{}", self.hist[512 + bin]));
-                                    ui.colored_label(egui::Color32::LIGHT_GRAY,  format!("Gray:{}", self.hist[768 + bin]));
+                                    ui.colored_label(egui::Color32::LIGHT_GRAY,  format!("{}:{}", t!("color-gray"), self.hist[768 + bin]));
                                 });
                                 // Függőleges vonal rajzolása az egérnél
                                 painter.line_segment(
@@ -744,13 +899,13 @@ impl ImageViewer {
 
                 ui.add_space(10.0);
                 ui.horizontal(|ui| {
-                    if ui.button("Reset All Settings").clicked() {
+                    if ui.button(t!("color-reset-all")).clicked() {
                         self.color_settings = ColorSettings::default();
                         changed = true;
                     }
                     ui.add_space(160.0); 
 
-                    let btn = ui.add(egui::Button::new("Show Original (Shift+Alt)"));
+                    let btn = ui.add(egui::Button::new(t!("color-show-original")));
                     
                     let keys_active = (btn.contains_pointer() && ui.input(|i| i.pointer.any_down())) 
                             || ctx.input(|i| i.modifiers.shift && i.modifiers.alt);
@@ -767,14 +922,57 @@ impl ImageViewer {
                 }
             });
             });
-            if changed {
+            if load_look {
+                self.load_look_dialog(ctx);
+            }
+            else if clear_look {
+                self.clear_look(ctx);
+            }
+            else if changed {
                 self.review(ctx, true, false);
             }
         }
         
 
+        self.auto_rotate_window(ctx);
+        self.error_window(ctx);
+
         self.after_all_menus(ctx);
 
     }
 
+    /// Hiba megjelenítése felugró ablakban (és a konzolon); a még nyitott üzenethez hozzáfűzi
+    pub fn show_error(&mut self, message: String) {
+        println!("{}", message);
+        match &mut self.error_message {
+            Some(shown) => {
+                shown.push('\n');
+                shown.push_str(&message);
+            }
+            None => self.error_message = Some(message),
+        }
+    }
+
+    fn error_window(&mut self, ctx: &egui::Context) {
+        let Some(message) = &self.error_message else {
+            return;
+        };
+        let mut close = false;
+        egui::Window::new(t!("error-title"))
+            .collapsible(false)
+            .resizable(false)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.viewport_rect().center())
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(message.as_str()).color(egui::Color32::RED));
+                ui.add_space(6.0);
+                if ui.button(t!("error-ok")).clicked() {
+                    close = true;
+                }
+            });
+        if close {
+            self.error_message = None;
+        }
+    }
+
 }
\ No newline at end of file
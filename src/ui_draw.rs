@@ -2,6 +2,11 @@ use crate::image_processing::*;
 use crate::ui_elements::*;
 use crate::ImageViewer;
 use crate::pf32::*;
+use crate::ui_browser::FILMSTRIP_HEIGHT;
+use crate::compare::*;
+use crate::crop::CROP_BAR_HEIGHT;
+use crate::file_handlers::{WindowMode, ViewFit};
+use iview::t;
 
 
 impl ImageViewer {
@@ -18,6 +23,31 @@ impl ImageViewer {
         ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
     }
 
+    /// Az ablak a kép méretét követi (nem átméretezhető, nem teljes képernyő)
+    pub fn window_follows_image(&self) -> bool {
+        self.window_mode == WindowMode::FollowImage && !self.fullscreen
+    }
+
+    /// Az ablak állapota az ablak mód és a teljes képernyő szerint
+    pub fn apply_window_mode(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.fullscreen));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Resizable(self.window_mode != WindowMode::FollowImage));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(self.window_mode == WindowMode::Maximized));
+        self.menvar.fullscreen = self.fullscreen;
+        self.inner_size = Pf32::pf32(0.0, 0.0); // a következő rajzolás újraméretez
+        self.want_magnify = -1.0;
+    }
+
+    pub fn set_window_mode(&mut self, ctx: &egui::Context, mode: WindowMode) {
+        self.window_mode = mode;
+        self.apply_window_mode(ctx);
+    }
+
+    pub fn toggle_fullscreen(&mut self, ctx: &egui::Context) {
+        self.fullscreen = !self.fullscreen;
+        self.apply_window_mode(ctx);
+    }
+
     pub fn draw_image_area(&mut self, ctx: &egui::Context){
         
         egui::CentralPanel::default()
@@ -30,18 +60,44 @@ impl ImageViewer {
                 
                 let display_size: Pf32 = ctx.input(|i| i.viewport().monitor_size.unwrap()).into();
                 let window_outer_frame = Pf32::pf32(16.0,50.0);
-                let window_inner_frame = Pf32::pf32(6.0,30.0);
-                let display_size_netto = (display_size - window_outer_frame - window_inner_frame).floor();
+                let filmstrip = if self.show_filmstrip { FILMSTRIP_HEIGHT } else { 0.0 };
+                let compare_info = if self.compare.is_some() { COMPARE_INFO_HEIGHT } else { 0.0 };
+                let crop_bar = if self.crop_tool { CROP_BAR_HEIGHT } else { 0.0 };
+                let window_inner_frame = Pf32::pf32(6.0,30.0 + filmstrip + compare_info + crop_bar);
+                let follow = self.window_follows_image();
+                let display_size_netto = if follow {
+                    (display_size - window_outer_frame - window_inner_frame).floor()
+                } else {
+                    // átméretezhető ablak, teljes képernyő: a kép a rendelkezésre álló helyhez igazodik
+                    let area: Pf32 = ui.available_size().into();
+                    area.floor()
+                };
+                if !follow && self.view_fit != ViewFit::Center && self.inner_size != display_size_netto {
+                    self.want_magnify = -1.0; // átméretezték az ablakot
+                }
+                // összehasonlításnál két kép egymás mellett, a nagyobbik méretével
+                let panes = self.compare_panes() as f32;
+                let pane_netto = Pf32::pf32((display_size_netto.x / panes).floor(), display_size_netto.y);
+                let content_size = match &self.compare {
+                    Some(compare) => self.image_size.max(compare.image_size),
+                    None => self.image_size,
+                };
                 let mut bigger = 1.0;
                 
                 if self.want_magnify == -1.0 { // set size to fit
-                    let ratio = display_size_netto / self.image_size; // divide by tags
-                    self.magnify = ratio.x.min(ratio.y);
+                    let ratio = pane_netto / content_size; // divide by tags
+                    let fill = !follow && self.view_fit == ViewFit::Fill;
+                    self.magnify = if fill { ratio.x.max(ratio.y) } else { ratio.x.min(ratio.y) };
+                    if !follow && self.view_fit == ViewFit::Center {
+                        self.magnify = 1.0;
+                    }
 
                     if !self.rgba_image.is_some() {
                         self.magnify *= 0.5; // empty window
                     }
-                    self.magnify = (((self.magnify * 20.0 ) as i32) as f32) / 20.0;
+                    // kitöltésnél felfelé kerekítünk, hogy ne maradjon csík
+                    let steps = self.magnify * 20.0;
+                    self.magnify = (if fill { steps.ceil() } else { steps.floor() }) / 20.0;
                 }
 
                 let old_magnify = self.magnify;
@@ -67,9 +123,13 @@ impl ImageViewer {
                 let zero:Pf32 = (0.0, 0.0).into();
                 let mut new_offset = Pf32 { x: 0.0, y: 0.0 };
                 
-                let new_image_size = (self.image_size * self.magnify).floor();
-                let inner_size = new_image_size.min(display_size_netto);
+                let new_image_size = (content_size * self.magnify).floor();
+                let pane_size = new_image_size.min(pane_netto);
+                let inner_size = if follow { Pf32::pf32(pane_size.x * panes, pane_size.y) } else { display_size_netto };
                 let pos = (if self.center { (display_size_netto - inner_size) * 0.5 } else { zero }).floor();
+                // a helynél kisebb kép középre kerül
+                let margin = if follow { zero } else { ((pane_netto - new_image_size) * 0.5).max(zero).floor() };
+                let margin_v: egui::Vec2 = margin.into();
 
                 /*if bigger != 1.0  || self.want_magnify == -1.0 {
                     println!("{:?} {:?}",self.inner_size, inner_size);
@@ -85,10 +145,10 @@ impl ImageViewer {
                 }*/
 
 
-                if self.inner_size != inner_size {
+                if follow && self.inner_size != inner_size {
                     ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize((inner_size+window_inner_frame).into()));
                 }
-                if self.set_pos && self.inner_size != inner_size {
+                if follow && self.set_pos && self.inner_size != inner_size {
                     ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos.into()));
                 }
 
@@ -108,13 +168,21 @@ impl ImageViewer {
                 }*/
 
 
-                if let Some(tex) = self.texture.as_ref() {
+                if !self.tiles.is_empty() {
                 
                     //let old_image_size:Pf32 = ui_rect.max.into() - ui_rect.min.into();
                
-                    self.show_title(ctx,None);
+                    let title_txt = self.compare.as_ref().map(|compare| match compare.mode {
+                        CompareMode::SideBySide => "[A | B]".to_string(),
+                        CompareMode::Blink => if compare.show_second { "[B]".to_string() } else { "[A]".to_string() },
+                        CompareMode::Difference => "[|A - B|]".to_string(),
+                    });
+                    self.show_title(ctx,title_txt);
                     
-                    let output = egui::Frame::canvas(ui.style())
+                    let mut new_split_pos = None;
+                    let mut new_crop = None;
+                    let mut straighten = None;
+                    let outputs = egui::Frame::canvas(ui.style())
                         .fill(egui::Color32::TRANSPARENT)
                         .show(ui, |ui| {
 
@@ -122,21 +190,23 @@ impl ImageViewer {
 
                             //let ui_rect = ui.max_rect();
 
+                            let old_pane_size = Pf32::pf32(self.inner_size.x / panes, self.inner_size.y);
                             let mouse_pos_in_window = if self.mouse_zoom {
                                     if let Some(p) = ctx.pointer_latest_pos() {
-                                        Pf32{ x: p.x, y: p.y }.clamp(zero,self.inner_size)
-                                    } else { self.inner_size * 0.5 }
-                                } else { self.inner_size * 0.5 };
+                                        // a jobb oldali képen is a saját ablakához mérjük
+                                        Pf32{ x: p.x % old_pane_size.x.max(1.0), y: p.y }.clamp(zero,old_pane_size)
+                                    } else { old_pane_size * 0.5 }
+                                } else { old_pane_size * 0.5 };
 
                             let mut mouse_pos_in_image = mouse_pos_in_window + self.aktualis_offset; // old
                             mouse_pos_in_image *= bigger; // new
                             let offset = (mouse_pos_in_image - mouse_pos_in_window).max(zero);
                            
 
-                            if new_image_size.x > inner_size.x {
+                            if new_image_size.x > pane_size.x {
                                 new_offset.x = offset.x; // need horizontal scrollbar
                             }
-                            if new_image_size.y > inner_size.y {
+                            if new_image_size.y > pane_size.y {
                                 new_offset.y = offset.y; // need vertical scrollbar
                             }
                             /*if bigger != 1.0 || self.want_magnify == -1.0 {
@@ -146,21 +216,87 @@ impl ImageViewer {
                                 println!();
                             }*/
 
-                            let scroll_id = ui.make_persistent_id("kep_scroll");
-                            let mut scroll_area = egui::ScrollArea::both().id_salt(scroll_id).auto_shrink([false; 2]);
-
-                            if bigger != 1.0 {
-                                scroll_area = scroll_area.vertical_scroll_offset(new_offset.y).
-                                                        horizontal_scroll_offset(new_offset.x);
-                            }
+                            // két képnél mindkettő ugyanoda görget
+                            let target_offset = if bigger != 1.0 {
+                                Some(new_offset)
+                            } else if panes > 1.0 {
+                                Some(self.aktualis_offset)
+                            } else {
+                                None
+                            };
+                            let second_shown = self.compare.as_ref().is_some_and(|c| c.mode == CompareMode::Blink && c.show_second);
+                            let pane_width = ui.available_width() / panes;
+                            let pane_height = ui.available_height();
+
+                            let split = self.split_view && self.compare.is_none();
+                            let mut outputs = Vec::new();
+                            ui.horizontal(|ui| {
+                                ui.spacing_mut().item_spacing.x = 0.0;
+                                for pane in 0..panes as usize {
+                                    let second = if panes > 1.0 { pane == 1 } else { second_shown };
+                                    let scroll_id = ui.make_persistent_id(if pane == 0 { "kep_scroll" } else { "kep_scroll_b" });
+                                    let mut scroll_area = egui::ScrollArea::both().id_salt(scroll_id).auto_shrink([false; 2]);
+                                    if let Some(target) = target_offset {
+                                        scroll_area = scroll_area.vertical_scroll_offset(target.y).
+                                                                horizontal_scroll_offset(target.x);
+                                    }
 
-                            let scroll_output  = scroll_area.show(ui, |ui2| {
-                                ui2.add(egui::Image::from_texture(tex).fit_to_exact_size(new_image_size.into()));
+                                    let scroll_output = ui.allocate_ui(egui::vec2(pane_width, pane_height), |ui| {
+                                        scroll_area.show(ui, |ui2| {
+                                            let (rect, _) = ui2.allocate_exact_size((new_image_size + margin * 2.0).into(), egui::Sense::hover());
+                                            let first = (&self.tiles, self.image_size);
+                                            let (tiles, image_size) = match &self.compare {
+                                                Some(compare) => compare.shown(first, second),
+                                                None => first,
+                                            };
+                                            let image_rect = egui::Rect::from_min_size(rect.min + margin_v, (image_size * self.magnify).floor().into());
+                                            if !self.draw_transition(ui2, image_rect, tiles) {
+                                                tiles.paint(ui2.painter(), image_rect, self.magnify);
+                                            }
+                                            if split {
+                                                if let Some(pos) = self.draw_split(ui2, image_rect, tiles) {
+                                                    new_split_pos = Some(pos);
+                                                }
+                                            }
+                                            if self.compare.is_none() && self.horizon_tool {
+                                                if let Some(angle) = self.draw_horizon(ui2, image_rect) {
+                                                    straighten = Some(angle);
+                                                }
+                                            } else if self.compare.is_none() {
+                                                if let Some(crop) = self.draw_crop(ui2, image_rect) {
+                                                    new_crop = Some(crop);
+                                                }
+                                            }
+                                        })
+                                    }).inner;
+                                    outputs.push((scroll_output, second));
+                                }
                             });
-                            scroll_output
+                            outputs
                         }).inner;
-                        
-                    self.aktualis_offset = output.state.offset.into(); // correct with manual scroll
+
+                    if let Some(pos) = new_split_pos {
+                        self.split_pos = pos;
+                    }
+                    if let Some(crop) = new_crop {
+                        self.color_settings.crop = Some(crop);
+                    }
+                    if let Some(angle) = straighten {
+                        self.horizon_tool = false;
+                        self.set_fine_rotation(ctx, self.color_settings.rotation + angle);
+                    }
+
+                    // kézi görgetés: amelyik kép eltért a közös pozíciótól, az viszi a másikat is
+                    self.aktualis_offset = outputs[0].0.state.offset.into(); // correct with manual scroll
+                    if panes > 1.0 {
+                        let common = self.aktualis_offset;
+                        for (output, _) in &outputs {
+                            let offset: Pf32 = output.state.offset.into();
+                            if offset != common {
+                                self.aktualis_offset = offset;
+                            }
+                        }
+                    }
 
                     /*let keys_active = !self.color_correction_dialog && ctx.input(|i| i.modifiers.shift && i.modifiers.alt);                    
                     if (keys_active && !self.show_original_only) || (!keys_active && self.show_original_only) {
@@ -171,13 +307,22 @@ impl ImageViewer {
                     // Csak akkor fut le, ha a Ctrl le van nyomva
                     if ctx.input(|i| i.modifiers.ctrl ) {
                         if let Some(pointer_pos) = ctx.pointer_latest_pos() {
-                            let inner_rect = output.inner_rect;
-                            if inner_rect.contains(pointer_pos) {
-                                let relative_pos = pointer_pos - inner_rect.min + output.state.offset;
+                            // a kép körüli üres sáv nem számít
+                            let in_image = |o: &egui::scroll_area::ScrollAreaOutput<()>| {
+                                let p = pointer_pos - o.inner_rect.min + o.state.offset - margin_v;
+                                o.inner_rect.contains(pointer_pos) && p.x >= 0.0 && p.y >= 0.0
+                            };
+                            if let Some((output, second)) = outputs.iter().find(|(o, _)| in_image(o)) {
+                                let relative_pos = pointer_pos - output.inner_rect.min + output.state.offset - margin_v;
                                 let pixel_x = (relative_pos.x / self.magnify) as u32;
                                 let pixel_y = (relative_pos.y / self.magnify) as u32;
 
-                                if let Some(color) = self.pick_color(pixel_x, pixel_y) {
+                                // összehasonlításnál mindkét kép színe ugyanabban a pontban
+                                let color_a = self.pick_color(pixel_x, pixel_y);
+                                let color_b = self.compare.as_ref().and_then(|c| c.pick_color(pixel_x, pixel_y));
+                                let picked = if *second { color_b } else { color_a };
+
+                                if color_a.is_some() || color_b.is_some() {
                                     let tooltip_id = egui::Id::new("pixel_info");
                                     #[allow(deprecated)]     
                                     egui::show_tooltip_at(
@@ -186,19 +331,17 @@ impl ImageViewer {
                                         tooltip_id,
                                         ctx.pointer_latest_pos().unwrap_or(egui::Pos2::ZERO) + egui::vec2(20.0, 20.0),
                                         |ui: &mut egui::Ui| {
-                                            ui.horizontal(|ui: &mut egui::Ui| {
-                                                ui.label(format!("Pos: {}, {} ", pixel_x, pixel_y));
-                                                let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
-                                                ui.painter().rect_filled(rect, 2.0, color);
-                                                if color.a() != 255 {
-                                                    ui.label(format!("Rgba: {}, {}, {}, {}", color.r(), color.g(), color.b(), color.a()));
-                                                }
-                                                else {
-                                                    ui.label(format!("Rgb: {}, {}, {}", color.r(), color.g(), color.b()));
-                                                }
-                                            });
+                                            ui.label(format!("Pos: {}, {} ", pixel_x, pixel_y));
+                                            if self.compare.is_some() {
+                                                color_row(ui, "A:", color_a);
+                                                color_row(ui, "B:", color_b);
+                                            } else {
+                                                color_row(ui, "", color_a);
+                                            }
                                         }
                                     );
+                                }
+                                if let Some(color) = picked {
                                     if ctx.input(|i| i.pointer.primary_clicked()) {
                                         // Átváltjuk a színt f32-re a shader/korrekció számára
                                         self.color_settings.transparent_color = [
@@ -222,18 +365,18 @@ impl ImageViewer {
                     ui.vertical_centered(|ui| {
                         ui.add_space(ui.max_rect().height() / 3.0); // Kicsit feljebb a közepénél
 
-                        label_with_shadow(ui, "IView - No image opened", 24.0);
+                        label_with_shadow(ui, &t!("view-no-image"), 24.0);
 
                         ui.add_space(10.0);
                         label_with_shadow(
                             ui,
-                            "Drag & Drop a file or select it from the File menu!",
+                            &t!("view-drop-hint"),
                             20.0,
                         );
 
                         if !self.config.recent_files.is_empty() {
                             ui.add_space(20.0);
-                            label_with_shadow(ui, "Choose from the latests", 20.0);
+                            label_with_shadow(ui, &t!("view-choose-recent"), 20.0);
                             // Itt akár listázhatod is a legutóbbi 3-at gombként...
                         }
                     });
@@ -243,6 +386,95 @@ impl ImageViewer {
                 self.change_magnify = 0.0;
                 self.inner_size = inner_size;
             });
+
+        // betöltés folyamatban: a régi kép fölött jelezzük
+        if let Some(pending) = &self.pending_load {
+            let name = pending.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let elapsed = pending.started.elapsed().as_secs_f32();
+            egui::Area::new(egui::Id::new("loading_overlay"))
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .order(egui::Order::Foreground)
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::Spinner::new().size(20.0));
+                            ui.label(t!("view-loading", name = name, seconds = format!("{:.1}", elapsed)));
+                        });
+                        ui.label(t!("view-loading-cancel"));
+                    });
+                });
+        }
+    }
+
+    /// Előtte/utána: az elválasztó előtti részre a korrekció nélküli kép, és a húzható elválasztó.
+    /// Visszaadja az elválasztó új helyét, ha elhúzták.
+    fn draw_split(&self, ui: &mut egui::Ui, image_rect: egui::Rect,
+                  after: &TileSet) -> Option<f32> {
+        let view = ui.clip_rect().intersect(image_rect);
+        if view.width() <= 0.0 || view.height() <= 0.0 {
+            return None;
+        }
+        // korrekció nélkül a két oldal azonos
+        let tiles = if self.before_tiles.is_empty() { after } else { &self.before_tiles };
+        let (before_clip, handle, line) = if self.split_vertical {
+            let x = view.min.x + view.width() * self.split_pos;
+            (egui::Rect::from_min_max(view.min, egui::pos2(x, view.max.y)),
+             egui::Rect::from_min_max(egui::pos2(x - 4.0, view.min.y), egui::pos2(x + 4.0, view.max.y)),
+             [egui::pos2(x, view.min.y), egui::pos2(x, view.max.y)])
+        } else {
+            let y = view.min.y + view.height() * self.split_pos;
+            (egui::Rect::from_min_max(view.min, egui::pos2(view.max.x, y)),
+             egui::Rect::from_min_max(egui::pos2(view.min.x, y - 4.0), egui::pos2(view.max.x, y + 4.0)),
+             [egui::pos2(view.min.x, y), egui::pos2(view.max.x, y)])
+        };
+        tiles.paint(&ui.painter().with_clip_rect(before_clip), image_rect, self.magnify);
+
+        let painter = ui.painter();
+        painter.line_segment(line, egui::Stroke::new(3.0, egui::Color32::from_black_alpha(160)));
+        painter.line_segment(line, egui::Stroke::new(1.0, egui::Color32::WHITE));
+        let font = egui::FontId::proportional(14.0);
+        let (before_pos, before_align, after_pos, after_align) = if self.split_vertical {
+            (line[0] + egui::vec2(-6.0, 4.0), egui::Align2::RIGHT_TOP, line[0] + egui::vec2(6.0, 4.0), egui::Align2::LEFT_TOP)
+        } else {
+            (line[0] + egui::vec2(4.0, -4.0), egui::Align2::LEFT_BOTTOM, line[0] + egui::vec2(4.0, 4.0), egui::Align2::LEFT_TOP)
+        };
+        painter.text(before_pos, before_align, t!("view-before"), font.clone(), egui::Color32::WHITE);
+        painter.text(after_pos, after_align, t!("view-after"), font, egui::Color32::WHITE);
+
+        let cursor = if self.split_vertical { egui::CursorIcon::ResizeHorizontal } else { egui::CursorIcon::ResizeVertical };
+        let response = ui.interact(handle, ui.id().with("split_divider"), egui::Sense::drag()).on_hover_cursor(cursor);
+        if response.dragged() {
+            if let Some(p) = response.interact_pointer_pos() {
+                let pos = if self.split_vertical {
+                    (p.x - view.min.x) / view.width()
+                } else {
+                    (p.y - view.min.y) / view.height()
+                };
+                return Some(pos.clamp(0.0, 1.0));
+            }
+        }
+        None
     }
 
-}
\ No newline at end of file
+}
+
+/// Pixel színe a Ctrl-os tooltipben: minta és számok
+fn color_row(ui: &mut egui::Ui, label: &str, color: Option<egui::Color32>) {
+    ui.horizontal(|ui: &mut egui::Ui| {
+        if !label.is_empty() {
+            ui.label(label);
+        }
+        let Some(color) = color else {
+            ui.label("-");
+            return;
+        };
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, color);
+        if color.a() != 255 {
+            ui.label(format!("Rgba: {}, {}, {}, {}", color.r(), color.g(), color.b(), color.a()));
+        }
+        else {
+            ui.label(format!("Rgb: {}, {}, {}", color.r(), color.g(), color.b()));
+        }
+    });
+}
@@ -1,5 +1,6 @@
 use crate::gpu_colors::GpuInterface;
 use crate::ImageViewer;
+use iview::t;
 
 pub fn label_with_shadow(ui: &mut egui::Ui, text: &str, size: f32) {
     let font_id = egui::FontId::proportional(size);
@@ -72,15 +73,16 @@ impl ImageViewer {
         // Csak az első futáskor inicializálunk, amikor már van frame és GPU
         if self.use_gpu && !self.gpu_tried_init && self.gpu_interface.is_none() {
             if let Some(render_state) = frame.wgpu_render_state() {
-                println!("Most már van GPU állapota, indulhat a gpu_init...");
+                println!("{}", t!("gpu-init-start"));
                 if let Some(interface) = GpuInterface::gpu_init(render_state) {
+                    interface.set_look(self.look.as_ref());
                     self.gpu_interface = Some(interface);
-                    println!("GPU INTERFÉSZ KÉSZ!");
+                    println!("{}", t!("gpu-init-done"));
                 }
                 self.gpu_tried_init = true;
             }
             else {
-                println!("frame.wgpu_render_state() is None");
+                println!("{}", t!("gpu-no-render-state"));
                 self.gpu_tried_init = true;
             }
         }
@@ -1,10 +1,16 @@
 use crate::colors::*;
 use crate::file_handlers::*;
 use crate::image_processing::*;
+use crate::compare::CompareMode;
+use crate::slideshow::Transition;
+use crate::bindings::{Action, BindingsDialog};
+use crate::presets::PRESET_KEYS;
 use crate::ImageViewer;
 use crate::Menu;
 use crate::MenuVariables;
 use crate::pf32::Pf32;
+use iview::i18n::{Language, language, set_language};
+use iview::t;
 
 pub fn separator(ui: &mut egui::Ui) {
     let rect = ui.available_rect_before_wrap();
@@ -16,6 +22,14 @@ pub fn separator(ui: &mut egui::Ui) {
     ui.advance_cursor_after_rect(line_rect);
 }
 
+// teljes képernyőn ennyire kell a felső szélhez vinni az egeret a menühöz
+const MENU_REVEAL_HEIGHT: f32 = 8.0;
+
+/// Almenüt nyitó gomb felirata
+fn submenu(text: String) -> String {
+    format!("{}   >", text)
+}
+
 pub fn pos(ui: &mut egui::Ui, pos1:Pf32, pos2:Pf32 ) -> Pf32 {
     let mut pos = pos1;
     pos.x = ui.max_rect().right();
@@ -42,6 +56,9 @@ impl MenuVariables {
             Menu::Channels      => self.channels_menu_pos   + self.menu_pos,
             Menu::Backgrounds   => self.background_menu_pos + self.menu_pos,
             Menu::Zoom          => self.zoom_menu_pos       + self.menu_pos,
+            Menu::Slideshow     => self.slideshow_menu_pos  + self.menu_pos,
+            Menu::Presets       => self.presets_menu_pos    + self.menu_pos,
+            Menu::Language      => self.language_menu_pos   + self.menu_pos,
         }
     }
 
@@ -58,6 +75,9 @@ impl MenuVariables {
             Menu::Channels      => 2,
             Menu::Backgrounds   => 2,
             Menu::Zoom          => 2,
+            Menu::Slideshow     => 2,
+            Menu::Presets       => 2,
+            Menu::Language      => 2,
         }
     }
 
@@ -74,6 +94,9 @@ impl MenuVariables {
             Menu::Channels      => menu == Menu::None || menu == Menu::Options,
             Menu::Backgrounds   => menu == Menu::None || menu == Menu::Options,
             Menu::Zoom          => menu == Menu::None || menu == Menu::Options,
+            Menu::Slideshow     => menu == Menu::None || menu == Menu::Options,
+            Menu::Presets       => menu == Menu::None || menu == Menu::Options,
+            Menu::Language      => menu == Menu::None || menu == Menu::Options,
         }
     }
 
@@ -121,7 +144,15 @@ impl MenuVariables {
 
     pub fn menu_is_opened(&mut self, ctx: &egui::Context, build_menu: Menu ) -> bool {
         
-        if build_menu == Menu::None {
+        if build_menu == Menu::None && self.fullscreen {
+            // nincs címsor: a menü a képernyő tetején, csak ha az egér a felső szélen vagy a menün van
+            self.menu_pos = ctx.input(|i| i.viewport().outer_rect.unwrap_or(egui::Rect::EVERYTHING).min.into());
+            let at_top = ctx.input(|i| i.pointer.hover_pos().is_some_and(|p| p.y < MENU_REVEAL_HEIGHT));
+            if !at_top && !self.menu_hovered && self.current_menu == Menu::None {
+                return false;
+            }
+        }
+        else if build_menu == Menu::None {
             self.menu_pos = ctx.input(|i| {
                 let main_window_rect = i.viewport().outer_rect.unwrap_or(egui::Rect::EVERYTHING);
                 Pf32{x:8.0, y:32.0} + main_window_rect.min.into()
@@ -143,6 +174,7 @@ impl MenuVariables {
     
     pub fn before(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, build_menu: Menu) {
         if build_menu == Menu::None {
+            self.menu_hovered = ui.input(|i| i.pointer.has_pointer());
             if ui.input(|i| i.viewport().focused.unwrap_or(false)) {
                 if ctx.input(|i| i.viewport().focused != Some(true)) &&
                     !self.closing_menu_request && self.current_menu == Menu::None {
@@ -303,29 +335,25 @@ impl ImageViewer {
 
         // Főmenü (must first)
         show_menu!(self.menvar, ctx, Menu::None, ui, {
-            let file_btn = ui.button("File");
+            let file_btn = ui.button(t!("menu-file"));
             if file_btn.clicked() {
                 self.menvar.file_menu_pos = file_btn.rect.left_bottom().into();
                 self.menvar.change_menu(ctx,Menu::File) ;
             }
 
-            let options_btn = ui.button("Options");
+            let options_btn = ui.button(t!("menu-options"));
             if options_btn.clicked() {
                 self.menvar.options_menu_pos = options_btn.rect.left_bottom().into();
                 self.menvar.change_menu(ctx,Menu::Options);
             }
 
-            let prev_button = egui::Button::new("<<").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::B),
-            ));
+            let prev_button = egui::Button::new("<<").shortcut_text(self.bindings.text(ctx, Action::Previous));
             
             if ui.add(prev_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.navigation(ctx, -1);
             }
-            let next_button = egui::Button::new(">>").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::N),
-            ));
+            let next_button = egui::Button::new(">>").shortcut_text(self.bindings.text(ctx, Action::Next));
             if ui.add(next_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.navigation(ctx, 1);
@@ -336,9 +364,9 @@ impl ImageViewer {
                 separator(ui);
                 
                 let play_btn = if self.anim_playing {
-                    "⏸ Stop"
+                    t!("menu-anim-stop")
                 } else {
-                    "▶ Play"
+                    t!("menu-anim-play")
                 };
                 if ui.button(play_btn).clicked()
                     || ui.input(|i| i.key_pressed(egui::Key::Space))
@@ -364,38 +392,56 @@ impl ImageViewer {
                     self.menvar.change_menu(ctx,Menu::None);
                     self.anim_next_frame(ctx);
                 }
-                ui.label(format!(
-                    "Frame: {} / {}",
-                    self.current_frame + 1,
-                    self.total_frames
-                ));
+                ui.label(t!("menu-anim-frame", frame = self.current_frame + 1, total = self.total_frames));
             }
         });
 
         // File menü
         show_menu!(self.menvar, ctx, Menu::File, ui, {
             let open_button =
-                egui::Button::new("Open ...").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::O),
-                ));
+                egui::Button::new(t!("menu-open")).shortcut_text(self.bindings.text(ctx, Action::Open));
             if ui.add(open_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.open_image_dialog(ctx, &None);
             }
 
             let reopen_button =
-                egui::Button::new("Reopen").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::R),
-                ));
+                egui::Button::new(t!("menu-reopen")).shortcut_text(self.bindings.text(ctx, Action::Reopen));
             if ui.add(reopen_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.load_image(ctx, true);
             }
 
+            let compare_button =
+                egui::Button::new(t!("menu-compare")).shortcut_text(self.bindings.text(ctx, Action::Compare));
+            if ui.add(compare_button).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.open_compare_dialog(ctx);
+            }
+
+            if ui.button(t!("menu-compare-clipboard")).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.open_compare_clipboard(ctx);
+            }
+
+            if let Some(compare) = &self.compare {
+                let mode = compare.mode;
+                if ui.selectable_label(mode == CompareMode::Blink, t!("menu-blink-compare")).clicked() {
+                    self.menvar.change_menu(ctx,Menu::None);
+                    self.set_compare_mode(ctx, if mode == CompareMode::Blink { CompareMode::SideBySide } else { CompareMode::Blink });
+                }
+                if ui.selectable_label(mode == CompareMode::Difference, t!("menu-pixel-difference")).clicked() {
+                    self.menvar.change_menu(ctx,Menu::None);
+                    self.set_compare_mode(ctx, if mode == CompareMode::Difference { CompareMode::SideBySide } else { CompareMode::Difference });
+                }
+                if ui.button(t!("menu-close-compare")).clicked() {
+                    self.menvar.change_menu(ctx,Menu::None);
+                    self.close_compare();
+                }
+            }
+
             let save_button =
-                egui::Button::new("Save as ...").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::S),
-                ));
+                egui::Button::new(t!("menu-save")).shortcut_text(self.bindings.text(ctx, Action::Save));
             if ui.add(save_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = true;
@@ -403,16 +449,35 @@ impl ImageViewer {
             }
 
             let save_button =
-                egui::Button::new("Save view as ...").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::SHIFT, egui::Key::S),
-                ));
+                egui::Button::new(t!("menu-save-view")).shortcut_text(self.bindings.text(ctx, Action::SaveView));
             if ui.add(save_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = false;
                 self.starting_save(&None);
             }
+
+            let resize_button =
+                egui::Button::new(t!("menu-resize")).shortcut_text(self.bindings.text(ctx, Action::Resize));
+            if ui.add(resize_button).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.open_resize_dialog();
+            }
+
+            let lossless = self.can_lossless_jpeg();
+            if ui.add_enabled(lossless, egui::Button::new(t!("menu-lossless-save"))).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.lossless_jpeg_in_place(ctx);
+            }
+            if ui.add_enabled(lossless, egui::Button::new(t!("menu-lossless-save-as"))).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.lossless_jpeg_save_as();
+            }
+            if ui.button(t!("menu-auto-rotate-folder")).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.auto_rotate_folder(ctx);
+            }
             
-            let recents_btn = ui.button("Recent Paths ...   >");
+            let recents_btn = ui.button(submenu(t!("menu-recent")));
             if recents_btn.clicked() {
                 self.menvar.recents_menu_pos = pos( ui, recents_btn.rect.right_top().into(), self.menvar.file_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Recents);
@@ -420,20 +485,15 @@ impl ImageViewer {
 
             separator(ui);
 
-            let copy_button = egui::Button::new("Copy").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::C),
-            ));
+            let copy_button = egui::Button::new(t!("menu-copy")).shortcut_text(self.bindings.text(ctx, Action::Copy));
             if ui.add(copy_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = true;
                 self.copy_to_clipboard();
             }
 
-            let copy_button = egui::Button::new("Copy view").shortcut_text(
-                ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
-                    egui::Key::C,
-                )),
+            let copy_button = egui::Button::new(t!("menu-copy-view")).shortcut_text(
+                self.bindings.text(ctx, Action::CopyView),
             );
             if ui.add(copy_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
@@ -442,26 +502,20 @@ impl ImageViewer {
             }
 
             let paste_button =
-                egui::Button::new("Paste").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::V),
-                ));
+                egui::Button::new(t!("menu-paste")).shortcut_text(self.bindings.text(ctx, Action::Paste));
             if ui.add(paste_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.copy_from_clipboard(ctx);
             }
 
-            let copy_button = egui::Button::new("Change").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::X),
-            ));
+            let copy_button = egui::Button::new(t!("menu-change")).shortcut_text(self.bindings.text(ctx, Action::Change));
             if ui.add(copy_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = false;
                 self.change_with_clipboard(ctx);
             }
 
-            let copy_button = egui::Button::new("Change view").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::X),
-            ));
+            let copy_button = egui::Button::new(t!("menu-change-view")).shortcut_text(self.bindings.text(ctx, Action::ChangeView));
             if ui.add(copy_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = true;
@@ -470,14 +524,12 @@ impl ImageViewer {
 
             separator(ui);
 
-            if ui.button("About IView...").clicked() {
+            if ui.button(t!("menu-about")).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.show_about_window = true;
             }
 
-            let exit_button = egui::Button::new("Exit").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Escape),
-            ));
+            let exit_button = egui::Button::new(t!("menu-exit")).shortcut_text(self.bindings.text(ctx, Action::Back));
             if ui.add(exit_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 ctx.send_viewport_cmd(egui::ViewportCommand::Close);
@@ -495,7 +547,7 @@ impl ImageViewer {
                 let file_name = path.file_name().map(|n| n.to_string_lossy())
                     .unwrap_or_default();
                 let folder_path = path.parent().map(|p| p.to_string_lossy().into_owned())
-                    .unwrap_or_else(|| "Root".to_string());
+                    .unwrap_or_else(|| t!("menu-root"));
                 let button = ui.button(&*file_name);
                 button.clone().on_hover_text(&folder_path);
                 if button.clicked() {
@@ -511,20 +563,20 @@ impl ImageViewer {
 
         // Recent file options
         show_menu!(self.menvar, ctx, Menu::RecentFile, ui, {
-            if ui.button("Open file").clicked() {
+            if ui.button(t!("menu-recent-open")).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.open_image(ctx, &self.menvar.recentfile.clone(), true);
             }
-            if ui.button("Open File Here ...").clicked() {
+            if ui.button(t!("menu-recent-open-here")).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.open_image_dialog(ctx, &Some(self.menvar.recentfile.clone()));
             }
-            if ui.button("Save Here ...").clicked() {
+            if ui.button(t!("menu-recent-save-here")).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = true;
                 self.starting_save(&Some(self.menvar.recentfile.clone()));
             }
-            if ui.button("Save View Here ...").clicked() {
+            if ui.button(t!("menu-recent-save-view-here")).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.save_original = false;
                 self.starting_save(&Some(self.menvar.recentfile.clone()));
@@ -535,83 +587,123 @@ impl ImageViewer {
 
         // Options menu
         show_menu!(self.menvar, ctx, Menu::Options, ui, {
-            let sort_btn = ui.button("Order of images         >");
+            let sort_btn = ui.button(submenu(t!("menu-sort")));
             if sort_btn.clicked() {
                 self.menvar.sort_menu_pos = pos( ui, sort_btn.rect.right_top().into(), self.menvar.options_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Sort);
             }
-            let rotate_btn = ui.button("Orientation                    >");
+            let rotate_btn = ui.button(submenu(t!("menu-orientation")));
             if rotate_btn.clicked() {
                 self.menvar.orientation_menu_pos = pos( ui, rotate_btn.rect.right_top().into(), self.menvar.options_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Orientation);
             }
-            let background_btn = ui.add(egui::Button::new("Background           >").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::G),
-                )));
+            let background_btn = ui.add(egui::Button::new(submenu(t!("menu-background"))).shortcut_text(self.bindings.text(ctx, Action::Background)));
             if background_btn.clicked() {
                 self.menvar.background_menu_pos = pos( ui, background_btn.rect.right_top().into(), self.menvar.options_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Backgrounds);
             }
-            let channels_btn = ui.button("Channels                      >");
+            let channels_btn = ui.button(submenu(t!("menu-channels")));
             if channels_btn.clicked() {
                 self.menvar.channels_menu_pos = pos( ui, channels_btn.rect.right_top().into(), self.menvar.options_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Channels);
             }
-            let zoom_btn = ui.button("Zoom                             >");
+            let zoom_btn = ui.button(submenu(t!("menu-zoom")));
             if zoom_btn.clicked() {
                 self.menvar.zoom_menu_pos = pos( ui, zoom_btn.rect.right_top().into(), self.menvar.options_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Zoom);
             }
-            let col_button = egui::Button::new("Color correction    >").shortcut_text(ctx.format_shortcut(
-                    &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::C),
-                ));
+            let col_button = egui::Button::new(submenu(t!("menu-color-correction"))).shortcut_text(self.bindings.text(ctx, Action::ColorCorrection));
             if ui.add(col_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.color_correction_dialog = true;
             }
-            let info_button = egui::Button::new("Info                                ").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::I),
-            ));
+            let presets_btn = ui.button(submenu(t!("menu-presets")));
+            if presets_btn.clicked() {
+                self.menvar.presets_menu_pos = pos( ui, presets_btn.rect.right_top().into(), self.menvar.options_menu_pos);
+                self.menvar.change_menu(ctx,Menu::Presets);
+            }
+            let info_button = egui::Button::new(t!("menu-info")).shortcut_text(self.bindings.text(ctx, Action::Info));
             if ui.add(info_button).clicked() {
                 self.show_info = true;
                 self.menvar.change_menu(ctx,Menu::None);
             }
-            
-            let position_btn = ui.button("Window position        >");
+
+            let undo_button = egui::Button::new(t!("menu-undo")).shortcut_text(self.bindings.text(ctx, Action::Undo));
+            if ui.add_enabled(self.history.can_undo(), undo_button).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.undo(ctx);
+            }
+            let redo_button = egui::Button::new(t!("menu-redo")).shortcut_text(self.bindings.text(ctx, Action::Redo));
+            if ui.add_enabled(self.history.can_redo(), redo_button).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.redo(ctx);
+            }
+            if ui.selectable_label(self.show_history, format!("{}{}", t!("menu-history"), self.bindings.hint(ctx, Action::History))).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.show_history = !self.show_history;
+            }
+
+            if ui.selectable_label(self.crop_tool, format!("{}{}", t!("menu-crop"), self.bindings.hint(ctx, Action::Crop))).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.toggle_crop_tool();
+            }
+
+            if ui.selectable_label(self.split_view, format!("{}{}", t!("menu-split"), self.bindings.hint(ctx, Action::SplitView))).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.toggle_split_view(ctx);
+            }
+
+            if ui.selectable_label(self.show_grid, format!("{}{}", t!("menu-grid"), self.bindings.hint(ctx, Action::ThumbnailGrid))).clicked() {
+                self.show_grid = !self.show_grid;
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+
+            if ui.selectable_label(self.show_filmstrip, format!("{}{}", t!("menu-filmstrip"), self.bindings.hint(ctx, Action::Filmstrip))).clicked() {
+                self.show_filmstrip = !self.show_filmstrip;
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+
+            let slideshow_btn = ui.add(egui::Button::new(submenu(t!("menu-slideshow"))).shortcut_text(self.bindings.text(ctx, Action::Slideshow)));
+            if slideshow_btn.clicked() {
+                self.menvar.slideshow_menu_pos = pos( ui, slideshow_btn.rect.right_top().into(), self.menvar.options_menu_pos);
+                self.menvar.change_menu(ctx,Menu::Slideshow);
+            }
+
+            let position_btn = ui.button(submenu(t!("menu-window")));
             if position_btn.clicked() {
                 self.menvar.position_menu_pos = pos( ui, position_btn.rect.right_top().into(), self.menvar.options_menu_pos);
                 self.menvar.change_menu(ctx,Menu::Position);
             }
             
-            if ui.selectable_label(self.set_pos, "Set Window Position").clicked()
+            if ui.selectable_label(self.set_pos, t!("menu-set-pos")).clicked()
             {
                 self.set_pos = !self.set_pos;
                 self.menvar.change_menu(ctx,Menu::None);
             }
 
-            if ui.selectable_label(self.refit_reopen, "Refit at Reopen").clicked()
+            if ui.selectable_label(self.refit_reopen, t!("menu-refit-reopen")).clicked()
             {
                 self.refit_reopen = !self.refit_reopen;
                 self.menvar.change_menu(ctx,Menu::None);
             }
 
-            if ui.selectable_label(self.fit_open, "Fit at Open").clicked() {
+            if ui.selectable_label(self.fit_open, t!("menu-fit-open")).clicked() {
                 self.fit_open = !self.fit_open;
                 self.menvar.change_menu(ctx,Menu::None);
             }
 
-            if ui.selectable_label(self.same_correction_open, "No Correction at Open").clicked() {
+            if ui.selectable_label(self.same_correction_open, t!("menu-no-correction")).clicked() {
                 self.same_correction_open = !self.same_correction_open;
                 self.menvar.change_menu(ctx,Menu::None);
             }
 
-            if ui.selectable_label(self.anim_loop, "Animation Loop").clicked()
+            if ui.selectable_label(self.anim_loop, t!("menu-anim-loop")).clicked()
             {
                 self.anim_loop = !self.anim_loop;
                 self.menvar.change_menu(ctx,Menu::None);
             }
             
-            if ui.selectable_label(self.use_gpu, "Use Gpu").clicked()
+            if ui.selectable_label(self.use_gpu, t!("menu-use-gpu")).clicked()
             {
                 self.use_gpu = !self.use_gpu;
                 if !self.use_gpu {
@@ -623,21 +715,32 @@ impl ImageViewer {
                 self.menvar.change_menu(ctx,Menu::None);
             }
 
+            separator(ui);
+            let language_btn = ui.button(submenu(t!("menu-language")));
+            if language_btn.clicked() {
+                self.menvar.language_menu_pos = pos( ui, language_btn.rect.right_top().into(), self.menvar.options_menu_pos);
+                self.menvar.change_menu(ctx,Menu::Language);
+            }
+            if ui.button(t!("menu-key-bindings")).clicked() {
+                self.bindings_dialog = Some(BindingsDialog::default());
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+
         });
 
         // sort menu
         show_menu!(self.menvar, ctx, Menu::Sort, ui, {
             let mut changed = false;
-            if ui.selectable_value(&mut self.sort, SortDir::Name, "by name").clicked() {
+            if ui.selectable_value(&mut self.sort, SortDir::Name, t!("menu-sort-name")).clicked() {
                 changed = true;
             }
-            if ui.selectable_value(&mut self.sort, SortDir::Ext, "by  extension").clicked() {
+            if ui.selectable_value(&mut self.sort, SortDir::Ext, t!("menu-sort-ext")).clicked() {
                 changed = true;
             }
-            if ui.selectable_value(&mut self.sort, SortDir::Date, "by date").clicked() {
+            if ui.selectable_value(&mut self.sort, SortDir::Date, t!("menu-sort-date")).clicked() {
                 changed = true;
             }
-            if ui.selectable_value(&mut self.sort, SortDir::Size, "by syze").clicked() {
+            if ui.selectable_value(&mut self.sort, SortDir::Size, t!("menu-sort-size")).clicked() {
                 changed = true;
             }
             if changed {
@@ -650,105 +753,170 @@ impl ImageViewer {
         // position menu
         show_menu!(self.menvar, ctx, Menu::Position, ui, {
             let mut changed = false;
-            if ui.selectable_value(&mut self.center, false, "Left Up").clicked() {
+            if ui.selectable_value(&mut self.center, false, t!("menu-pos-left-up")).clicked() {
                 changed = true;
             }
-            if ui.selectable_value(&mut self.center, true, "Center").clicked() {
+            if ui.selectable_value(&mut self.center, true, t!("menu-pos-center")).clicked() {
                 changed = true;
             }
             if changed {
                 self.load_image(ctx, false);
                 self.menvar.change_menu(ctx,Menu::None);
             }
+            separator(ui);
+            let mut mode = self.window_mode;
+            ui.selectable_value(&mut mode, WindowMode::FollowImage, t!("menu-window-follow"));
+            ui.selectable_value(&mut mode, WindowMode::Resizable, t!("menu-window-resizable"));
+            ui.selectable_value(&mut mode, WindowMode::Maximized, t!("menu-window-maximized"));
+            if mode != self.window_mode {
+                self.set_window_mode(ctx, mode);
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+            separator(ui);
+            let mut fit = self.view_fit;
+            ui.selectable_value(&mut fit, ViewFit::Fit, t!("menu-fit"));
+            ui.selectable_value(&mut fit, ViewFit::Fill, t!("menu-fill"));
+            ui.selectable_value(&mut fit, ViewFit::Center, t!("menu-center-1-1"));
+            if fit != self.view_fit {
+                self.view_fit = fit;
+                self.want_magnify = -1.0;
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+            separator(ui);
+            if ui.selectable_label(self.fullscreen, format!("{}{}", t!("menu-fullscreen"), self.bindings.hint(ctx, Action::Fullscreen))).clicked() {
+                self.toggle_fullscreen(ctx);
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+        });
+
+        // slideshow menu
+        show_menu!(self.menvar, ctx, Menu::Slideshow, ui, {
+            let label = if self.slideshow.is_some() { t!("menu-slideshow-stop") } else { t!("menu-slideshow-start") };
+            if ui.add(egui::Button::new(label).shortcut_text(self.bindings.text(ctx, Action::Slideshow))).clicked() {
+                self.toggle_slideshow();
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+            separator(ui);
+            let settings = &mut self.slideshow_settings;
+            ui.add(egui::Slider::new(&mut settings.interval, 1.0..=60.0).text(t!("menu-interval")));
+            ui.checkbox(&mut settings.shuffle, t!("menu-shuffle"));
+            ui.checkbox(&mut settings.repeat, t!("menu-loop"));
+            separator(ui);
+            ui.selectable_value(&mut settings.transition, Transition::None, t!("menu-no-transition"));
+            ui.selectable_value(&mut settings.transition, Transition::CrossFade, t!("menu-cross-fade"));
+            ui.selectable_value(&mut settings.transition, Transition::Slide, t!("menu-slide"));
+            ui.add_enabled(settings.transition != Transition::None,
+                egui::Slider::new(&mut settings.transition_time, 0.1..=3.0).text(t!("menu-transition-time")));
+        });
+
+        // presets menu
+        show_menu!(self.menvar, ctx, Menu::Presets, ui, {
+            if ui.button(t!("menu-preset-save")).clicked() {
+                self.preset_name = Some(String::new());
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+            separator(ui);
+            let mut apply = None;
+            let mut delete = None;
+            for (index, preset) in self.presets.iter().enumerate() {
+                let mut button = egui::Button::new(preset.name.as_str());
+                if index < PRESET_KEYS.len() {
+                    button = button.shortcut_text(self.bindings.text(ctx, Action::Preset(index as u8)));
+                }
+                let response = ui.add(button).on_hover_text(t!("menu-preset-delete-hint"));
+                if response.clicked() {
+                    apply = Some(index);
+                }
+                if response.secondary_clicked() {
+                    delete = Some(index);
+                }
+            }
+            if let Some(index) = apply {
+                self.apply_preset(ctx, index);
+                self.menvar.change_menu(ctx,Menu::None);
+            }
+            if let Some(index) = delete {
+                self.delete_preset(index);
+            }
+            if !self.presets.is_empty() {
+                separator(ui);
+            }
+            if ui.button(t!("menu-import")).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.import_presets_dialog();
+            }
+            if ui.add_enabled(!self.presets.is_empty(), egui::Button::new(t!("menu-export"))).clicked() {
+                self.menvar.change_menu(ctx,Menu::None);
+                self.export_presets_dialog();
+            }
         });
 
         // zoom menu
         show_menu!(self.menvar, ctx, Menu::Zoom, ui, {
             let mut need = -2.0;
-            if ui.add(egui::Button::new("Fit").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F)))).clicked() {
+            if ui.add(egui::Button::new(t!("menu-zoom-fit")).shortcut_text(self.bindings.text(ctx, Action::ZoomFit))).clicked() {
                 need = -1.0;
             }
             separator(ui);
-            if ui.add(egui::Button::new("1:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num1)))).clicked() {
+            if ui.add(egui::Button::new("1:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(100)))).clicked() {
                 need = 1.0;
             }
-            if ui.add(egui::Button::new("2:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num2)))).clicked() {
+            if ui.add(egui::Button::new("2:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(200)))).clicked() {
                 need = 2.0;
             }
-            if ui.add(egui::Button::new("3:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num3)))).clicked() {
+            if ui.add(egui::Button::new("3:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(300)))).clicked() {
                 need = 3.0;
             }
-            if ui.add(egui::Button::new("4:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num4)))).clicked() {
+            if ui.add(egui::Button::new("4:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(400)))).clicked() {
                 need = 4.0;
             }
-            if ui.add(egui::Button::new("5:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num5)))).clicked() {
+            if ui.add(egui::Button::new("5:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(500)))).clicked() {
                 need = 5.0;
             }
-            if ui.add(egui::Button::new("6:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num6)))).clicked() {
+            if ui.add(egui::Button::new("6:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(600)))).clicked() {
                 need = 6.0;
             }
-            if ui.add(egui::Button::new("7:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num7)))).clicked() {
+            if ui.add(egui::Button::new("7:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(700)))).clicked() {
                 need = 7.0;
             }
-            if ui.add(egui::Button::new("8:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num8)))).clicked() {
+            if ui.add(egui::Button::new("8:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(800)))).clicked() {
                 need = 8.0;
             }
-            if ui.add(egui::Button::new("9:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num9)))).clicked() {
+            if ui.add(egui::Button::new("9:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(900)))).clicked() {
                 need = 9.0;
             }
-            if ui.add(egui::Button::new("10:1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Num0)))).clicked() {
+            if ui.add(egui::Button::new("10:1").shortcut_text(self.bindings.text(ctx, Action::Zoom(1000)))).clicked() {
                 need = 10.0;
             }
             separator(ui);
-            if ui.add(egui::Button::new("0.8").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num1)))).clicked() {
+            if ui.add(egui::Button::new("0.8").shortcut_text(self.bindings.text(ctx, Action::Zoom(80)))).clicked() {
                 need = 0.8;
             }
-            if ui.add(egui::Button::new("0.75").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num2)))).clicked() {
+            if ui.add(egui::Button::new("0.75").shortcut_text(self.bindings.text(ctx, Action::Zoom(75)))).clicked() {
                 need = 0.75;
             }
-            if ui.add(egui::Button::new("0.5").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num3)))).clicked() {
+            if ui.add(egui::Button::new("0.5").shortcut_text(self.bindings.text(ctx, Action::Zoom(50)))).clicked() {
                 need = 0.5;
             }
-            if ui.add(egui::Button::new("0.45").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num4)))).clicked() {
+            if ui.add(egui::Button::new("0.45").shortcut_text(self.bindings.text(ctx, Action::Zoom(45)))).clicked() {
                 need = 0.45;
             }
-            if ui.add(egui::Button::new("0.4").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num5)))).clicked() {
+            if ui.add(egui::Button::new("0.4").shortcut_text(self.bindings.text(ctx, Action::Zoom(40)))).clicked() {
                 need = 0.4;
             }
-            if ui.add(egui::Button::new("0.35").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num6)))).clicked() {
+            if ui.add(egui::Button::new("0.35").shortcut_text(self.bindings.text(ctx, Action::Zoom(35)))).clicked() {
                 need = 0.35;
             }
-            if ui.add(egui::Button::new("0.3").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num7)))).clicked() {
+            if ui.add(egui::Button::new("0.3").shortcut_text(self.bindings.text(ctx, Action::Zoom(30)))).clicked() {
                 need = 0.3;
             }
-            if ui.add(egui::Button::new("0.25").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num8)))).clicked() {
+            if ui.add(egui::Button::new("0.25").shortcut_text(self.bindings.text(ctx, Action::Zoom(25)))).clicked() {
                 need = 0.25;
             }
-            if ui.add(egui::Button::new("0.2").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num9)))).clicked() {
+            if ui.add(egui::Button::new("0.2").shortcut_text(self.bindings.text(ctx, Action::Zoom(20)))).clicked() {
                 need = 0.2;
             }
-            if ui.add(egui::Button::new("0.1").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Num0)))).clicked() {
+            if ui.add(egui::Button::new("0.1").shortcut_text(self.bindings.text(ctx, Action::Zoom(10)))).clicked() {
                 need = 0.1;
             }
             if need != -2.0 {
@@ -762,52 +930,40 @@ impl ImageViewer {
 
         // channels menu
         show_menu!(self.menvar, ctx, Menu::Channels, ui, {
-            let red_button = egui::Button::new(format!( "Red{}",
+            let red_button = egui::Button::new(format!( "{}{}", t!("menu-red"),
                 if self.color_settings.show_r { "✔" } else { "" }
             ))
-            .shortcut_text(ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::R,
-            )));
+            .shortcut_text(self.bindings.text(ctx, Action::ToggleRed));
             if ui.add(red_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.color_settings.show_r = !self.color_settings.show_r;
                 self.review(ctx, true, false);
             }
 
-            let green_button = egui::Button::new(format!( "Green{}",
+            let green_button = egui::Button::new(format!( "{}{}", t!("menu-green"),
                 if self.color_settings.show_g { "✔" } else { "" }
             ))
-            .shortcut_text(ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::G,
-            )));
+            .shortcut_text(self.bindings.text(ctx, Action::ToggleGreen));
             if ui.add(green_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.color_settings.show_g = !self.color_settings.show_g;
                 self.review(ctx, true, false);
             }
 
-            let blue_button = egui::Button::new(format!( "Blue{}",
+            let blue_button = egui::Button::new(format!( "{}{}", t!("menu-blue"),
                 if self.color_settings.show_b { "✔" } else { "" }
             ))
-            .shortcut_text(ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::B,
-            )));
+            .shortcut_text(self.bindings.text(ctx, Action::ToggleBlue));
             if ui.add(blue_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.color_settings.show_b = !self.color_settings.show_b;
                 self.review(ctx, true, false);
             }
 
-            let invert_button = egui::Button::new(format!( "Invert{}",
+            let invert_button = egui::Button::new(format!( "{}{}", t!("menu-invert"),
                 if self.color_settings.invert { "✔" } else { "" }
             ))
-            .shortcut_text(ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::I,
-            )));
+            .shortcut_text(self.bindings.text(ctx, Action::Invert));
             if ui.add(invert_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.color_settings.invert = !self.color_settings.invert;
@@ -817,20 +973,15 @@ impl ImageViewer {
 
         // rotate menu
         show_menu!(self.menvar, ctx, Menu::Orientation, ui, {
-            let up_button = egui::Button::new("Up").shortcut_text(ctx.format_shortcut(
-                &egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::ArrowUp),
-            ));
+            let up_button = egui::Button::new(t!("menu-rotate-180")).shortcut_text(self.bindings.text(ctx, Action::Rotate180));
             if ui.add(up_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
                 self.color_settings.orientation.rotate_up();
                 self.review(ctx, true, false);
             }
 
-            let right_button = egui::Button::new("Right").shortcut_text(
-                ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                    egui::Modifiers::COMMAND,
-                    egui::Key::ArrowRight,
-                )),
+            let right_button = egui::Button::new(t!("menu-rotate-right")).shortcut_text(
+                self.bindings.text(ctx, Action::RotateRight),
             );
             if ui.add(right_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
@@ -838,11 +989,8 @@ impl ImageViewer {
                 self.review(ctx, true, true);
             }
 
-            let left_button = egui::Button::new("Left").shortcut_text(
-                ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                    egui::Modifiers::COMMAND,
-                    egui::Key::ArrowLeft,
-                )),
+            let left_button = egui::Button::new(t!("menu-rotate-left")).shortcut_text(
+                self.bindings.text(ctx, Action::RotateLeft),
             );
             if ui.add(left_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
@@ -850,11 +998,8 @@ impl ImageViewer {
                 self.review(ctx, true, true);
             }
 
-            let down_button = egui::Button::new("Stand").shortcut_text(
-                ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                    egui::Modifiers::COMMAND,
-                    egui::Key::ArrowDown,
-                )),
+            let down_button = egui::Button::new(t!("menu-rotate-reset")).shortcut_text(
+                self.bindings.text(ctx, Action::RotateReset),
             );
             if ui.add(down_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
@@ -866,11 +1011,8 @@ impl ImageViewer {
                 self.review(ctx, true, r);
             }
 
-            let hflip_button = egui::Button::new("Horizontal ↔").shortcut_text(
-                ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                    egui::Modifiers::COMMAND|egui::Modifiers::ALT,
-                    egui::Key::ArrowLeft,
-                )),
+            let hflip_button = egui::Button::new(t!("menu-flip-h")).shortcut_text(
+                self.bindings.text(ctx, Action::FlipHorizontal),
             );
             if ui.add(hflip_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
@@ -878,11 +1020,8 @@ impl ImageViewer {
                 self.review(ctx, true, true);
             }
 
-            let vflip_button = egui::Button::new("Vertical ↕").shortcut_text(
-                ctx.format_shortcut(&egui::KeyboardShortcut::new(
-                    egui::Modifiers::COMMAND|egui::Modifiers::ALT,
-                    egui::Key::ArrowUp,
-                )),
+            let vflip_button = egui::Button::new(t!("menu-flip-v")).shortcut_text(
+                self.bindings.text(ctx, Action::FlipVertical),
             );
             if ui.add(vflip_button).clicked() {
                 self.menvar.change_menu(ctx,Menu::None);
@@ -895,32 +1034,32 @@ impl ImageViewer {
         // backgrounds menu
         show_menu!(self.menvar, ctx, Menu::Backgrounds, ui, {
             let mut changed = false;
-            if ui.radio_value(&mut self.bg_style, BackgroundStyle::Black, "Black").clicked()
+            if ui.radio_value(&mut self.bg_style, BackgroundStyle::Black, t!("menu-bg-black")).clicked()
             {
                 changed = true;
             }
-            if ui.radio_value(&mut self.bg_style, BackgroundStyle::Gray, "Gray").clicked()
+            if ui.radio_value(&mut self.bg_style, BackgroundStyle::Gray, t!("menu-bg-gray")).clicked()
             {
                 changed = true;
             }
-            if ui.radio_value(&mut self.bg_style, BackgroundStyle::White, "White").clicked()
+            if ui.radio_value(&mut self.bg_style, BackgroundStyle::White, t!("menu-bg-white")).clicked()
             {
                 changed = true;
             }
-            if ui.radio_value(&mut self.bg_style, BackgroundStyle::Green, "Green").clicked()
+            if ui.radio_value(&mut self.bg_style, BackgroundStyle::Green, t!("menu-bg-green")).clicked()
             {
                 changed = true;
             }
             separator(ui);
-            if ui.radio_value(&mut self.bg_style,BackgroundStyle::DarkBright,"🏁 DarkBright").clicked()
+            if ui.radio_value(&mut self.bg_style,BackgroundStyle::DarkBright,t!("menu-bg-dark-bright")).clicked()
             {
                 changed = true;
             }
-            if ui.radio_value(&mut self.bg_style,BackgroundStyle::GreenMagenta,"🏁 GreenMagenta").clicked()
+            if ui.radio_value(&mut self.bg_style,BackgroundStyle::GreenMagenta,t!("menu-bg-green-magenta")).clicked()
             {
                 changed = true;
             }
-            if ui.radio_value(&mut self.bg_style,BackgroundStyle::BlackBrown,"🏁 BlackBrown").clicked()
+            if ui.radio_value(&mut self.bg_style,BackgroundStyle::BlackBrown,t!("menu-bg-black-brown")).clicked()
             {
                 changed = true;
             }
@@ -928,7 +1067,16 @@ impl ImageViewer {
                 self.menvar.change_menu(ctx,Menu::None);
             }
         });
-        
+
+        // language menu
+        show_menu!(self.menvar, ctx, Menu::Language, ui, {
+            for lang in Language::ALL {
+                if ui.selectable_label(language() == lang, lang.name()).clicked() {
+                    set_language(lang);
+                    self.menvar.change_menu(ctx,Menu::None);
+                }
+            }
+        });
 
     }
 
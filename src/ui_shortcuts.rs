@@ -1,4 +1,5 @@
 use crate::colors::*;
+use crate::bindings::Action;
 use crate::ImageViewer;
 
 impl ImageViewer {
@@ -17,503 +18,188 @@ impl ImageViewer {
             }
             self.modifiers = modifiers;
         }
-        
-       // Gyorsbillentyűk figyelése
-       // release section
-        if ctx.input(|i| i.key_released(egui::Key::C) && i.modifiers.command && i.modifiers.shift ) {
-            // copy view
-            self.save_original = false;
-            self.copy_to_clipboard();
-        }
-        else if ctx.input(|i| i.key_released(egui::Key::C) && i.modifiers.command ) {
-            // copy
-            self.save_original = true;
-            self.copy_to_clipboard();
-        }
-        else if ctx.input(|i| i.key_released(egui::Key::V) && i.modifiers.command && i.modifiers.shift) {
-        }
-        else if ctx.input(|i| i.key_released(egui::Key::V) && i.modifiers.command) {
-            // paste
-            self.copy_from_clipboard(ctx);
+
+        // új hozzárendelés rögzítése közben nem futnak a műveletek
+        if self.capture_binding(ctx) {
+            return;
         }
-        else if ctx.input(|i| i.key_released(egui::Key::X) && i.modifiers.command && i.modifiers.shift) {
-            // change view
-            self.save_original = false;
-            self.change_with_clipboard(ctx);
+
+        // szövegmezőben gépelve ne fussanak a gyorsbillentyűk (más fókuszált elem, pl. gomb vagy csúszka nem tiltja)
+        let typing = ctx.memory(|m| m.focused())
+            .is_some_and(|id| egui::text_edit::TextEditState::load(ctx, id).is_some());
+        if typing {
+            return;
         }
-        else if ctx.input(|i| i.key_released(egui::Key::X) && i.modifiers.command) {
-            // change
-            self.save_original = true;
-            self.change_with_clipboard(ctx);
+
+        // Gyorsbillentyűk figyelése a hozzárendelések szerint
+        for (action, amount) in self.bindings.triggered(ctx) {
+            self.run_action(ctx, action, amount);
         }
 
-        if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::SHIFT,
-                egui::Key::S,
-            ))
-        }) {
-            // save view
-            self.save_original = false;
-            self.starting_save(&None);
-            
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::R,
-            ))
-        }) {
-            // red channel
-            self.color_settings.show_r = !self.color_settings.show_r;
-            self.review(ctx, true, false);
-            
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::G,
-            ))
-        }) {
-            // green channel
-            self.color_settings.show_g = !self.color_settings.show_g;
-            self.review(ctx, true, false);
-            
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::B,
-            ))
-        }) {
-            // blue channel
-            self.color_settings.show_b = !self.color_settings.show_b;
-            self.review(ctx, true, false);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::I,
-            ))
-        }) {
-            // invert color
-            self.color_settings.invert = !self.color_settings.invert;
-            self.review(ctx, true, false);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND|egui::Modifiers::ALT,
-                egui::Key::ArrowUp,
-            ))
-        }) {
-            // flip vertical
-            self.color_settings.orientation.flip_v();
-            self.review(ctx, true, false);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND|egui::Modifiers::ALT,
-                egui::Key::ArrowLeft,
-            ))
-        }) {
-            // flip horizontal
-            self.color_settings.orientation.flip_h();
-            self.review(ctx, true, false);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::ArrowUp,
-            ))
-        }) {
-            // rotate 180
-            self.color_settings.orientation.rotate_up();
-            self.review(ctx, true, false);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::ArrowLeft,
-            ))
-        }) {
-            // rotate -90
-            self.color_settings.orientation.rotate_left();
-            self.review(ctx,true, true);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::ArrowRight,
-            ))
-        }) {
-            // rotate 90
-            self.color_settings.orientation.rotate_right();
-            self.review(ctx, true, true);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::ArrowDown,
-            ))
-        }) {
-            // rotate  to 0
-            let rot = self.color_settings.orientation == Orientation::Rotate90
-                || self.color_settings.orientation == Orientation::Rotate270
-                || self.color_settings.orientation == Orientation::Rotate90F
-                || self.color_settings.orientation == Orientation::Rotate270F;
-            self.color_settings.orientation = Orientation::Rotate0;
-            self.review(ctx, true, rot);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::C,
-            ))
-        }) {
-            // Színkorrekció
-            self.color_correction_dialog = !self.color_correction_dialog;
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::O,
-            ))
-        }) {
-            // open
-            self.open_image_dialog(ctx, &None);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::R,
-            ))
-        }) {
-            // reopen
-            self.load_image(ctx, true);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::S,
-            ))
-        }) {
-            // save
-            self.save_original = true;
-            self.starting_save(&None);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::N,
-            ))
-        }) {
-            // next
-            self.navigation(ctx, 1);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::G,
-            ))
-        }) {
-            // background rotate
-            self.bg_style = self.bg_style.clone().inc();
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::B,
-            ))
-        }) {
-            // before
-            self.navigation(ctx, -1);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::I,
-            ))
-        }) {
-            // info
-            self.show_info = !self.show_info;
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num0,
-            ))
-        }) {
-            // 0
-            if self.magnify != 10.0 {
-                self.want_magnify = 10.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num1,
-            ))
-        }) {
-            // 1
-            if self.magnify != 1.0 {
-                self.want_magnify = 1.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num2,
-            ))
-        }) {
-            // 2
-            if self.magnify != 2.0 {
-                self.want_magnify = 2.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num3,
-            ))
-        }) {
-            // 3
-            if self.magnify != 3.0 {
-                self.want_magnify = 3.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num4,
-            ))
-        }) {
-            // 4
-            if self.magnify != 4.0 {
-                self.want_magnify = 4.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num5,
-            ))
-        }) {
-            // 5
-            if self.magnify != 5.0 {
-                self.want_magnify = 5.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num6,
-            ))
-        }) {
-            if self.magnify != 6.0 {
-                self.want_magnify = 6.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num7,
-            ))
-        }) {
-            if self.magnify != 7.0 {
-                self.want_magnify = 7.0;
-                self.review(ctx,true, false);
-            }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num8,
-            ))
-        }) {
-            if self.magnify != 8.0 {
-                self.want_magnify = 8.0;
-                self.review(ctx,true, false);
+        // eating default menu text magnify
+        ctx.input_mut(|i| {
+            i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Plus));
+            i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Minus));
+        });
+    }
+
+    /// Egy művelet végrehajtása; görgetéses nagyításnál `amount` a görgetés mértéke
+    pub fn run_action(&mut self, ctx: &egui::Context, action: Action, amount: Option<f32>) {
+        match action {
+            Action::Open => self.open_image_dialog(ctx, &None),
+            Action::Reopen => self.load_image(ctx, true),
+            Action::Compare => self.open_compare_dialog(ctx),
+            Action::Save => {
+                self.save_original = true;
+                self.starting_save(&None);
+            }
+            Action::SaveView => {
+                self.save_original = false;
+                self.starting_save(&None);
+            }
+            Action::Resize => self.open_resize_dialog(),
+            Action::Copy => {
+                self.save_original = true;
+                self.copy_to_clipboard();
+            }
+            Action::CopyView => {
+                self.save_original = false;
+                self.copy_to_clipboard();
+            }
+            Action::Paste => self.copy_from_clipboard(ctx),
+            Action::Change => {
+                self.save_original = true;
+                self.change_with_clipboard(ctx);
+            }
+            Action::ChangeView => {
+                self.save_original = false;
+                self.change_with_clipboard(ctx);
+            }
+            Action::Next => self.navigation(ctx, 1),
+            Action::Previous => self.navigation(ctx, -1),
+            Action::Undo => self.undo(ctx),
+            Action::Redo => self.redo(ctx),
+            Action::History => self.show_history = !self.show_history,
+            Action::ToggleRed => {
+                self.color_settings.show_r = !self.color_settings.show_r;
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Num9,
-            ))
-        }) {
-            if self.magnify != 9.0 {
-                self.want_magnify = 9.0;
-                self.review(ctx,true, false);
+            Action::ToggleGreen => {
+                self.color_settings.show_g = !self.color_settings.show_g;
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num0,
-            ))
-        }) {
-            if self.magnify != 0.1 {
-                self.want_magnify = 0.1;
-                self.review(ctx,true, false);
+            Action::ToggleBlue => {
+                self.color_settings.show_b = !self.color_settings.show_b;
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num1,
-            ))
-        }) {
-            if self.magnify != 0.8 {
-                self.want_magnify = 0.8;
-                self.review(ctx,true, false);
+            Action::Invert => {
+                self.color_settings.invert = !self.color_settings.invert;
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num2,
-            ))
-        }) {
-            if self.magnify != 0.75 {
-                self.want_magnify = 0.75;
-                self.review(ctx,true, false);
+            Action::RotateRight => {
+                self.color_settings.orientation.rotate_right();
+                self.review(ctx, true, true);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num3,
-            ))
-        }) {
-            if self.magnify != 0.5 {
-                self.want_magnify = 0.5;
-                self.review(ctx,true, false);
+            Action::RotateLeft => {
+                self.color_settings.orientation.rotate_left();
+                self.review(ctx, true, true);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num4,
-            ))
-        }) {
-            if self.magnify != 0.45 {
-                self.want_magnify = 0.45;
-                self.review(ctx,true, false);
+            Action::Rotate180 => {
+                self.color_settings.orientation.rotate_up();
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num5,
-            ))
-        }) {
-            if self.magnify != 0.4 {
-                self.want_magnify = 0.4;
-                self.review(ctx,true, false);
+            Action::RotateReset => {
+                let rot = self.color_settings.orientation == Orientation::Rotate90
+                    || self.color_settings.orientation == Orientation::Rotate270
+                    || self.color_settings.orientation == Orientation::Rotate90F
+                    || self.color_settings.orientation == Orientation::Rotate270F;
+                self.color_settings.orientation = Orientation::Rotate0;
+                self.review(ctx, true, rot);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num6,
-            ))
-        }) {
-            if self.magnify != 0.35 {
-                self.want_magnify = 0.35;
-                self.review(ctx,true, false);
+            Action::FlipHorizontal => {
+                self.color_settings.orientation.flip_h();
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num7,
-            ))
-        }) {
-            if self.magnify != 0.3 {
-                self.want_magnify = 0.3;
-                self.review(ctx,true, false);
+            Action::FlipVertical => {
+                self.color_settings.orientation.flip_v();
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num8,
-            ))
-        }) {
-            if self.magnify != 0.25 {
-                self.want_magnify = 0.25;
-                self.review(ctx,true, false);
+            Action::ColorCorrection => self.color_correction_dialog = !self.color_correction_dialog,
+            Action::Crop => self.toggle_crop_tool(),
+            Action::Info => self.show_info = !self.show_info,
+            Action::SplitView => self.toggle_split_view(ctx),
+            Action::SplitDirection => self.split_vertical = !self.split_vertical,
+            Action::ThumbnailGrid => self.show_grid = !self.show_grid,
+            Action::Filmstrip => self.show_filmstrip = !self.show_filmstrip,
+            Action::CompareMode => self.toggle_compare_mode(ctx),
+            Action::Blink => self.blink_compare(ctx),
+            Action::Slideshow => self.toggle_slideshow(),
+            Action::Fullscreen => self.toggle_fullscreen(ctx),
+            Action::Background => self.bg_style = self.bg_style.clone().inc(),
+            Action::ZoomIn | Action::ZoomOut => {
+                let sign = if action == Action::ZoomIn { 1.0 } else { -1.0 };
+                match amount {
+                    Some(delta) => {
+                        // görgetésnél az egér helyén nagyít
+                        self.change_magnify = sign * delta;
+                        self.mouse_zoom = true;
+                    }
+                    None => self.change_magnify = sign,
+                }
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Num9,
-            ))
-        }) {
-            if self.magnify != 0.2 {
-                self.want_magnify = 0.2;
-                self.review(ctx,true, false);
+            Action::ZoomFit => {
+                self.want_magnify = -1.0;
+                self.review(ctx, true, false);
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::F,
-            ))
-        }) {
-            // f
-            self.want_magnify = -1.0;
-            self.review(ctx,true, false);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::ArrowLeft,
-            ))
-        }) {
-            self.anim_prev_frame(ctx);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::ArrowRight,
-            ))
-        }) {
-            self.anim_next_frame(ctx);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Space,
-            ))
-        }) {
-            self.anim_play_stop(ctx);
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Escape,
-            )) ||
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::NONE,
-                egui::Key::Enter,
-            ))
-        }) {
-            // quit
-            if self.color_correction_dialog {
-                self.color_correction_dialog = false;
-            } else if self.show_info {
-                self.show_info = false;
-            } else if let Some(_adatok) = &mut self.save_dialog {
-                self.save_dialog = None;
-            } else /*if self.show_recent_window {
-                self.show_recent_window = false;
-            } else*/ if self.show_about_window {
-                self.show_about_window = false;
-            } else {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            Action::Zoom(percent) => {
+                let magnify = percent as f32 / 100.0;
+                if self.magnify != magnify {
+                    self.want_magnify = magnify;
+                    self.review(ctx, true, false);
+                }
             }
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Plus,
-            ))
-        }) {
-            // eating default menu text magnify
-        } else if ctx.input_mut(|i| {
-            i.consume_shortcut(&egui::KeyboardShortcut::new(
-                egui::Modifiers::COMMAND,
-                egui::Key::Minus,
-            ))
-        }) {
-            // eating default menu text magnify
+            Action::Preset(index) => self.apply_preset(ctx, index as usize),
+            Action::PlayPause => self.anim_play_stop(ctx),
+            Action::PrevFrame => self.anim_prev_frame(ctx),
+            Action::NextFrame => self.anim_next_frame(ctx),
+            Action::Back => self.back(ctx),
+        }
+    }
+
+    /// Esc: a legfelső megnyitott dolgot zárja be, végül kilép
+    fn back(&mut self, ctx: &egui::Context) {
+        if self.pending_load.is_some() {
+            self.cancel_load();
+        } else if self.preset_name.is_some() {
+            self.preset_name = None;
+        } else if self.bindings_dialog.is_some() {
+            self.bindings_dialog = None;
+        } else if self.slideshow.is_some() {
+            self.stop_slideshow();
+        } else if self.show_grid {
+            self.show_grid = false;
+        } else if self.compare.is_some() {
+            self.close_compare();
+        } else if self.crop_tool {
+            self.close_crop_tool();
+        } else if self.color_correction_dialog {
+            self.color_correction_dialog = false;
+        } else if self.show_info {
+            self.show_info = false;
+        } else if let Some(_adatok) = &mut self.save_dialog {
+            self.save_dialog = None;
+        } else if self.resize_dialog.is_some() {
+            self.resize_dialog = None;
+        } else if self.show_history {
+            self.show_history = false;
+        } else /*if self.show_recent_window {
+            self.show_recent_window = false;
+        } else*/ if self.show_about_window {
+            self.show_about_window = false;
+        } else if self.fullscreen {
+            self.toggle_fullscreen(ctx);
         } else {
-            ctx.input(|i| {
-                if i.modifiers.command {
-                    for event in &i.events {
-                        if let egui::Event::MouseWheel { unit: _, delta, .. } = event {
-                            self.change_magnify = delta.y;
-                            if self.change_magnify != 0.0 {
-                                self.mouse_zoom = true;
-                            }
-                        }
-                    }
-                } else {
-                    // magnify without command and text magnify
-                    if i.key_pressed(egui::Key::Plus) {
-                        // bigger
-                        self.change_magnify = 1.0;
-                    } else if i.key_pressed(egui::Key::Minus) {
-                        // smaller
-                        self.change_magnify = -1.0;
-                    }
-                }
-            });
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
         }
     }
-}
\ No newline at end of file
+}